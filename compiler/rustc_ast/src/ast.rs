@@ -2045,6 +2045,7 @@ pub enum InlineAsmOperand {
 pub struct InlineAsm {
     pub template: Vec<InlineAsmTemplatePiece>,
     pub operands: Vec<(InlineAsmOperand, Span)>,
+    pub clobber_abi: Option<(Symbol, Span)>,
     pub options: InlineAsmOptions,
     pub line_spans: Vec<Span>,
 }
@@ -2319,9 +2320,11 @@ pub struct ForeignMod {
 /// Global inline assembly.
 ///
 /// Also known as "module-level assembly" or "file-scoped assembly".
-#[derive(Clone, Encodable, Decodable, Debug, Copy)]
+#[derive(Clone, Encodable, Decodable, Debug)]
 pub struct GlobalAsm {
     pub asm: Symbol,
+    /// The span of each line of `asm`, used to point errors at the right line.
+    pub line_spans: Vec<Span>,
 }
 
 #[derive(Clone, Encodable, Decodable, Debug)]
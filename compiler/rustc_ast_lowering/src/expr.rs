@@ -976,10 +976,40 @@ impl<'hir> LoweringContext<'_, 'hir> {
                 .emit();
         }
 
+        let mut clobber_abi = None;
+        if let Some(asm_arch) = self.sess.asm_arch {
+            if let Some((abi_name, abi_span)) = asm.clobber_abi {
+                match asm::InlineAsmClobberAbi::parse(asm_arch, &self.sess.target, abi_name) {
+                    Ok(abi) => clobber_abi = Some(abi),
+                    Err(&[]) => {
+                        self.sess
+                            .struct_span_err(
+                                abi_span,
+                                "`clobber_abi` is not supported on this target",
+                            )
+                            .emit();
+                    }
+                    Err(supported_abis) => {
+                        let mut err =
+                            self.sess.struct_span_err(abi_span, "invalid ABI for `clobber_abi`");
+                        let mut abis = format!("`{}`", supported_abis[0]);
+                        for m in &supported_abis[1..] {
+                            let _ = write!(abis, ", `{}`", m);
+                        }
+                        err.note(&format!(
+                            "the following ABIs are supported on this target: {}",
+                            abis
+                        ));
+                        err.emit();
+                    }
+                }
+            }
+        }
+
         // Lower operands to HIR, filter_map skips any operands with invalid
         // register classes.
         let sess = self.sess;
-        let operands: Vec<_> = asm
+        let mut operands: Vec<_> = asm
             .operands
             .iter()
             .filter_map(|(op, op_sp)| {
@@ -1129,10 +1159,25 @@ impl<'hir> LoweringContext<'_, 'hir> {
         for (idx, op) in operands.iter().enumerate() {
             let op_sp = asm.operands[idx].1;
             if let Some(reg) = op.reg() {
+                // Some registers may only be clobbered: they have to be named
+                // explicitly and can't have a value passed in or out.
+                let reg_class = reg.reg_class();
+                if reg_class.is_clobber_only() {
+                    let is_clobber = matches!(op, hir::InlineAsmOperand::Out { expr: None, .. });
+                    if !is_clobber || !matches!(reg, asm::InlineAsmRegOrRegClass::Reg(_)) {
+                        let msg = format!(
+                            "register class `{}` can only be used as a clobber, \
+                             not as an input or output",
+                            reg_class.name()
+                        );
+                        sess.struct_span_err(op_sp, &msg).emit();
+                        continue;
+                    }
+                }
+
                 // Validate register classes against currently enabled target
                 // features. We check that at least one type is available for
                 // the current target.
-                let reg_class = reg.reg_class();
                 let mut required_features: Vec<&str> = vec![];
                 for &(_, feature) in reg_class.supported_types(asm_arch) {
                     if let Some(feature) = feature {
@@ -1248,6 +1293,28 @@ impl<'hir> LoweringContext<'_, 'hir> {
             }
         }
 
+        // If a clobber_abi is specified, add the necessary clobbers to the
+        // operands list. Registers which are already used as explicit outputs
+        // are left alone.
+        if let Some(abi) = clobber_abi {
+            for &clobber in abi.clobbered_regs() {
+                let mut output_used = false;
+                clobber.overlapping_regs(|reg| {
+                    if used_output_regs.contains_key(&reg) {
+                        output_used = true;
+                    }
+                });
+
+                if !output_used {
+                    operands.push(hir::InlineAsmOperand::Out {
+                        reg: asm::InlineAsmRegOrRegClass::Reg(clobber),
+                        late: true,
+                        expr: None,
+                    });
+                }
+            }
+        }
+
         let operands = self.arena.alloc_from_iter(operands);
         let template = self.arena.alloc_from_iter(asm.template.iter().cloned());
         let line_spans = self.arena.alloc_slice(&asm.line_spans[..]);
@@ -711,8 +711,9 @@ impl<'hir> LoweringContext<'_, 'hir> {
         }
     }
 
-    fn lower_global_asm(&mut self, ga: &GlobalAsm) -> &'hir hir::GlobalAsm {
-        self.arena.alloc(hir::GlobalAsm { asm: ga.asm })
+    fn lower_global_asm(&mut self, ga: &GlobalAsm) -> &'hir hir::GlobalAsm<'hir> {
+        let line_spans = self.arena.alloc_slice(&ga.line_spans[..]);
+        self.arena.alloc(hir::GlobalAsm { asm: ga.asm, line_spans })
     }
 
     fn lower_variant(&mut self, v: &Variant) -> hir::Variant<'hir> {
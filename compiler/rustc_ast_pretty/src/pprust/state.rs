@@ -2115,12 +2115,16 @@ impl<'a> State<'a> {
                 enum AsmArg<'a> {
                     Template(String),
                     Operand(&'a InlineAsmOperand),
+                    ClobberAbi(Symbol),
                     Options(InlineAsmOptions),
                 }
 
                 let mut args = vec![];
                 args.push(AsmArg::Template(InlineAsmTemplatePiece::to_string(&a.template)));
                 args.extend(a.operands.iter().map(|(o, _)| AsmArg::Operand(o)));
+                if let Some((abi, _)) = a.clobber_abi {
+                    args.push(AsmArg::ClobberAbi(abi));
+                }
                 if !a.options.is_empty() {
                     args.push(AsmArg::Options(a.options));
                 }
@@ -2191,6 +2195,12 @@ impl<'a> State<'a> {
                             }
                         }
                     }
+                    AsmArg::ClobberAbi(abi) => {
+                        s.word("clobber_abi");
+                        s.popen();
+                        s.print_symbol(*abi, ast::StrStyle::Cooked);
+                        s.pclose();
+                    }
                     AsmArg::Options(opts) => {
                         s.word("options");
                         s.popen();
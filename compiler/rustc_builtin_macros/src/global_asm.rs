@@ -14,8 +14,9 @@ use rustc_ast::token;
 use rustc_ast::tokenstream::TokenStream;
 use rustc_errors::DiagnosticBuilder;
 use rustc_expand::base::{self, *};
-use rustc_span::symbol::Ident;
-use rustc_span::Span;
+use rustc_lexer::unescape::{unescape_literal, Mode};
+use rustc_span::symbol::{Ident, Symbol};
+use rustc_span::{InnerSpan, Span};
 use smallvec::smallvec;
 
 pub fn expand_global_asm<'cx>(
@@ -59,10 +60,56 @@ fn parse_global_asm<'a>(
     }
 
     let expr = p.parse_expr()?;
-    let (asm, _) = match expr_to_string(cx, expr, "inline assembly must be a string literal") {
-        Some((s, st)) => (s, st),
-        None => return Ok(None),
+    let msg = "inline assembly must be a string literal";
+    let (asm, style, asm_sp) = match expr_to_spanned_string(cx, expr, msg) {
+        Ok(asm) => asm,
+        Err(err) => {
+            if let Some(mut err) = err {
+                err.emit();
+            }
+            return Ok(None);
+        }
     };
+    let line_spans = line_spans(cx, asm, style, asm_sp);
+
+    Ok(Some(ast::GlobalAsm { asm, line_spans }))
+}
 
-    Ok(Some(ast::GlobalAsm { asm }))
+/// Finds the span of each line of `asm`, without its indentation, in the string
+/// literal at `sp` it was parsed from. If that isn't possible, e.g. because the
+/// string came from `concat!`, every line gets the span of the whole string.
+fn line_spans(cx: &ExtCtxt<'_>, asm: Symbol, style: ast::StrStyle, sp: Span) -> Vec<Span> {
+    let asm = asm.as_str();
+    let snippet = cx.source_map().span_to_snippet(sp).ok();
+    let inner_spans = snippet.and_then(|snippet| {
+        let (mode, prefix_len, suffix_len) = match style {
+            ast::StrStyle::Cooked => (Mode::Str, 1, 1),
+            ast::StrStyle::Raw(n) => (Mode::RawStr, 2 + n as usize, 1 + n as usize),
+        };
+        let body = snippet.get(prefix_len..snippet.len().checked_sub(suffix_len)?)?;
+
+        // Unescape the literal again to find where each line starts in it.
+        let mut unescaped = String::with_capacity(asm.len());
+        let mut inner_spans = vec![];
+        let mut line_start = None;
+        unescape_literal(body, mode, &mut |range, c| {
+            if let Ok(c) = c {
+                unescaped.push(c);
+                if c == '\n' {
+                    let end = prefix_len + range.start;
+                    inner_spans.push(InnerSpan::new(line_start.take().unwrap_or(end), end));
+                } else if line_start.is_none() && c != ' ' && c != '\t' {
+                    line_start = Some(prefix_len + range.start);
+                }
+            }
+        });
+        let end = prefix_len + body.len();
+        inner_spans.push(InnerSpan::new(line_start.unwrap_or(end), end));
+        (unescaped == *asm).then_some(inner_spans)
+    });
+
+    match inner_spans {
+        Some(inner_spans) => inner_spans.into_iter().map(|inner| sp.from_inner(inner)).collect(),
+        None => vec![sp; 1 + asm.matches('\n').count()],
+    }
 }
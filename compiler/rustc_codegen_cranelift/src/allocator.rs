@@ -4,6 +4,7 @@
 use crate::prelude::*;
 
 use rustc_ast::expand::allocator::{AllocatorKind, AllocatorTy, ALLOCATOR_METHODS};
+use rustc_session::config::OomStrategy;
 use rustc_span::symbol::sym;
 
 /// Returns whether an allocator shim was created
@@ -19,7 +20,7 @@ pub(crate) fn codegen(
     if any_dynamic_crate {
         false
     } else if let Some(kind) = tcx.allocator_kind() {
-        codegen_inner(module, unwind_context, kind);
+        codegen_inner(module, unwind_context, kind, tcx.sess.opts.debugging_opts.oom);
         true
     } else {
         false
@@ -30,6 +31,7 @@ fn codegen_inner(
     module: &mut impl Module,
     unwind_context: &mut UnwindContext<'_>,
     kind: AllocatorKind,
+    oom_strategy: OomStrategy,
 ) {
     let usize_ty = module.target_config().pointer_type();
 
@@ -150,4 +152,13 @@ fn codegen_inner(
         )
         .unwrap();
     unwind_context.add_function(func_id, &ctx, module.isa());
+
+    let data_id = module
+        .declare_data(OomStrategy::SYMBOL, Linkage::Export, false, false)
+        .unwrap();
+    let mut data_ctx = DataContext::new();
+    data_ctx.set_align(1);
+    let val = oom_strategy.should_panic();
+    data_ctx.define(Box::new([val]));
+    module.define_data(data_id, &data_ctx).unwrap();
 }
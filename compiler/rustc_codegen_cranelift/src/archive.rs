@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 
 use rustc_codegen_ssa::back::archive::{find_library, ArchiveBuilder};
 use rustc_codegen_ssa::METADATA_FILENAME;
+use rustc_middle::middle::cstore::DllImport;
 use rustc_session::Session;
 
 use object::{Object, SymbolKind};
@@ -63,7 +64,8 @@ impl<'a> ArchiveBuilder<'a> for ArArchiveBuilder<'a> {
             sess,
             dst: output.to_path_buf(),
             lib_search_paths: archive_search_paths(sess),
-            use_gnu_style_archive: sess.target.options.archive_format == "gnu",
+            // COFF archives use the GNU member name and symbol table layout
+            use_gnu_style_archive: matches!(&*sess.target.options.archive_format, "gnu" | "coff"),
             // FIXME fix builtin ranlib on macOS
             no_builtin_ranlib: sess.target.options.is_like_osx,
 
@@ -276,6 +278,15 @@ impl<'a> ArchiveBuilder<'a> for ArArchiveBuilder<'a> {
             }
         }
     }
+
+    fn create_dll_import_lib(
+        sess: &Session,
+        _lib_name: &str,
+        _dll_imports: &[DllImport],
+        _output: &Path,
+    ) {
+        sess.fatal("`raw-dylib` libraries are not yet supported by cg_clif");
+    }
 }
 
 impl<'a> ArArchiveBuilder<'a> {
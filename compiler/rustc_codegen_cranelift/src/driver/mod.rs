@@ -87,7 +87,7 @@ fn trans_mono_item<'tcx, M: Module>(
         }
         MonoItem::GlobalAsm(hir_id) => {
             let item = tcx.hir().expect_item(hir_id);
-            if let rustc_hir::ItemKind::GlobalAsm(rustc_hir::GlobalAsm { asm }) = item.kind {
+            if let rustc_hir::ItemKind::GlobalAsm(rustc_hir::GlobalAsm { asm, .. }) = item.kind {
                 cx.global_asm.push_str(&*asm.as_str());
                 cx.global_asm.push_str("\n\n");
             } else {
@@ -884,6 +884,37 @@ pub(crate) fn codegen_intrinsic_call<'tcx>(
             ret.write_cvalue(fx, val);
         };
 
+        raw_eq, <T> (v lhs_ptr, v rhs_ptr) {
+            let size = fx.layout_of(T).size.bytes();
+            let is_eq_value = if size == 0 {
+                fx.bcx.ins().iconst(types::I8, 1)
+            } else {
+                // FIXME emit an inline comparison for small sizes
+                let bytes_val = fx.bcx.ins().iconst(fx.pointer_type, size as i64);
+                let cmp = fx.lib_call(
+                    "memcmp",
+                    vec![fx.pointer_type, fx.pointer_type, fx.pointer_type],
+                    vec![types::I32],
+                    &[lhs_ptr, rhs_ptr, bytes_val],
+                )[0];
+                let is_eq = fx.bcx.ins().icmp_imm(IntCC::Equal, cmp, 0);
+                fx.bcx.ins().bint(types::I8, is_eq)
+            };
+            ret.write_cvalue(fx, CValue::by_val(is_eq_value, ret.layout()));
+        };
+
+        raw_eq_bytes, (v lhs_ptr, v rhs_ptr, v len) {
+            let cmp = fx.lib_call(
+                "memcmp",
+                vec![fx.pointer_type, fx.pointer_type, fx.pointer_type],
+                vec![types::I32],
+                &[lhs_ptr, rhs_ptr, len],
+            )[0];
+            let is_eq = fx.bcx.ins().icmp_imm(IntCC::Equal, cmp, 0);
+            let is_eq_value = fx.bcx.ins().bint(types::I8, is_eq);
+            ret.write_cvalue(fx, CValue::by_val(is_eq_value, ret.layout()));
+        };
+
         ptr_guaranteed_eq, (c a, c b) {
             let val = crate::num::trans_ptr_binop(fx, BinOp::Eq, a, b);
             ret.write_cvalue(fx, val);
@@ -238,6 +238,7 @@ impl CodegenBackend for CraneliftCodegenBackend {
                 outputs,
                 &codegen_results.crate_name.as_str(),
                 &target_cpu,
+                None,
             );
         });
 
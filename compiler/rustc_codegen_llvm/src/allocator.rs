@@ -1,9 +1,13 @@
 use crate::attributes;
 use libc::c_uint;
-use rustc_ast::expand::allocator::{AllocatorKind, AllocatorTy, ALLOCATOR_METHODS};
+use rustc_ast::expand::allocator::{
+    AllocatorKind, AllocatorMethod, AllocatorTy, ALLOCATOR_METHODS,
+};
 use rustc_middle::bug;
 use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OomStrategy;
 use rustc_span::symbol::sym;
+use std::ptr;
 
 use crate::llvm::{self, False, True};
 use crate::ModuleLlvm;
@@ -69,6 +73,18 @@ pub(crate) unsafe fn codegen(
             llvm::LLVMRustGetOrInsertFunction(llmod, callee.as_ptr().cast(), callee.len(), ty);
         llvm::LLVMRustSetVisibility(callee, llvm::Visibility::Hidden);
 
+        // Global allocators are not allowed to unwind, and the pointers they
+        // return don't alias anything else.
+        for &f in &[llfn, callee] {
+            llvm::Attribute::NoUnwind.apply_llfn(llvm::AttributePlace::Function, f);
+            if output.is_some() {
+                llvm::Attribute::NoAlias.apply_llfn(llvm::AttributePlace::ReturnValue, f);
+            }
+            if let Some(arg) = alloc_size_arg(method) {
+                llvm::LLVMRustAddAllocSizeAttr(f, arg as c_uint);
+            }
+        }
+
         let llbb = llvm::LLVMAppendBasicBlockInContext(llcx, llfn, "entry\0".as_ptr().cast());
 
         let llbuilder = llvm::LLVMCreateBuilderInContext(llcx);
@@ -78,8 +94,14 @@ pub(crate) unsafe fn codegen(
             .enumerate()
             .map(|(i, _)| llvm::LLVMGetParam(llfn, i as c_uint))
             .collect::<Vec<_>>();
-        let ret =
-            llvm::LLVMRustBuildCall(llbuilder, callee, args.as_ptr(), args.len() as c_uint, None);
+        let ret = llvm::LLVMRustBuildCall(
+            llbuilder,
+            callee,
+            args.as_ptr(),
+            args.len() as c_uint,
+            ptr::null(),
+            0,
+        );
         llvm::LLVMSetTailCall(ret, True);
         if output.is_some() {
             llvm::LLVMBuildRet(llbuilder, ret);
@@ -121,8 +143,52 @@ pub(crate) unsafe fn codegen(
         .enumerate()
         .map(|(i, _)| llvm::LLVMGetParam(llfn, i as c_uint))
         .collect::<Vec<_>>();
-    let ret = llvm::LLVMRustBuildCall(llbuilder, callee, args.as_ptr(), args.len() as c_uint, None);
+    let ret = llvm::LLVMRustBuildCall(
+        llbuilder,
+        callee,
+        args.as_ptr(),
+        args.len() as c_uint,
+        ptr::null(),
+        0,
+    );
     llvm::LLVMSetTailCall(ret, True);
     llvm::LLVMBuildRetVoid(llbuilder);
     llvm::LLVMDisposeBuilder(llbuilder);
+
+    // Tells the default alloc error hook in `std` whether to panic (`-Z oom=panic`) or to
+    // abort once it has reported the failure.
+    let name = OomStrategy::SYMBOL;
+    let ll_g = llvm::LLVMRustGetOrInsertGlobal(llmod, name.as_ptr().cast(), name.len(), i8);
+    if tcx.sess.target.options.default_hidden_visibility {
+        llvm::LLVMRustSetVisibility(ll_g, llvm::Visibility::Hidden);
+    }
+    let val = tcx.sess.opts.debugging_opts.oom.should_panic();
+    let llval = llvm::LLVMConstInt(i8, val as u64, False);
+    llvm::LLVMSetInitializer(ll_g, llval);
+}
+
+/// Returns the index of the LLVM parameter of the `method` shim that holds the
+/// size of the memory it returns: the trailing `new_size` for `realloc`, and
+/// the size of the layout otherwise.
+pub(crate) fn alloc_size_arg(method: &AllocatorMethod) -> Option<usize> {
+    if let AllocatorTy::Unit = method.output {
+        return None;
+    }
+    let mut size_arg = None;
+    let mut arg = 0;
+    for ty in method.inputs.iter() {
+        match *ty {
+            AllocatorTy::Layout => {
+                size_arg = Some(arg);
+                arg += 2;
+            }
+            AllocatorTy::Usize => {
+                size_arg = Some(arg);
+                arg += 1;
+            }
+            AllocatorTy::Ptr => arg += 1,
+            AllocatorTy::ResultPtr | AllocatorTy::Unit => panic!("invalid allocator arg"),
+        }
+    }
+    size_arg
 }
@@ -14,7 +14,7 @@ use rustc_data_structures::fx::FxHashMap;
 use rustc_hir as hir;
 use rustc_middle::span_bug;
 use rustc_middle::ty::layout::TyAndLayout;
-use rustc_span::{Pos, Span};
+use rustc_span::{Pos, Span, Symbol};
 use rustc_target::abi::*;
 use rustc_target::asm::*;
 
@@ -122,9 +122,17 @@ impl AsmBuilderMethods<'tcx> for Builder<'a, 'll, 'tcx> {
         line_spans: &[Span],
     ) {
         let asm_arch = self.tcx.sess.asm_arch.unwrap();
+        let sess = self.tcx.sess;
+        let is_target_supported = |reg_class: InlineAsmRegClass| {
+            reg_class.supported_types(asm_arch).iter().any(|&(_, feature)| {
+                feature
+                    .map_or(true, |feature| sess.target_features.contains(&Symbol::intern(feature)))
+            })
+        };
 
         // Collect the types of output operands
         let mut constraints = vec![];
+        let mut clobbers = vec![];
         let mut output_types = vec![];
         let mut op_idx = FxHashMap::default();
         for (idx, op) in operands.iter().enumerate() {
@@ -134,6 +142,17 @@ impl AsmBuilderMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                     let ty = if let Some(ref place) = place {
                         layout = Some(&place.layout);
                         llvm_fixup_output_type(self.cx, reg.reg_class(), &place.layout)
+                    } else if reg.reg_class().is_clobber_only()
+                        || !is_target_supported(reg.reg_class())
+                    {
+                        // Discarded outputs in clobber-only register classes,
+                        // or in register classes whose target feature is
+                        // disabled (e.g. from `clobber_abi`), are turned into
+                        // plain clobbers. LLVM would otherwise try to allocate
+                        // a register for the dummy output.
+                        assert!(matches!(reg, InlineAsmRegOrRegClass::Reg(_)));
+                        clobbers.push(format!("~{}", reg_to_llvm(reg, None)));
+                        continue;
                     } else {
                         // If the output is discarded, we don't really care what
                         // type is used. We're just using this to tell LLVM to
@@ -198,6 +217,9 @@ impl AsmBuilderMethods<'tcx> for Builder<'a, 'll, 'tcx> {
             }
         }
 
+        // Clobbers must come after all outputs and inputs
+        constraints.append(&mut clobbers);
+
         // Build the template string
         let mut template_str = String::new();
         for piece in template {
@@ -329,12 +351,47 @@ impl AsmBuilderMethods<'tcx> for Builder<'a, 'll, 'tcx> {
 }
 
 impl AsmMethods for CodegenCx<'ll, 'tcx> {
-    fn codegen_global_asm(&self, ga: &hir::GlobalAsm) {
-        let asm = ga.asm.as_str();
+    fn codegen_global_asm(&self, ga: &hir::GlobalAsm<'_>) {
+        self.codegen_module_asm(&ga.asm.as_str(), ga.line_spans);
+    }
+
+    fn codegen_module_asm(&self, asm: &str, line_spans: &[Span]) {
+        let start = module_asm_len(self.llmod);
         unsafe {
             llvm::LLVMRustAppendModuleInlineAsm(self.llmod, asm.as_ptr().cast(), asm.len());
         }
+        // LLVM reports errors in module-level assembly without a srcloc cookie,
+        // so remember which source line each line of it came from.
+        let end = module_asm_len(self.llmod);
+        let mut srclocs = self.module_asm_srclocs.borrow_mut();
+        let mut line_start = start;
+        for (i, line) in asm.split('\n').enumerate() {
+            let span = line_spans.get(i).or_else(|| line_spans.last()).unwrap();
+            let line_end = (line_start + line.len() + 1).min(end);
+            srclocs.push(ModuleAsmSrcLoc {
+                start: line_start,
+                end: line_end,
+                cookie: span.lo().to_u32(),
+            });
+            line_start = line_end;
+        }
+    }
+}
+
+/// The srcloc cookie for a line of the module-level assembly.
+#[derive(Clone, Copy, Debug)]
+pub struct ModuleAsmSrcLoc {
+    pub start: usize,
+    pub end: usize,
+    pub cookie: u32,
+}
+
+fn module_asm_len(llmod: &llvm::Module) -> usize {
+    let mut len = 0;
+    unsafe {
+        llvm::LLVMGetModuleInlineAsm(llmod, &mut len);
     }
+    len
 }
 
 fn inline_asm_call(
@@ -520,6 +577,7 @@ fn reg_to_llvm(reg: InlineAsmRegOrRegClass, layout: Option<&TyAndLayout<'tcx>>)
             | InlineAsmRegClass::X86(X86InlineAsmRegClass::ymm_reg) => "x",
             InlineAsmRegClass::X86(X86InlineAsmRegClass::zmm_reg) => "v",
             InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg) => "^Yk",
+            InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg0) => unreachable!("clobber-only"),
         }
         .to_string(),
     }
@@ -582,6 +640,7 @@ fn modifier_to_llvm(
             _ => unreachable!(),
         },
         InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg) => None,
+        InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg0) => unreachable!("clobber-only"),
     }
 }
 
@@ -620,7 +679,8 @@ fn dummy_output_type(cx: &CodegenCx<'ll, 'tcx>, reg: InlineAsmRegClass) -> &'ll
         InlineAsmRegClass::X86(X86InlineAsmRegClass::xmm_reg)
         | InlineAsmRegClass::X86(X86InlineAsmRegClass::ymm_reg)
         | InlineAsmRegClass::X86(X86InlineAsmRegClass::zmm_reg) => cx.type_f32(),
-        InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg) => cx.type_i16(),
+        InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg)
+        | InlineAsmRegClass::X86(X86InlineAsmRegClass::kreg0) => cx.type_i16(),
     }
 }
 
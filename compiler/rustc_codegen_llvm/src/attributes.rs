@@ -2,11 +2,13 @@
 
 use std::ffi::CString;
 
+use libc::c_uint;
+use rustc_ast::expand::allocator::ALLOCATOR_METHODS;
 use rustc_codegen_ssa::traits::*;
 use rustc_data_structures::const_cstr;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::small_c_str::SmallCStr;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::ty::layout::HasTyCtxt;
 use rustc_middle::ty::query::Providers;
@@ -14,6 +16,7 @@ use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::config::{OptLevel, SanitizerSet};
 use rustc_session::Session;
 
+use crate::allocator;
 use crate::attributes;
 use crate::llvm::AttributePlace::Function;
 use crate::llvm::{self, Attribute};
@@ -84,7 +87,16 @@ pub fn set_frame_pointer_elimination(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value)
 /// Tell LLVM what instrument function to insert.
 #[inline]
 fn set_instrument_function(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
-    if cx.sess().instrument_mcount() {
+    if cx.sess().opts.debugging_opts.instrument_mcount_fentry {
+        // Similar to `clang -pg -mfentry` behavior. The x86 backend emits the
+        // `__fentry__` call itself.
+        llvm::AddFunctionAttrStringValue(
+            llfn,
+            llvm::AttributePlace::Function,
+            const_cstr!("fentry-call"),
+            const_cstr!("true"),
+        );
+    } else if cx.sess().instrument_mcount() {
         // Similar to `clang -pg` behavior. Handled by the
         // `post-inline-ee-instrument` LLVM pass.
 
@@ -102,6 +114,22 @@ fn set_instrument_function(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     }
 }
 
+fn set_hotpatch(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
+    // Makes LLVM start the function with an instruction of at least two bytes
+    // that a live patcher can overwrite with a short jump, like MSVC's
+    // `/hotpatch`.
+    if cx.sess().opts.debugging_opts.hotpatch
+        && (cx.sess().target.arch == "x86" || cx.sess().target.arch == "x86_64")
+    {
+        llvm::AddFunctionAttrStringValue(
+            llfn,
+            llvm::AttributePlace::Function,
+            const_cstr!("patchable-function"),
+            const_cstr!("prologue-short-redirect"),
+        );
+    }
+}
+
 fn set_probestack(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     // Only use stack probes if the target specification indicates that we
     // should be using stack probes
@@ -172,14 +200,17 @@ pub fn llvm_target_features(sess: &Session) -> impl Iterator<Item = &str> {
         .cg
         .target_feature
         .split(',')
-        .filter(|f| !RUSTC_SPECIFIC_FEATURES.iter().any(|s| f.contains(s)));
+        .filter(|f| !RUSTC_SPECIFIC_FEATURES.iter().any(|s| f.contains(s)))
+        .filter(|l| !l.is_empty())
+        .map(translate_obsolete_target_features)
+        .filter(move |f| llvm_util::is_valid_cmdline_feature(sess, f));
     sess.target
         .options
         .features
         .split(',')
-        .chain(cmdline)
         .filter(|l| !l.is_empty())
         .map(translate_obsolete_target_features)
+        .chain(cmdline)
 }
 
 pub fn apply_target_cpu_attr(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
@@ -204,6 +235,33 @@ pub fn apply_tune_cpu_attr(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     }
 }
 
+/// Makes LLVM lower floating point operations in `llfn` to library calls, so
+/// that it doesn't touch FPU or SIMD registers, when generating soft float code.
+pub fn apply_soft_float_attr(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
+    if cx.tcx.sess.soft_float() {
+        llvm::AddFunctionAttrStringValue(
+            llfn,
+            llvm::AttributePlace::Function,
+            const_cstr!("use-soft-float"),
+            const_cstr!("true"),
+        );
+    }
+}
+
+/// Stops LLVM from recognizing library idioms in `llfn` when compiling a
+/// `#![no_builtins]` crate, which may be the one implementing them: otherwise
+/// the loop in a `memcpy` implementation becomes a call to `memcpy` itself.
+pub fn apply_no_builtins_attr(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
+    if cx.tcx.sess.target.options.no_builtins || cx.tcx.is_no_builtins(LOCAL_CRATE) {
+        llvm::AddFunctionAttrStringValue(
+            llfn,
+            llvm::AttributePlace::Function,
+            const_cstr!("no-builtins"),
+            const_cstr!("true"),
+        );
+    }
+}
+
 /// Sets the `NonLazyBind` LLVM attribute on a given function,
 /// assuming the codegen options allow skipping the PLT.
 pub fn non_lazy_bind(sess: &Session, llfn: &'ll Value) {
@@ -261,6 +319,9 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
     }
 
     inline(cx, llfn, codegen_fn_attrs.inline.clone());
+    if instance.def.inlines_in_debug(cx.tcx) {
+        inline(cx, llfn, attributes::InlineAttr::Always);
+    }
 
     // The `uwtable` attribute according to LLVM is:
     //
@@ -283,7 +344,12 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
     }
 
     set_frame_pointer_elimination(cx, llfn);
-    set_instrument_function(cx, llfn);
+    // Naked functions have no prologue, so don't ask LLVM to put
+    // instrumentation or a patchable entry sequence in front of the body.
+    if !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+        set_instrument_function(cx, llfn);
+        set_hotpatch(cx, llfn);
+    }
     set_probestack(cx, llfn);
 
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
@@ -303,6 +369,15 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
     }
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::ALLOCATOR) {
         Attribute::NoAlias.apply_llfn(llvm::AttributePlace::ReturnValue, llfn);
+        // Give callers of the global allocator shims the same `allocsize` as
+        // the shim definitions from the allocator module.
+        let symbol_name = cx.tcx.symbol_name(instance).name;
+        let method = ALLOCATOR_METHODS
+            .iter()
+            .find(|method| symbol_name == format!("__rust_{}", method.name));
+        if let Some(arg) = method.and_then(allocator::alloc_size_arg) {
+            unsafe { llvm::LLVMRustAddAllocSizeAttr(llfn, arg as c_uint) };
+        }
     }
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::CMSE_NONSECURE_ENTRY) {
         llvm::AddFunctionAttrString(llfn, Function, const_cstr!("cmse_nonsecure_entry"));
@@ -316,8 +391,12 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
     // tune-cpu is only conveyed through the attribute for our purpose.
     // The target doesn't care; the subtarget reads our attribute.
     apply_tune_cpu_attr(cx, llfn);
+    apply_soft_float_attr(cx, llfn);
+    apply_no_builtins_attr(cx, llfn);
 
-    let features = llvm_target_features(cx.tcx.sess)
+    let features = cx
+        .target_features
+        .iter()
         .map(|s| s.to_string())
         .chain(codegen_fn_attrs.target_features.iter().map(|f| {
             let feature = &f.as_str();
@@ -340,10 +419,10 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
         );
     }
 
-    // Note that currently the `wasm-import-module` doesn't do anything, but
-    // eventually LLVM 7 should read this and ferry the appropriate import
-    // module to the output file.
-    if cx.tcx.sess.target.arch == "wasm32" {
+    // Foreign functions from a `#[link(wasm_import_module = "...")]` block are
+    // tagged so that LLVM emits them as imports from that module, under their
+    // `#[link_name]` if one was given.
+    if cx.tcx.sess.target.options.is_like_wasm {
         if let Some(module) = wasm_import_module(cx.tcx, instance.def_id()) {
             llvm::AddFunctionAttrStringValue(
                 llfn,
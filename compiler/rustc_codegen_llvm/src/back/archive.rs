@@ -8,9 +8,11 @@ use std::ptr;
 use std::str;
 
 use crate::llvm::archive_ro::{ArchiveRO, Child};
-use crate::llvm::{self, ArchiveKind};
+use crate::llvm::{self, ArchiveKind, LLVMMachineType, LLVMRustCOFFShortExport};
 use rustc_codegen_ssa::back::archive::{find_library, ArchiveBuilder};
 use rustc_codegen_ssa::{looks_like_rust_object_file, METADATA_FILENAME};
+use rustc_data_structures::small_c_str::SmallCStr;
+use rustc_middle::middle::cstore::{DllCallingConvention, DllImport};
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
 
@@ -174,6 +176,72 @@ impl<'a> ArchiveBuilder<'a> for LlvmArchiveBuilder<'a> {
             self.config.sess.fatal(&format!("failed to build archive: {}", e));
         }
     }
+
+    fn create_dll_import_lib(
+        sess: &Session,
+        lib_name: &str,
+        dll_imports: &[DllImport],
+        output: &Path,
+    ) {
+        let machine = match &*sess.target.arch {
+            "x86_64" => LLVMMachineType::AMD64,
+            "x86" => LLVMMachineType::I386,
+            "aarch64" => LLVMMachineType::ARM64,
+            "arm" => LLVMMachineType::ARMNT,
+            arch => sess.fatal(&format!("`raw-dylib` is not supported on `{}`", arch)),
+        };
+
+        // The names must outlive the exports pointing into them. On x86 calls
+        // reference decorated symbols, while the DLL exports undecorated names.
+        let names: Vec<(SmallCStr, Option<SmallCStr>)> = dll_imports
+            .iter()
+            .map(|import| {
+                let symbol_name = match machine {
+                    LLVMMachineType::I386 => Some(SmallCStr::new(&i686_decorated_name(import))),
+                    _ => None,
+                };
+                (SmallCStr::new(&import.name.as_str()), symbol_name)
+            })
+            .collect();
+        let exports: Vec<LLVMRustCOFFShortExport> = dll_imports
+            .iter()
+            .zip(&names)
+            .map(|(import, (name, symbol_name))| {
+                let symbol_name = symbol_name.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+                LLVMRustCOFFShortExport::new(name.as_ptr(), symbol_name, import.ordinal)
+            })
+            .collect();
+
+        let lib_name_z = SmallCStr::new(lib_name);
+        let output_z = SmallCStr::new(&output.to_string_lossy());
+        let result = unsafe {
+            llvm::LLVMRustWriteImportLibrary(
+                lib_name_z.as_ptr(),
+                output_z.as_ptr(),
+                exports.as_ptr(),
+                exports.len() as libc::size_t,
+                machine as u16,
+                !sess.target.options.is_like_msvc,
+            )
+        };
+
+        if result.into_result().is_err() {
+            let err = llvm::last_error().unwrap_or_else(|| "unknown LLVM error".to_string());
+            sess.fatal(&format!("failed to create import library for `{}`: {}", lib_name, err));
+        }
+    }
+}
+
+/// Returns the symbol that calls to `import` reference on i686: C functions
+/// get a `_` prefix, and stdcall and fastcall functions are also suffixed with
+/// the size of their arguments.
+fn i686_decorated_name(import: &DllImport) -> String {
+    let name = import.name;
+    match import.calling_convention {
+        DllCallingConvention::C => format!("_{}", name),
+        DllCallingConvention::Stdcall(arg_list_size) => format!("_{}@{}", name, arg_list_size),
+        DllCallingConvention::Fastcall(arg_list_size) => format!("@{}@{}", name, arg_list_size),
+    }
 }
 
 impl<'a> LlvmArchiveBuilder<'a> {
@@ -0,0 +1,84 @@
+//! Running a binary crate in LLVM's ORC JIT instead of linking it, for
+//! `-Z jit-run`.
+//!
+//! Codegen goes through the usual pipeline in `back::write`; only the link
+//! step is replaced. The crate's objects are loaded into the JIT, upstream
+//! crates are pulled in from their rlibs or dylibs, and everything else is
+//! resolved against the compiler's own process.
+
+use crate::llvm;
+
+use rustc_codegen_ssa::CodegenResults;
+use rustc_errors::ErrorReported;
+use rustc_fs_util::path_to_c_string;
+use rustc_middle::middle::dependency_format::Linkage;
+use rustc_session::config::CrateType;
+use rustc_session::Session;
+
+use libc::c_int;
+use std::ffi::CString;
+use std::fs;
+
+/// Runs the `main` function of the crate whose codegen produced
+/// `codegen_results`, and records its exit code in `Session::jit_exit_code`
+/// for the driver to exit with.
+pub fn run(sess: &Session, codegen_results: &CodegenResults) -> Result<(), ErrorReported> {
+    let crate_info = &codegen_results.crate_info;
+    let data =
+        match crate_info.dependency_formats.iter().find(|(ty, _)| *ty == CrateType::Executable) {
+            Some((_, data)) => data,
+            None => sess.fatal("`-Z jit-run` can only be used on binary crates"),
+        };
+
+    let objects: Vec<_> = codegen_results
+        .modules
+        .iter()
+        .chain(&codegen_results.allocator_module)
+        .filter_map(|module| module.object.as_ref())
+        .collect();
+
+    let mut archives = Vec::new();
+    let mut dylibs = Vec::new();
+    for &(cnum, _) in crate_info.used_crates_dynamic.iter() {
+        let src = &crate_info.used_crate_source[&cnum];
+        match data[cnum.as_usize() - 1] {
+            Linkage::NotLinked | Linkage::IncludedFromDylib => {}
+            Linkage::Static => archives.push(path_to_c_string(&src.rlib.as_ref().unwrap().0)),
+            Linkage::Dynamic => dylibs.push(path_to_c_string(&src.dylib.as_ref().unwrap().0)),
+        }
+    }
+
+    let object_paths: Vec<_> = objects.iter().map(|path| path_to_c_string(path)).collect();
+    let object_ptrs: Vec<_> = object_paths.iter().map(|path| path.as_ptr()).collect();
+    let archive_ptrs: Vec<_> = archives.iter().map(|path| path.as_ptr()).collect();
+    let dylib_ptrs: Vec<_> = dylibs.iter().map(|path| path.as_ptr()).collect();
+    let program_name = CString::new(&*codegen_results.crate_name.as_str()).unwrap();
+
+    let mut exit_code: c_int = 0;
+    let ok = sess.time("jit_run", || unsafe {
+        llvm::LLVMRustRunJIT(
+            object_ptrs.as_ptr(),
+            object_ptrs.len(),
+            archive_ptrs.as_ptr(),
+            archive_ptrs.len(),
+            dylib_ptrs.as_ptr(),
+            dylib_ptrs.len(),
+            program_name.as_ptr(),
+            &mut exit_code,
+        )
+    });
+
+    if !sess.opts.cg.save_temps {
+        for path in objects {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    if !ok {
+        let msg = llvm::last_error().unwrap_or_else(|| "unknown error".to_string());
+        sess.fatal(&format!("failed to run the crate in the JIT: {}", msg));
+    }
+
+    sess.jit_exit_code.set(exit_code).unwrap();
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Running LLD inside the compiler process instead of spawning `rust-lld`, for
+//! `-Z in-process-lld`.
+//!
+//! The linker arguments are the ones `back::link` builds for `rust-lld`; only
+//! the way LLD is invoked changes. This saves a process launch per link and
+//! lets `back::link` turn LLD's diagnostics into diagnostics of our own.
+
+use crate::llvm;
+
+use rustc_codegen_ssa::back::link::InProcessLinkOutput;
+use rustc_fs_util::path_to_c_string;
+use rustc_middle::bug;
+use rustc_target::spec::LldFlavor;
+
+use std::ffi::OsString;
+use std::path::Path;
+
+/// Links with the LLD driver for `flavor`, which must not be `Ld64`.
+pub fn link(flavor: LldFlavor, args: &[OsString]) -> InProcessLinkOutput {
+    let flavor = match flavor {
+        LldFlavor::Ld => llvm::LLDFlavor::Elf,
+        LldFlavor::Link => llvm::LLDFlavor::Coff,
+        LldFlavor::Wasm => llvm::LLDFlavor::Wasm,
+        LldFlavor::Ld64 => bug!("in-process LLD does not support the `darwin` flavor"),
+    };
+    let args: Vec<_> = args.iter().map(|arg| path_to_c_string(Path::new(arg))).collect();
+    let arg_ptrs: Vec<_> = args.iter().map(|arg| arg.as_ptr()).collect();
+
+    let mut success = false;
+    let mut stdout = Vec::new();
+    let stderr = llvm::build_byte_buffer(|stderr| {
+        stdout = llvm::build_byte_buffer(|stdout| {
+            success = unsafe {
+                llvm::LLVMRustLinkWithLLD(flavor, arg_ptrs.as_ptr(), arg_ptrs.len(), stdout, stderr)
+            };
+        });
+    });
+    InProcessLinkOutput { success, stdout, stderr }
+}
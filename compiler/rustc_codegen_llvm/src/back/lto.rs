@@ -287,7 +287,9 @@ fn fat_lto(
         // The linking steps below may produce errors and diagnostics within LLVM
         // which we'd like to handle and print, so set up our diagnostic handlers
         // (which get unregistered when they go out of scope below).
-        let _handler = DiagnosticHandlers::new(cgcx, diag_handler, llcx);
+        // The module-level assembly of the linked module no longer matches the
+        // srclocs recorded during codegen, and srclocs are ignored with LTO anyway.
+        let _handler = DiagnosticHandlers::new(cgcx, diag_handler, llcx, &[]);
 
         // For all other modules we codegened we'll need to link them into our own
         // bitcode. All modules were codegened in their own LLVM context, however,
@@ -577,7 +579,15 @@ pub(crate) fn run_pass_manager(
     // This code is based off the code found in llvm's LTO code generator:
     //      tools/lto/LTOCodeGenerator.cpp
     debug!("running the pass manager");
+    write::start_llvm_stats(cgcx);
     unsafe {
+        // Every virtual call of the crate graph is now in this module, so the
+        // vtable entries that are never loaded can be removed.
+        if !thin && config.virtual_function_elimination {
+            let lto_post_link = "LTOPostLink\0".as_ptr().cast();
+            llvm::LLVMRustAddModuleFlag(module.module_llvm.llmod(), lto_post_link, 1);
+        }
+
         if write::should_use_new_llvm_pass_manager(config) {
             let opt_stage = if thin { llvm::OptStage::ThinLTO } else { llvm::OptStage::FatLTO };
             let opt_level = config.opt_level.unwrap_or(config::OptLevel::No);
@@ -588,6 +598,7 @@ pub(crate) fn run_pass_manager(
                 opt_level
             };
             write::optimize_with_new_llvm_pass_manager(cgcx, module, config, opt_level, opt_stage);
+            write::record_llvm_stats(cgcx, module, "lto");
             debug!("lto done");
             return;
         }
@@ -646,6 +657,7 @@ pub(crate) fn run_pass_manager(
 
         llvm::LLVMDisposePassManager(pm);
     }
+    write::record_llvm_stats(cgcx, module, "lto");
     debug!("lto done");
 }
 
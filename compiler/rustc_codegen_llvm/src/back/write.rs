@@ -1,3 +1,4 @@
+use crate::asm::ModuleAsmSrcLoc;
 use crate::attributes;
 use crate::back::lto::ThinBuffer;
 use crate::back::profiling::{
@@ -126,11 +127,11 @@ pub fn target_machine_factory(
     let reloc_model = to_llvm_relocation_model(sess.relocation_model());
 
     let (opt_level, _) = to_llvm_opt_settings(optlvl);
-    let use_softfp = sess.opts.cg.soft_float;
+    let use_softfp = sess.soft_float();
 
     let ffunction_sections =
         sess.opts.debugging_opts.function_sections.unwrap_or(sess.target.options.function_sections);
-    let fdata_sections = ffunction_sections;
+    let fdata_sections = sess.opts.debugging_opts.data_sections.unwrap_or(ffunction_sections);
 
     let code_model = to_llvm_code_model(sess.code_model());
 
@@ -141,7 +142,7 @@ pub fn target_machine_factory(
     // we're no longer single-threaded, or otherwise we don't want LLVM to
     // lower atomic operations to single-threaded operations.
     if singlethread
-        && sess.target.llvm_target.contains("wasm32")
+        && sess.target.options.is_like_wasm
         && sess.target_features.contains(&sym::atomics)
     {
         singlethread = false;
@@ -154,6 +155,12 @@ pub fn target_machine_factory(
     let abi = SmallCStr::new(&sess.target.options.llvm_abiname);
     let trap_unreachable = sess.target.options.trap_unreachable;
     let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes;
+    // Address-significance tables let linkers fold identical functions whose
+    // address is never taken, like lld's `--icf=safe`. LLVM emits them for ELF
+    // and COFF, as clang does.
+    let emit_addrsig = !sess.opts.debugging_opts.no_addrsig
+        && !sess.target.options.is_like_osx
+        && !sess.target.options.is_like_wasm;
 
     let asm_comments = sess.asm_comments();
     let relax_elf_relocations = sess.target.options.relax_elf_relocations;
@@ -181,6 +188,7 @@ pub fn target_machine_factory(
                 singlethread,
                 asm_comments,
                 emit_stack_size_section,
+                emit_addrsig,
                 relax_elf_relocations,
                 use_init_array,
             )
@@ -210,18 +218,24 @@ pub(crate) fn save_temp_bitcode(
     }
 }
 
+type DiagnosticHandlerData<'a> =
+    (&'a CodegenContext<LlvmCodegenBackend>, &'a Handler, &'a [ModuleAsmSrcLoc]);
+
 pub struct DiagnosticHandlers<'a> {
-    data: *mut (&'a CodegenContext<LlvmCodegenBackend>, &'a Handler),
+    data: *mut DiagnosticHandlerData<'a>,
     llcx: &'a llvm::Context,
 }
 
 impl<'a> DiagnosticHandlers<'a> {
+    /// `module_asm_srclocs` maps the module-level assembly of the module back
+    /// to the items it came from, see `codegen_module_asm`.
     pub fn new(
         cgcx: &'a CodegenContext<LlvmCodegenBackend>,
         handler: &'a Handler,
         llcx: &'a llvm::Context,
+        module_asm_srclocs: &'a [ModuleAsmSrcLoc],
     ) -> Self {
-        let data = Box::into_raw(Box::new((cgcx, handler)));
+        let data = Box::into_raw(Box::new((cgcx, handler, module_asm_srclocs)));
         unsafe {
             llvm::LLVMRustSetInlineAsmDiagnosticHandler(llcx, inline_asm_handler, data.cast());
             llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, data.cast());
@@ -262,11 +276,15 @@ fn report_inline_asm(
     cgcx.diag_emitter.inline_asm_error(cookie as u32, msg, level, source);
 }
 
-unsafe extern "C" fn inline_asm_handler(diag: &SMDiagnostic, user: *const c_void, cookie: c_uint) {
+unsafe extern "C" fn inline_asm_handler(
+    diag: &SMDiagnostic,
+    user: *const c_void,
+    mut cookie: c_uint,
+) {
     if user.is_null() {
         return;
     }
-    let (cgcx, _) = *(user as *const (&CodegenContext<LlvmCodegenBackend>, &Handler));
+    let (cgcx, _, module_asm_srclocs) = *(user as *const DiagnosticHandlerData<'_>);
 
     // Recover the post-substitution assembly code from LLVM for better
     // diagnostics.
@@ -300,6 +318,15 @@ unsafe extern "C" fn inline_asm_handler(diag: &SMDiagnostic, user: *const c_void
         (buffer, spans)
     });
 
+    // Errors in module-level assembly don't carry a cookie, find it from the
+    // source line which contributed the offending line of the assembly instead.
+    if cookie == 0 && have_source {
+        let loc = loc as usize;
+        if let Some(srcloc) = module_asm_srclocs.iter().find(|s| s.start <= loc && loc < s.end) {
+            cookie = srcloc.cookie;
+        }
+    }
+
     report_inline_asm(cgcx, msg, level, cookie, source);
 }
 
@@ -307,7 +334,7 @@ unsafe extern "C" fn diagnostic_handler(info: &DiagnosticInfo, user: *mut c_void
     if user.is_null() {
         return;
     }
-    let (cgcx, diag_handler) = *(user as *const (&CodegenContext<LlvmCodegenBackend>, &Handler));
+    let (cgcx, diag_handler, _) = *(user as *const DiagnosticHandlerData<'_>);
 
     match llvm::diagnostic::Diagnostic::unpack(info) {
         llvm::diagnostic::InlineAsm(inline) => {
@@ -414,6 +441,10 @@ pub(crate) unsafe fn optimize_with_new_llvm_pass_manager(
     } else {
         None
     };
+    // `-Z profile` requests the legacy `insert-gcov-profiling` pass, which is
+    // inserted at the start of the pre-link pipeline here.
+    let insert_gcov_profiling =
+        !is_lto && config.passes.iter().any(|pass| pass == "insert-gcov-profiling");
 
     let llvm_selfprofiler = if cgcx.prof.llvm_recording_enabled() {
         let mut llvm_profiler = LlvmSelfProfiler::new(cgcx.prof.get_self_profiler().unwrap());
@@ -443,6 +474,7 @@ pub(crate) unsafe fn optimize_with_new_llvm_pass_manager(
         config.no_builtins,
         config.emit_lifetime_markers,
         sanitizer_options.as_ref(),
+        insert_gcov_profiling,
         pgo_gen_path.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
         pgo_use_path.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
         llvm_selfprofiler,
@@ -463,7 +495,8 @@ pub(crate) unsafe fn optimize(
     let llmod = module.module_llvm.llmod();
     let llcx = &*module.module_llvm.llcx;
     let tm = &*module.module_llvm.tm;
-    let _handlers = DiagnosticHandlers::new(cgcx, diag_handler, llcx);
+    let _handlers =
+        DiagnosticHandlers::new(cgcx, diag_handler, llcx, &module.module_llvm.module_asm_srclocs);
 
     let module_name = module.name.clone();
     let module_name = Some(&module_name[..]);
@@ -475,6 +508,8 @@ pub(crate) unsafe fn optimize(
     }
 
     if let Some(opt_level) = config.opt_level {
+        start_llvm_stats(cgcx);
+
         if should_use_new_llvm_pass_manager(config) {
             let opt_stage = match cgcx.lto {
                 Lto::Fat => llvm::OptStage::PreLinkFatLTO,
@@ -483,6 +518,8 @@ pub(crate) unsafe fn optimize(
                 _ => llvm::OptStage::PreLinkNoLTO,
             };
             optimize_with_new_llvm_pass_manager(cgcx, module, config, opt_level, opt_stage);
+            save_temp_bitcode(cgcx, module, "opt");
+            record_llvm_stats(cgcx, module, "opt");
             return Ok(());
         }
 
@@ -605,10 +642,34 @@ pub(crate) unsafe fn optimize(
         // Deallocate managers that we're now done with
         llvm::LLVMDisposePassManager(fpm);
         llvm::LLVMDisposePassManager(mpm);
+
+        save_temp_bitcode(cgcx, module, "opt");
+        record_llvm_stats(cgcx, module, "opt");
     }
     Ok(())
 }
 
+/// Under `-Z llvm-stats`, starts collecting LLVM's statistics counters for a
+/// stage of a module about to run.
+pub(crate) fn start_llvm_stats(cgcx: &CodegenContext<LlvmCodegenBackend>) {
+    if cgcx.opts.debugging_opts.llvm_stats {
+        llvm_util::llvm_stats_start();
+    }
+}
+
+/// Under `-Z llvm-stats`, records the counters collected since
+/// `start_llvm_stats` as those of the `stage` (`opt`, `lto` or `codegen`) of
+/// `module`.
+pub(crate) fn record_llvm_stats(
+    cgcx: &CodegenContext<LlvmCodegenBackend>,
+    module: &ModuleCodegen<ModuleLlvm>,
+    stage: &str,
+) {
+    if cgcx.opts.debugging_opts.llvm_stats {
+        llvm_util::llvm_stats_record(&module.name, stage);
+    }
+}
+
 unsafe fn add_sanitizer_passes(config: &ModuleConfig, passes: &mut Vec<&'static mut llvm::Pass>) {
     if config.sanitizer.contains(SanitizerSet::ADDRESS) {
         let recover = config.sanitizer_recover.contains(SanitizerSet::ADDRESS);
@@ -663,12 +724,19 @@ pub(crate) unsafe fn codegen(
         let tm = &*module.module_llvm.tm;
         let module_name = module.name.clone();
         let module_name = Some(&module_name[..]);
-        let handlers = DiagnosticHandlers::new(cgcx, diag_handler, llcx);
+        let handlers = DiagnosticHandlers::new(
+            cgcx,
+            diag_handler,
+            llcx,
+            &module.module_llvm.module_asm_srclocs,
+        );
 
         if cgcx.msvc_imps_needed {
             create_msvc_imps(cgcx, llcx, llmod);
         }
 
+        start_llvm_stats(cgcx);
+
         // A codegen-specific pass manager is used to generate object
         // files for an LLVM module.
         //
@@ -829,6 +897,7 @@ pub(crate) unsafe fn codegen(
             EmitObj::None => {}
         }
 
+        record_llvm_stats(cgcx, &module, "codegen");
         drop(handlers);
     }
 
@@ -28,6 +28,7 @@ use rustc_codegen_ssa::mono_item::MonoItemExt;
 use rustc_codegen_ssa::traits::*;
 use rustc_codegen_ssa::{ModuleCodegen, ModuleKind};
 use rustc_data_structures::small_c_str::SmallCStr;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_middle::dep_graph;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
 use rustc_middle::middle::cstore::EncodedMetadata;
@@ -72,6 +73,57 @@ pub fn write_compressed_metadata<'tcx>(
     }
 }
 
+/// Defines the `-Z embed-abi-checksum` symbol, a NUL-terminated string describing the
+/// target ABI, and the guard symbol of the local crate. Every codegen unit gets
+/// `linkonce_odr` copies so that exactly one survives linking, and they are marked as used
+/// so that they aren't garbage collected.
+///
+/// Upstream crates built with the option export their own guard symbol, whose name
+/// encodes the hash of their checksum. For each of them we reference the name it would
+/// have if it was built for the same ABI as the local crate, so that a mismatch shows up
+/// as an undefined symbol when linking.
+fn embed_abi_checksum(cx: &CodegenCx<'ll, '_>) {
+    let tcx = cx.tcx;
+    let checksum = exported_symbols::abi_checksum(tcx);
+    let llconst = cx.const_bytes(&[checksum.as_bytes(), &[0]].concat());
+    let guard_name = exported_symbols::abi_checksum_guard_name(tcx, LOCAL_CRATE);
+    for name in &[exported_symbols::ABI_CHECKSUM_SYMBOL_NAME, guard_name.as_str()] {
+        let name = CString::new(*name).unwrap();
+        let llglobal = unsafe {
+            let llglobal = llvm::LLVMAddGlobal(cx.llmod, common::val_ty(llconst), name.as_ptr());
+            llvm::LLVMSetInitializer(llglobal, llconst);
+            llvm::LLVMSetGlobalConstant(llglobal, llvm::True);
+            llvm::LLVMRustSetLinkage(llglobal, llvm::Linkage::LinkOnceODRLinkage);
+            llvm::LLVMSetAlignment(llglobal, 1);
+            llglobal
+        };
+        cx.add_used_global(llglobal);
+    }
+
+    let i8p = cx.type_i8p();
+    let upstream_checksums: Vec<_> = tcx
+        .upstream_abi_checksum_crates(LOCAL_CRATE)
+        .iter()
+        .map(|&cnum| {
+            let name = exported_symbols::abi_checksum_guard_name(tcx, cnum);
+            let llglobal = cx.declare_global(&name, cx.type_i8());
+            cx.const_bitcast(llglobal, i8p)
+        })
+        .collect();
+    if upstream_checksums.is_empty() {
+        return;
+    }
+
+    // Keep the references alive through a private array that is itself marked as used.
+    let llconst = cx.const_array(i8p, &upstream_checksums);
+    let llglobal = cx.define_private_global(cx.val_ty(llconst));
+    unsafe {
+        llvm::LLVMSetInitializer(llglobal, llconst);
+        llvm::LLVMSetGlobalConstant(llglobal, llvm::True);
+    }
+    cx.add_used_global(llglobal);
+}
+
 pub struct ValueIter<'ll> {
     cur: Option<&'ll Value>,
     step: unsafe extern "C" fn(&'ll Value) -> Option<&'ll Value>,
@@ -113,8 +165,8 @@ pub fn compile_codegen_unit(
     fn module_codegen(tcx: TyCtxt<'_>, cgu_name: Symbol) -> ModuleCodegen<ModuleLlvm> {
         let cgu = tcx.codegen_unit(cgu_name);
         // Instantiate monomorphizations without filling out definitions yet...
-        let llvm_module = ModuleLlvm::new(tcx, &cgu_name.as_str());
-        {
+        let mut llvm_module = ModuleLlvm::new(tcx, &cgu_name.as_str());
+        let module_asm_srclocs = {
             let cx = CodegenCx::new(tcx, cgu, &llvm_module);
             let mono_items = cx.codegen_unit.items_in_deterministic_order(cx.tcx);
             for &(mono_item, (linkage, visibility)) in &mono_items {
@@ -147,6 +199,10 @@ pub fn compile_codegen_unit(
                 cx.coverageinfo_finalize();
             }
 
+            if cx.sess().opts.debugging_opts.embed_abi_checksum {
+                embed_abi_checksum(&cx);
+            }
+
             // Create the llvm.used variable
             // This variable has type [N x i8*] and is stored in the llvm.metadata section
             if !cx.used_statics().borrow().is_empty() {
@@ -157,7 +213,10 @@ pub fn compile_codegen_unit(
             if cx.sess().opts.debuginfo != DebugInfo::None {
                 cx.debuginfo_finalize();
             }
-        }
+
+            cx.module_asm_srclocs.into_inner()
+        };
+        llvm_module.module_asm_srclocs = module_asm_srclocs;
 
         ModuleCodegen {
             name: cgu_name.to_string(),
@@ -1,12 +1,12 @@
 use crate::common::Funclet;
 use crate::context::CodegenCx;
-use crate::llvm::{self, BasicBlock, False};
+use crate::llvm::{self, BasicBlock, False, OperandBundleDef};
 use crate::llvm::{AtomicOrdering, AtomicRmwBinOp, SynchronizationScope};
 use crate::type_::Type;
 use crate::type_of::LayoutLlvmExt;
 use crate::value::Value;
 use libc::{c_char, c_uint};
-use rustc_codegen_ssa::common::{IntPredicate, RealPredicate, TypeKind};
+use rustc_codegen_ssa::common::{CallSiteAttr, IntPredicate, RealPredicate, TypeKind};
 use rustc_codegen_ssa::mir::operand::{OperandRef, OperandValue};
 use rustc_codegen_ssa::mir::place::PlaceRef;
 use rustc_codegen_ssa::traits::*;
@@ -14,15 +14,18 @@ use rustc_codegen_ssa::MemFlags;
 use rustc_data_structures::const_cstr;
 use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_hir::def_id::DefId;
+use rustc_middle::bug;
 use rustc_middle::ty::layout::TyAndLayout;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::{sym, Span};
 use rustc_target::abi::{self, Align, Size};
 use rustc_target::spec::{HasTargetSpec, Target};
+use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::ffi::CStr;
 use std::ops::{Deref, Range};
 use std::ptr;
+use std::slice;
 use tracing::debug;
 
 // All Builders must have an llfn associated with them
@@ -199,23 +202,26 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         catch: &'ll BasicBlock,
         funclet: Option<&Funclet<'ll>>,
     ) -> &'ll Value {
-        debug!("invoke {:?} with args ({:?})", llfn, args);
-
-        let args = self.check_call("invoke", llfn, args);
         let bundle = funclet.map(|funclet| funclet.bundle());
-        let bundle = bundle.as_ref().map(|b| &*b.raw);
+        let bundles = bundle.as_ref().map_or(&[][..], slice::from_ref);
+        self.invoke_with_bundles(llfn, args, then, catch, bundles)
+    }
 
-        unsafe {
-            llvm::LLVMRustBuildInvoke(
-                self.llbuilder,
-                llfn,
-                args.as_ptr(),
-                args.len() as c_uint,
-                then,
-                catch,
-                bundle,
-                UNNAMED,
-            )
+    fn invoke_with_operand_bundle(
+        &mut self,
+        llfn: &'ll Value,
+        args: &[&'ll Value],
+        then: &'ll BasicBlock,
+        catch: &'ll BasicBlock,
+        funclet: Option<&Funclet<'ll>>,
+        tag: &str,
+    ) -> &'ll Value {
+        let bundle = OperandBundleDef::new(tag, &[]);
+        match funclet {
+            Some(funclet) => {
+                self.invoke_with_bundles(llfn, args, then, catch, &[funclet.bundle(), &bundle])
+            }
+            None => self.invoke_with_bundles(llfn, args, then, catch, &[&bundle]),
         }
     }
 
@@ -297,12 +303,39 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn minnum(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
+        unsafe { llvm::LLVMRustBuildMinNum(self.llbuilder, lhs, rhs) }
+    }
+
+    fn maxnum(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
+        unsafe { llvm::LLVMRustBuildMaxNum(self.llbuilder, lhs, rhs) }
+    }
+
+    fn bitreverse(&mut self, v: &'ll Value) -> &'ll Value {
+        let width = self.int_width(self.val_ty(v));
+        let llfn = self.get_intrinsic(&format!("llvm.bitreverse.i{}", width));
+        self.call(llfn, &[v], None)
+    }
+
+    fn ctlz(&mut self, v: &'ll Value, is_zero_undef: bool) -> &'ll Value {
+        let width = self.int_width(self.val_ty(v));
+        let llfn = self.get_intrinsic(&format!("llvm.ctlz.i{}", width));
+        self.call(llfn, &[v, self.const_bool(is_zero_undef)], None)
+    }
+
+    fn cttz(&mut self, v: &'ll Value, is_zero_undef: bool) -> &'ll Value {
+        let width = self.int_width(self.val_ty(v));
+        let llfn = self.get_intrinsic(&format!("llvm.cttz.i{}", width));
+        self.call(llfn, &[v, self.const_bool(is_zero_undef)], None)
+    }
+
     fn checked_binop(
         &mut self,
         oop: OverflowOp,
         ty: Ty<'_>,
         lhs: Self::Value,
         rhs: Self::Value,
+        funclet: Option<&Funclet<'ll>>,
     ) -> (Self::Value, Self::Value) {
         use rustc_ast::IntTy::*;
         use rustc_ast::UintTy::*;
@@ -315,6 +348,18 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
             _ => panic!("tried to get overflow intrinsic for op applied to non-int type"),
         };
 
+        // LLVM only knows the `__muloti4` libcall on 64-bit targets, and has none
+        // for the unsigned case. Elsewhere it expands the 128-bit
+        // `llvm.*mul.with.overflow` intrinsics into a long branchy sequence, so
+        // call the compiler-builtins implementations directly instead.
+        if matches!(oop, OverflowOp::Mul) && self.tcx.sess.target.pointer_width < 64 {
+            match new_kind {
+                Int(I128) => return self.call_mulo_libcall("__muloti4", lhs, rhs, funclet),
+                Uint(U128) => return self.call_mulo_libcall("__rust_u128_mulo", lhs, rhs, funclet),
+                _ => {}
+            }
+        }
+
         let name = match oop {
             OverflowOp::Add => match new_kind {
                 Int(I8) => "llvm.sadd.with.overflow.i8",
@@ -412,10 +457,11 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
-    fn volatile_load(&mut self, ptr: &'ll Value) -> &'ll Value {
+    fn volatile_load(&mut self, ptr: &'ll Value, align: Align) -> &'ll Value {
         unsafe {
             let load = llvm::LLVMBuildLoad(self.llbuilder, ptr, UNNAMED);
             llvm::LLVMSetVolatile(load, llvm::True);
+            llvm::LLVMSetAlignment(load, align.bytes() as c_uint);
             load
         }
     }
@@ -424,7 +470,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         &mut self,
         ptr: &'ll Value,
         order: rustc_codegen_ssa::common::AtomicOrdering,
-        size: Size,
+        align: Align,
     ) -> &'ll Value {
         unsafe {
             let load = llvm::LLVMRustBuildAtomicLoad(
@@ -433,8 +479,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
                 UNNAMED,
                 AtomicOrdering::from_generic(order),
             );
-            // LLVM requires the alignment of atomic loads to be at least the size of the type.
-            llvm::LLVMSetAlignment(load, size.bytes() as c_uint);
+            llvm::LLVMSetAlignment(load, align.bytes() as c_uint);
             load
         }
     }
@@ -448,25 +493,12 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
             return OperandRef::new_zst(self, place.layout);
         }
 
-        fn scalar_load_metadata<'a, 'll, 'tcx>(
-            bx: &mut Builder<'a, 'll, 'tcx>,
-            load: &'ll Value,
-            scalar: &abi::Scalar,
-        ) {
-            let vr = scalar.valid_range.clone();
-            match scalar.value {
-                abi::Int(..) => {
-                    let range = scalar.valid_range_exclusive(bx);
-                    if range.start != range.end {
-                        bx.range_metadata(load, range);
-                    }
-                }
-                abi::Pointer if vr.start() < vr.end() && !vr.contains(&0) => {
-                    bx.nonnull_metadata(load);
-                }
-                _ => {}
-            }
-        }
+        // Loads from constant globals, such as promoted constants, always
+        // produce the same value, which lets LLVM hoist and CSE them.
+        let from_const_global = unsafe {
+            llvm::LLVMIsAGlobalVariable(llvm::LLVMRustStripPointerCasts(place.llval))
+                .map_or(false, |global| llvm::LLVMIsGlobalConstant(global) == llvm::True)
+        };
 
         let val = if let Some(llextra) = place.llextra {
             OperandValue::Ref(place.llval, Some(llextra), place.align)
@@ -480,26 +512,38 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
                 }
             }
             let llval = const_llval.unwrap_or_else(|| {
-                let load = self.load(place.llval, place.align);
+                let load = if place.needs_unaligned_access() {
+                    self.load_unaligned(place.llval)
+                } else {
+                    self.load(place.llval, place.align)
+                };
+                if from_const_global {
+                    self.set_invariant_load(load);
+                }
                 if let abi::Abi::Scalar(ref scalar) = place.layout.abi {
-                    scalar_load_metadata(self, load, scalar);
+                    self.scalar_load_metadata(load, scalar, place.layout, Size::ZERO);
                 }
                 load
             });
             OperandValue::Immediate(self.to_immediate(llval, place.layout))
         } else if let abi::Abi::ScalarPair(ref a, ref b) = place.layout.abi {
             let b_offset = a.value.size(self).align_to(b.value.align(self).abi);
+            let unaligned = place.needs_unaligned_access();
 
-            let mut load = |i, scalar: &abi::Scalar, align| {
+            let mut load = |i, scalar: &abi::Scalar, align, offset| {
                 let llptr = self.struct_gep(place.llval, i as u64);
-                let load = self.load(llptr, align);
-                scalar_load_metadata(self, load, scalar);
+                let load =
+                    if unaligned { self.load_unaligned(llptr) } else { self.load(llptr, align) };
+                if from_const_global {
+                    self.set_invariant_load(load);
+                }
+                self.scalar_load_metadata(load, scalar, place.layout, offset);
                 self.to_immediate_scalar(load, scalar)
             };
 
             OperandValue::Pair(
-                load(0, a, place.align),
-                load(1, b, place.align.restrict_for_offset(b_offset)),
+                load(0, a, place.align, Size::ZERO),
+                load(1, b, place.align.restrict_for_offset(b_offset), b_offset),
             )
         } else {
             OperandValue::Ref(place.llval, None, place.align)
@@ -575,6 +619,71 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn align_metadata(&mut self, load: &'ll Value, align: Align) {
+        unsafe {
+            let v = [self.cx.const_u64(align.bytes())];
+            llvm::LLVMSetMetadata(
+                load,
+                llvm::MD_align as c_uint,
+                llvm::LLVMMDNodeInContext(self.cx.llcx, v.as_ptr(), v.len() as c_uint),
+            );
+        }
+    }
+
+    fn alias_scope_domain(&mut self, name: &str, num_scopes: usize) -> Vec<&'ll Value> {
+        unsafe {
+            let llcx = self.cx.llcx;
+            let domain = llvm::LLVMRustCreateAnonymousAliasScopeDomain(
+                llcx,
+                name.as_ptr().cast(),
+                name.len(),
+            );
+            (0..num_scopes)
+                .map(|i| {
+                    let name = format!("{}: scope {}", name, i);
+                    llvm::LLVMRustCreateAnonymousAliasScope(
+                        llcx,
+                        domain,
+                        name.as_ptr().cast(),
+                        name.len(),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    fn alias_scope_metadata(&mut self, access: &'ll Value, scopes: &[&'ll Value], scope: usize) {
+        unsafe {
+            // Loads of `bool`s are truncated to `i1` by `to_immediate`.
+            let access = match llvm::LLVMIsATruncInst(access) {
+                Some(trunc) => llvm::LLVMGetOperand(trunc, 0),
+                None => access,
+            };
+            if llvm::LLVMIsALoadInst(access).is_none() && llvm::LLVMIsAStoreInst(access).is_none() {
+                return;
+            }
+
+            let llcx = self.cx.llcx;
+            let alias_scope = [scopes[scope]];
+            let noalias: Vec<_> = scopes
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != scope)
+                .map(|(_, &node)| node)
+                .collect();
+            llvm::LLVMSetMetadata(
+                access,
+                llvm::MD_alias_scope as c_uint,
+                llvm::LLVMMDNodeInContext(llcx, alias_scope.as_ptr(), 1),
+            );
+            llvm::LLVMSetMetadata(
+                access,
+                llvm::MD_noalias as c_uint,
+                llvm::LLVMMDNodeInContext(llcx, noalias.as_ptr(), noalias.len() as c_uint),
+            );
+        }
+    }
+
     fn store(&mut self, val: &'ll Value, ptr: &'ll Value, align: Align) -> &'ll Value {
         self.store_with_flags(val, ptr, align, MemFlags::empty())
     }
@@ -614,7 +723,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         val: &'ll Value,
         ptr: &'ll Value,
         order: rustc_codegen_ssa::common::AtomicOrdering,
-        size: Size,
+        align: Align,
     ) {
         debug!("Store {:?} -> {:?}", val, ptr);
         let ptr = self.check_store(val, ptr);
@@ -625,8 +734,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
                 ptr,
                 AtomicOrdering::from_generic(order),
             );
-            // LLVM requires the alignment of atomic stores to be at least the size of the type.
-            llvm::LLVMSetAlignment(store, size.bytes() as c_uint);
+            llvm::LLVMSetAlignment(store, align.bytes() as c_uint);
         }
     }
 
@@ -672,7 +780,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         // WebAssembly has saturating floating point to integer casts if the
         // `nontrapping-fptoint` target feature is activated. We'll use those if
         // they are available.
-        if self.sess().target.arch == "wasm32"
+        if self.sess().target.options.is_like_wasm
             && self.sess().target_features.contains(&sym::nontrapping_dash_fptoint)
         {
             let src_ty = self.cx.val_ty(val);
@@ -697,7 +805,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         // WebAssembly has saturating floating point to integer casts if the
         // `nontrapping-fptoint` target feature is activated. We'll use those if
         // they are available.
-        if self.sess().target.arch == "wasm32"
+        if self.sess().target.options.is_like_wasm
             && self.sess().target_features.contains(&sym::nontrapping_dash_fptoint)
         {
             let src_ty = self.cx.val_ty(val);
@@ -924,6 +1032,72 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn atomic_element_memcpy(
+        &mut self,
+        dst: &'ll Value,
+        dst_align: Align,
+        src: &'ll Value,
+        src_align: Align,
+        size: &'ll Value,
+        element_size: Size,
+    ) {
+        self.check_atomic_element_size(element_size, &[dst_align, src_align]);
+        let size = self.intcast(size, self.type_isize(), false);
+        let dst = self.pointercast(dst, self.type_i8p());
+        let src = self.pointercast(src, self.type_i8p());
+        unsafe {
+            llvm::LLVMRustBuildElementUnorderedAtomicMemCpy(
+                self.llbuilder,
+                dst,
+                dst_align.bytes() as c_uint,
+                src,
+                src_align.bytes() as c_uint,
+                size,
+                element_size.bytes() as c_uint,
+            );
+        }
+    }
+
+    fn atomic_element_memset(
+        &mut self,
+        ptr: &'ll Value,
+        fill_byte: &'ll Value,
+        size: &'ll Value,
+        align: Align,
+        element_size: Size,
+    ) {
+        self.check_atomic_element_size(element_size, &[align]);
+        let size = self.intcast(size, self.type_isize(), false);
+        let ptr = self.pointercast(ptr, self.type_i8p());
+        unsafe {
+            llvm::LLVMRustBuildElementUnorderedAtomicMemSet(
+                self.llbuilder,
+                ptr,
+                align.bytes() as c_uint,
+                fill_byte,
+                size,
+                element_size.bytes() as c_uint,
+            );
+        }
+    }
+
+    fn memcmp(&mut self, lhs: &'ll Value, rhs: &'ll Value, size: &'ll Value) -> &'ll Value {
+        let lhs = self.pointercast(lhs, self.type_i8p());
+        let rhs = self.pointercast(rhs, self.type_i8p());
+        let llfn = self.get_intrinsic("memcmp");
+        self.call(llfn, &[lhs, rhs, size], None)
+    }
+
+    fn bcmp(&mut self, lhs: &'ll Value, rhs: &'ll Value, size: &'ll Value) -> &'ll Value {
+        if !self.cx.has_bcmp() {
+            return self.memcmp(lhs, rhs, size);
+        }
+        let lhs = self.pointercast(lhs, self.type_i8p());
+        let rhs = self.pointercast(rhs, self.type_i8p());
+        let llfn = self.get_intrinsic("bcmp");
+        self.call(llfn, &[lhs, rhs, size], None)
+    }
+
     fn select(
         &mut self,
         cond: &'ll Value,
@@ -951,6 +1125,12 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn scalable_vector_splat(&mut self, min_num_elts: usize, elt: &'ll Value) -> &'ll Value {
+        unsafe {
+            llvm::LLVMRustBuildScalableVectorSplat(self.llbuilder, min_num_elts as c_uint, elt)
+        }
+    }
+
     fn extract_value(&mut self, agg_val: &'ll Value, idx: u64) -> &'ll Value {
         assert_eq!(idx as c_uint as u64, idx);
         unsafe { llvm::LLVMBuildExtractValue(self.llbuilder, agg_val, idx as c_uint, UNNAMED) }
@@ -1117,6 +1297,22 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn type_checked_load(
+        &mut self,
+        llvtable: &'ll Value,
+        vtable_byte_offset: u64,
+        typeid: &str,
+    ) -> &'ll Value {
+        let llvtable = self.pointercast(llvtable, self.type_i8p());
+        let typeid = self.cx.typeid_metadata(typeid);
+        let type_checked_load = self.get_intrinsic("llvm.type.checked.load");
+        let offset = self.const_i32(vtable_byte_offset as i32);
+        // The second element tells whether the vtable is a member of `typeid`,
+        // which it always is for well-formed trait objects.
+        let res = self.call(type_checked_load, &[llvtable, offset, typeid], None);
+        self.extract_value(res, 0)
+    }
+
     fn lifetime_start(&mut self, ptr: &'ll Value, size: Size) {
         self.call_lifetime_intrinsic("llvm.lifetime.start.p0i8", ptr, size);
     }
@@ -1147,7 +1343,8 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
                 llfn,
                 args.as_ptr() as *const &llvm::Value,
                 args.len() as c_uint,
-                None,
+                ptr::null(),
+                0,
             );
         }
     }
@@ -1158,20 +1355,22 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         args: &[&'ll Value],
         funclet: Option<&Funclet<'ll>>,
     ) -> &'ll Value {
-        debug!("call {:?} with args ({:?})", llfn, args);
-
-        let args = self.check_call("call", llfn, args);
         let bundle = funclet.map(|funclet| funclet.bundle());
-        let bundle = bundle.as_ref().map(|b| &*b.raw);
+        let bundles = bundle.as_ref().map_or(&[][..], slice::from_ref);
+        self.call_with_bundles(llfn, args, bundles)
+    }
 
-        unsafe {
-            llvm::LLVMRustBuildCall(
-                self.llbuilder,
-                llfn,
-                args.as_ptr() as *const &llvm::Value,
-                args.len() as c_uint,
-                bundle,
-            )
+    fn call_with_operand_bundle(
+        &mut self,
+        llfn: &'ll Value,
+        args: &[&'ll Value],
+        funclet: Option<&Funclet<'ll>>,
+        tag: &str,
+    ) -> &'ll Value {
+        let bundle = OperandBundleDef::new(tag, &[]);
+        match funclet {
+            Some(funclet) => self.call_with_bundles(llfn, args, &[funclet.bundle(), &bundle]),
+            None => self.call_with_bundles(llfn, args, &[&bundle]),
         }
     }
 
@@ -1187,8 +1386,25 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         llvm::LLVMDeleteBasicBlock(bb);
     }
 
-    fn do_not_inline(&mut self, llret: &'ll Value) {
-        llvm::Attribute::NoInline.apply_callsite(llvm::AttributePlace::Function, llret);
+    fn apply_callsite_attrs(&mut self, llret: &'ll Value, attrs: &[CallSiteAttr]) {
+        for attr in attrs {
+            let attr = match attr {
+                CallSiteAttr::Cold => llvm::Attribute::Cold,
+                CallSiteAttr::NoInline => llvm::Attribute::NoInline,
+                CallSiteAttr::NoReturn => llvm::Attribute::NoReturn,
+            };
+            attr.apply_callsite(llvm::AttributePlace::Function, llret);
+        }
+    }
+
+    fn set_tail_call_kind(
+        &mut self,
+        llret: &'ll Value,
+        kind: rustc_codegen_ssa::common::TailCallKind,
+    ) {
+        unsafe {
+            llvm::LLVMRustSetTailCallKind(llret, llvm::TailCallKind::from_generic(kind));
+        }
     }
 }
 
@@ -1200,6 +1416,43 @@ impl StaticBuilderMethods for Builder<'a, 'll, 'tcx> {
 }
 
 impl Builder<'a, 'll, 'tcx> {
+    /// Calls the 128-bit multiplication libcall `name(lhs, rhs, &mut overflow)`,
+    /// returning the product and whether it overflowed like the
+    /// `llvm.*mul.with.overflow.i128` intrinsics do.
+    fn call_mulo_libcall(
+        &mut self,
+        name: &str,
+        lhs: &'ll Value,
+        rhs: &'ll Value,
+        funclet: Option<&Funclet<'ll>>,
+    ) -> (&'ll Value, &'ll Value) {
+        let i128_ty = self.type_i128();
+        let i32_align = self.tcx.data_layout.i32_align.abi;
+        let fn_ty = self.type_func(&[i128_ty, i128_ty, self.type_ptr_to(self.type_i32())], i128_ty);
+        let mulo = self.declare_cfn(name, fn_ty);
+
+        let overflow = self.alloca(self.type_i32(), i32_align);
+        let res = self.call(mulo, &[lhs, rhs, overflow], funclet);
+        let overflow = self.load(overflow, i32_align);
+        (res, self.icmp(IntPredicate::IntNE, overflow, self.const_i32(0)))
+    }
+
+    /// LLVM rejects element-wise atomic memory intrinsics whose element size
+    /// isn't a power of two within the target's atomic width, or exceeds the
+    /// alignment of the pointers. Callers check this before getting here.
+    fn check_atomic_element_size(&self, element_size: Size, aligns: &[Align]) {
+        if !element_size.bytes().is_power_of_two()
+            || element_size.bits() > self.tcx.sess.target.max_atomic_width()
+        {
+            bug!("invalid atomic element size: {:?}", element_size);
+        }
+        for align in aligns {
+            if align.bytes() < element_size.bytes() {
+                bug!("atomic element of {:?} misaligned to {:?}", element_size, align);
+            }
+        }
+    }
+
     pub fn llfn(&self) -> &'ll Value {
         unsafe { llvm::LLVMGetBasicBlockParent(self.llbb()) }
     }
@@ -1210,14 +1463,6 @@ impl Builder<'a, 'll, 'tcx> {
         }
     }
 
-    pub fn minnum(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
-        unsafe { llvm::LLVMRustBuildMinNum(self.llbuilder, lhs, rhs) }
-    }
-
-    pub fn maxnum(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
-        unsafe { llvm::LLVMRustBuildMaxNum(self.llbuilder, lhs, rhs) }
-    }
-
     pub fn insert_element(
         &mut self,
         vec: &'ll Value,
@@ -1310,6 +1555,60 @@ impl Builder<'a, 'll, 'tcx> {
         }
     }
 
+    /// Emits a call carrying the given operand bundles, e.g. a `"funclet"` bundle on MSVC
+    /// or `"deopt"`/`"gc-transition"` bundles for GC statepoints and deoptimization.
+    pub fn call_with_bundles(
+        &mut self,
+        llfn: &'ll Value,
+        args: &[&'ll Value],
+        bundles: &[&OperandBundleDef<'ll>],
+    ) -> &'ll Value {
+        debug!("call {:?} with args ({:?})", llfn, args);
+
+        let args = self.check_call("call", llfn, args);
+        let bundles: SmallVec<[_; 2]> = bundles.iter().map(|b| &*b.raw).collect();
+
+        unsafe {
+            llvm::LLVMRustBuildCall(
+                self.llbuilder,
+                llfn,
+                args.as_ptr() as *const &llvm::Value,
+                args.len() as c_uint,
+                bundles.as_ptr(),
+                bundles.len() as c_uint,
+            )
+        }
+    }
+
+    /// Like `call_with_bundles`, but emits an `invoke` unwinding to `catch`.
+    pub fn invoke_with_bundles(
+        &mut self,
+        llfn: &'ll Value,
+        args: &[&'ll Value],
+        then: &'ll BasicBlock,
+        catch: &'ll BasicBlock,
+        bundles: &[&OperandBundleDef<'ll>],
+    ) -> &'ll Value {
+        debug!("invoke {:?} with args ({:?})", llfn, args);
+
+        let args = self.check_call("invoke", llfn, args);
+        let bundles: SmallVec<[_; 2]> = bundles.iter().map(|b| &*b.raw).collect();
+
+        unsafe {
+            llvm::LLVMRustBuildInvoke(
+                self.llbuilder,
+                llfn,
+                args.as_ptr(),
+                args.len() as c_uint,
+                then,
+                catch,
+                bundles.as_ptr(),
+                bundles.len() as c_uint,
+                UNNAMED,
+            )
+        }
+    }
+
     pub fn catch_ret(&mut self, funclet: &Funclet<'ll>, unwind: &'ll BasicBlock) -> &'ll Value {
         let ret =
             unsafe { llvm::LLVMRustBuildCatchRet(self.llbuilder, funclet.cleanuppad(), unwind) };
@@ -1428,7 +1727,7 @@ impl Builder<'a, 'll, 'tcx> {
     }
 
     fn wasm_and_missing_nontrapping_fptoint(&self) -> bool {
-        self.sess().target.arch == "wasm32"
+        self.sess().target.options.is_like_wasm
             && !self.sess().target_features.contains(&sym::nontrapping_dash_fptoint)
     }
 }
@@ -134,9 +134,18 @@ pub fn get_fn(cx: &CodegenCx<'ll, 'tcx>, instance: Instance<'tcx>) -> &'ll Value
                     } else {
                         // This is a monomorphization of a generic function
                         // defined in an upstream crate.
-                        if instance.upstream_monomorphization(tcx).is_some() {
+                        if let Some(upstream_crate) = instance.upstream_monomorphization(tcx) {
                             // This is instantiated in another crate. It cannot
-                            // be `hidden`.
+                            // be `hidden`. If that crate is a Rust dylib we
+                            // reference the shared copy through its import
+                            // rather than through a linker-generated thunk.
+                            if cx.use_dll_storage_attrs && cx.is_linked_dynamically(upstream_crate)
+                            {
+                                llvm::LLVMSetDLLStorageClass(
+                                    llfn,
+                                    llvm::DLLStorageClass::DllImport,
+                                );
+                            }
                         } else {
                             // This is a local instantiation of an upstream definition.
                             // If the current crate does not re-export it
@@ -8,9 +8,9 @@ use crate::type_of::LayoutLlvmExt;
 use crate::value::Value;
 
 use rustc_ast::Mutability;
+use rustc_codegen_ssa::common::TypeKind;
 use rustc_codegen_ssa::mir::place::PlaceRef;
 use rustc_codegen_ssa::traits::*;
-use rustc_middle::bug;
 use rustc_middle::mir::interpret::{Allocation, GlobalAlloc, Scalar};
 use rustc_middle::ty::layout::TyAndLayout;
 use rustc_span::symbol::Symbol;
@@ -119,9 +119,9 @@ impl CodegenCx<'ll, 'tcx> {
                 !null_terminated as Bool,
             );
             let sym = self.generate_local_symbol_name("str");
-            let g = self.define_global(&sym[..], self.val_ty(sc)).unwrap_or_else(|| {
-                bug!("symbol `{}` is already defined", sym);
-            });
+            let g = self
+                .define_global(&sym[..], self.val_ty(sc))
+                .unwrap_or_else(|err| self.report_symbol_conflict(None, err));
             llvm::LLVMSetInitializer(g, sc);
             llvm::LLVMSetGlobalConstant(g, True);
             llvm::LLVMRustSetLinkage(g, llvm::Linkage::InternalLinkage);
@@ -227,6 +227,38 @@ impl ConstMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         })
     }
 
+    fn const_to_opt_i128(&self, v: &'ll Value) -> Option<i128> {
+        self.const_to_opt_u128(v, true).map(|v| v as i128)
+    }
+
+    fn const_vector_elements(&self, v: &'ll Value) -> Option<Vec<&'ll Value>> {
+        unsafe {
+            if llvm::LLVMIsConstant(v) == False {
+                return None;
+            }
+            let ty = self.val_ty(v);
+            match self.type_kind(ty) {
+                TypeKind::Vector => {
+                    let len = llvm::LLVMGetVectorSize(ty);
+                    Some(
+                        (0..len)
+                            .map(|i| llvm::LLVMConstExtractElement(v, self.const_u32(i)))
+                            .collect(),
+                    )
+                }
+                TypeKind::Array => {
+                    let len = llvm::LLVMGetArrayLength(ty);
+                    Some((0..len).map(|i| self.const_get_elt(v, u64::from(i))).collect())
+                }
+                TypeKind::Struct => {
+                    let len = llvm::LLVMCountStructElementTypes(ty);
+                    Some((0..len).map(|i| self.const_get_elt(v, u64::from(i))).collect())
+                }
+                _ => None,
+            }
+        }
+    }
+
     fn scalar_to_backend(&self, cv: Scalar, layout: &abi::Scalar, llty: &'ll Type) -> &'ll Value {
         let bitsize = if layout.is_bool() { 1 } else { layout.value.size(self).bits() };
         match cv {
@@ -1,28 +1,32 @@
 use crate::base;
 use crate::common::CodegenCx;
 use crate::debuginfo;
-use crate::llvm::{self, True};
+use crate::llvm::{self, False, True};
 use crate::type_::Type;
 use crate::type_of::LayoutLlvmExt;
 use crate::value::Value;
 use libc::c_uint;
 use rustc_codegen_ssa::traits::*;
+use rustc_data_structures::base_n;
 use rustc_data_structures::const_cstr;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::Node;
-use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
+use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs, GpuAddressSpace};
 use rustc_middle::mir::interpret::{
     read_target_uint, Allocation, ErrorHandled, GlobalAlloc, Pointer,
 };
 use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::ty::{self, Instance, Ty};
 use rustc_middle::{bug, span_bug};
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
 use rustc_target::abi::{AddressSpace, Align, HasDataLayout, LayoutOf, Primitive, Scalar, Size};
 use tracing::debug;
 
+use std::hash::Hash;
+
 pub fn const_alloc_to_llvm(cx: &CodegenCx<'ll, '_>, alloc: &Allocation) -> &'ll Value {
     let mut llvals = Vec::with_capacity(alloc.relocations().len() + 1);
     let dl = cx.data_layout();
@@ -142,9 +146,9 @@ fn check_and_apply_linkage(
             // zero.
             let mut real_name = "_rust_extern_with_linkage_".to_string();
             real_name.push_str(&sym);
-            let g2 = cx.define_global(&real_name, llty).unwrap_or_else(|| {
-                cx.sess().span_fatal(span, &format!("symbol `{}` is already defined", &sym))
-            });
+            let g2 = cx
+                .define_global(&real_name, llty)
+                .unwrap_or_else(|err| cx.report_symbol_conflict(Some(span), err));
             llvm::LLVMRustSetLinkage(g2, llvm::Linkage::InternalLinkage);
             llvm::LLVMSetInitializer(g2, g1);
             g2
@@ -156,6 +160,15 @@ fn check_and_apply_linkage(
     }
 }
 
+/// Returns the NVPTX address space of a `#[address_space]` static.
+fn nvptx_address_space(address_space: GpuAddressSpace) -> AddressSpace {
+    match address_space {
+        GpuAddressSpace::Shared => AddressSpace(3),
+        GpuAddressSpace::Constant => AddressSpace(4),
+        GpuAddressSpace::Local => AddressSpace(5),
+    }
+}
+
 pub fn ptrcast(val: &'ll Value, ty: &'ll Type) -> &'ll Value {
     unsafe { llvm::LLVMConstPointerCast(val, ty) }
 }
@@ -175,9 +188,9 @@ impl CodegenCx<'ll, 'tcx> {
             let gv = match kind {
                 Some(kind) if !self.tcx.sess.fewer_names() => {
                     let name = self.generate_local_symbol_name(kind);
-                    let gv = self.define_global(&name[..], self.val_ty(cv)).unwrap_or_else(|| {
-                        bug!("symbol `{}` is already defined", name);
-                    });
+                    let gv = self
+                        .define_global(&name[..], self.val_ty(cv))
+                        .unwrap_or_else(|err| self.report_symbol_conflict(None, err));
                     llvm::LLVMRustSetLinkage(gv, llvm::Linkage::PrivateLinkage);
                     gv
                 }
@@ -190,6 +203,92 @@ impl CodegenCx<'ll, 'tcx> {
         }
     }
 
+    /// Adds `data` as a new entry of the section `section` in the final linked
+    /// artifact.
+    ///
+    /// Every codegen unit and every crate calling this with the same section
+    /// contributes a separate entry, which the linker concatenates into a single
+    /// output section. The entry gets internal linkage and no COMDAT, so it is
+    /// never deduplicated against entries from other objects, and it is added
+    /// to `llvm.used` so that LLVM does not remove it.
+    ///
+    /// `llvm.used` only protects the entry from the linker's dead code stripping
+    /// on Mach-O and COFF. On ELF, `--gc-sections` keeps the section only
+    /// because its name is a valid C identifier and the program refers to the
+    /// `__start_<section>`/`__stop_<section>` symbols to walk it, so `section`
+    /// must be such a name. On Mach-O a bare section name is placed in the
+    /// `__DATA` segment.
+    crate fn append_to_linked_section(
+        &self,
+        name: &str,
+        section: &str,
+        data: &'ll Value,
+        align: Align,
+    ) -> &'ll Value {
+        let section = if self.sess().target.options.is_like_osx && !section.contains(',') {
+            format!("__DATA,{}", section)
+        } else {
+            section.to_string()
+        };
+
+        let gv = llvm::add_global(self.llmod, self.val_ty(data), name);
+        llvm::set_initializer(gv, data);
+        llvm::set_global_constant(gv, true);
+        llvm::set_linkage(gv, llvm::Linkage::InternalLinkage);
+        llvm::set_section(gv, &section);
+        set_global_alignment(self, gv, align);
+        self.add_used_global(gv);
+        gv
+    }
+
+    /// Returns a pointer to the `usize` count of the hit counter for the
+    /// `hit_counter` intrinsic call at `span`.
+    ///
+    /// Each counter is an entry `{ count: usize, location: &'static str }` in the
+    /// `rust_hit_counts` section, so a runtime can walk every counter of the
+    /// program between the linker-provided bounds of that section.
+    ///
+    /// The call may be codegened more than once, in every instantiation of a
+    /// generic function or every codegen unit and crate it is inlined into. All
+    /// of these share one counter, whose symbol name is derived from the call
+    /// site, and which is `linkonce_odr` so the linker keeps a single copy of it
+    /// per linked image.
+    crate fn hit_counter(&self, span: Span) -> &'ll Value {
+        let topmost = span.ctxt().outer_expn().expansion_cause().unwrap_or(span);
+        let loc = self.sess().source_map().lookup_char_pos(topmost.lo());
+        let location = format!("{}:{}:{}", loc.file.name, loc.line, loc.col.to_usize() + 1);
+
+        // The location alone doesn't identify the call site, other crates may
+        // have files with the same name.
+        let mut hasher = StableHasher::new();
+        self.tcx.crate_disambiguator(loc.file.cnum).hash(&mut hasher);
+        location.hash(&mut hasher);
+        let hash: u64 = hasher.finish();
+        let hash = base_n::encode(hash as u128, base_n::ALPHANUMERIC_ONLY);
+        let name = format!("__rust_hit_counter_{}", hash);
+
+        let gv = match self.get_defined_value(&name) {
+            Some(gv) => gv,
+            None => {
+                let (location_ptr, location_len) = self.const_str(Symbol::intern(&location));
+                let entry =
+                    self.const_struct(&[self.const_usize(0), location_ptr, location_len], false);
+                let align = self.data_layout().pointer_align.abi;
+                let gv = self.append_to_linked_section(&name, "rust_hit_counts", entry, align);
+                // Unlike most linked section entries, counters are written at
+                // runtime and deduplicated.
+                llvm::set_global_constant(gv, false);
+                llvm::set_linkage(gv, llvm::Linkage::LinkOnceODRLinkage);
+                llvm::SetUniqueComdat(self.llmod, gv);
+                unsafe {
+                    llvm::LLVMRustSetVisibility(gv, llvm::Visibility::Hidden);
+                }
+                gv
+            }
+        };
+        self.const_bitcast(gv, self.type_ptr_to(self.type_isize()))
+    }
+
     crate fn get_static(&self, def_id: DefId) -> &'ll Value {
         let instance = Instance::mono(self.tcx, def_id);
         if let Some(&g) = self.instances.borrow().get(&instance) {
@@ -210,6 +309,12 @@ impl CodegenCx<'ll, 'tcx> {
 
         debug!("get_static: sym={} instance={:?}", sym, instance);
 
+        if let Some(address_space) = self.tcx.codegen_fn_attrs(def_id).address_space {
+            let g = self.get_gpu_static(def_id, ty, sym, address_space);
+            self.instances.borrow_mut().insert(instance, g);
+            return g;
+        }
+
         let g = if let Some(def_id) = def_id.as_local() {
             let id = self.tcx.hir().local_def_id_to_hir_id(def_id);
             let llty = self.layout_of(ty).llvm_type(self);
@@ -298,7 +403,12 @@ impl CodegenCx<'ll, 'tcx> {
                 // crates, so there are cases where a static with an upstream DefId
                 // is actually present in the current crate. We can find out via the
                 // is_codegened_item query.
-                if !self.tcx.is_codegened_item(def_id) {
+                //
+                // When every output links the upstream crate statically the static
+                // ends up in the same image, and going through a `__imp_` stub would
+                // only cost a runtime pseudo-relocation (and a copy-on-write page with
+                // MinGW), so only mark it when it can actually come from a dll.
+                if !self.tcx.is_codegened_item(def_id) && !self.is_linked_statically(def_id.krate) {
                     unsafe {
                         llvm::LLVMSetDLLStorageClass(g, llvm::DLLStorageClass::DllImport);
                     }
@@ -317,6 +427,30 @@ impl CodegenCx<'ll, 'tcx> {
         self.instances.borrow_mut().insert(instance, g);
         g
     }
+
+    /// Declares a static placed in a GPU address space by `#[address_space]`.
+    ///
+    /// The returned address is cast to the generic address space, which is the
+    /// one every pointer is in as far as the rest of codegen is concerned.
+    fn get_gpu_static(
+        &self,
+        def_id: DefId,
+        ty: Ty<'tcx>,
+        sym: &str,
+        address_space: GpuAddressSpace,
+    ) -> &'ll Value {
+        let llty = self.layout_of(ty).llvm_type(self);
+        let g = self.declare_global_in_addrspace(sym, llty, nvptx_address_space(address_space));
+        if def_id.is_local()
+            && !self.tcx.is_foreign_item(def_id)
+            && !self.tcx.is_reachable_non_generic(def_id)
+        {
+            unsafe {
+                llvm::LLVMRustSetVisibility(g, llvm::Visibility::Hidden);
+            }
+        }
+        unsafe { llvm::LLVMConstAddrSpaceCast(g, self.type_ptr_to(llty)) }
+    }
 }
 
 impl StaticMethods for CodegenCx<'ll, 'tcx> {
@@ -351,6 +485,9 @@ impl StaticMethods for CodegenCx<'ll, 'tcx> {
             };
 
             let g = self.get_static(def_id);
+            // Statics in a GPU address space are only referred to through a
+            // cast of them.
+            let g = if attrs.address_space.is_some() { llvm::LLVMRustStripPointerCasts(g) } else { g };
 
             // boolean SSA values are i1, but they have to be stored in i8 slots,
             // otherwise some LLVM optimization passes don't work as expected
@@ -376,12 +513,21 @@ impl StaticMethods for CodegenCx<'ll, 'tcx> {
                 let linkage = llvm::LLVMRustGetLinkage(g);
                 let visibility = llvm::LLVMRustGetVisibility(g);
 
-                let new_g = llvm::LLVMRustGetOrInsertGlobal(
-                    self.llmod,
-                    name.as_ptr().cast(),
-                    name.len(),
-                    val_llty,
-                );
+                let new_g = match attrs.address_space {
+                    Some(address_space) => llvm::LLVMRustGetOrInsertGlobalInAddrSpace(
+                        self.llmod,
+                        name.as_ptr().cast(),
+                        name.len(),
+                        val_llty,
+                        nvptx_address_space(address_space).0,
+                    ),
+                    None => llvm::LLVMRustGetOrInsertGlobal(
+                        self.llmod,
+                        name.as_ptr().cast(),
+                        name.len(),
+                        val_llty,
+                    ),
+                };
 
                 llvm::LLVMRustSetLinkage(new_g, linkage);
                 llvm::LLVMRustSetVisibility(new_g, visibility);
@@ -393,6 +539,25 @@ impl StaticMethods for CodegenCx<'ll, 'tcx> {
                 new_g
             };
             set_global_alignment(&self, g, self.align_of(ty));
+
+            // Shared and local memory is not initialized when a kernel starts,
+            // so only an initializer that doesn't matter is accepted.
+            let v = match attrs.address_space {
+                Some(GpuAddressSpace::Shared | GpuAddressSpace::Local) => {
+                    if llvm::LLVMIsNull(v) == False {
+                        self.sess()
+                            .struct_span_err(
+                                self.tcx.def_span(def_id),
+                                "statics in the `shared` or `local` address space must be \
+                                 zero-initialized",
+                            )
+                            .note("their memory is uninitialized when a kernel starts")
+                            .emit();
+                    }
+                    llvm::LLVMGetUndef(val_llty)
+                }
+                _ => v,
+            };
             llvm::LLVMSetInitializer(g, v);
 
             // As an optimization, all shared statics which do not have interior
@@ -463,7 +628,7 @@ impl StaticMethods for CodegenCx<'ll, 'tcx> {
 
             // Wasm statics with custom link sections get special treatment as they
             // go into custom sections of the wasm executable.
-            if self.tcx.sess.opts.target_triple.triple().starts_with("wasm32") {
+            if self.tcx.sess.target.options.is_like_wasm {
                 if let Some(section) = attrs.link_section {
                     let section = llvm::LLVMMDStringInContext(
                         self.llcx,
@@ -505,4 +670,23 @@ impl StaticMethods for CodegenCx<'ll, 'tcx> {
         let cast = unsafe { llvm::LLVMConstPointerCast(global, self.type_i8p()) };
         self.used_statics.borrow_mut().push(cast);
     }
+
+    fn add_vtable_type_metadata(&self, vtable: &'ll Value, typeid: &str, local_calls_only: bool) {
+        // The vtable pointer points at the start of the global, so its address
+        // point is at offset 0.
+        let type_md = [self.const_u64(0), self.typeid_metadata(typeid)];
+        // `-Z virtual-function-elimination` requires fat LTO, so the local
+        // calls are all in the linkage unit of the vtable. Entries of the
+        // other vtables may be loaded by prebuilt code and have to be kept.
+        let visibility = if local_calls_only {
+            llvm::VCallVisibility::LinkageUnit
+        } else {
+            llvm::VCallVisibility::Public
+        };
+        unsafe {
+            let type_md = llvm::LLVMMDNodeInContext(self.llcx, type_md.as_ptr(), 2);
+            llvm::LLVMRustGlobalAddMetadata(vtable, llvm::MD_type as c_uint, type_md);
+            llvm::LLVMRustGlobalSetVCallVisibility(vtable, visibility);
+        }
+    }
 }
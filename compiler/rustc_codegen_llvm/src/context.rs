@@ -1,4 +1,6 @@
+use crate::asm::ModuleAsmSrcLoc;
 use crate::attributes;
+use crate::builder::Builder;
 use crate::callee::get_fn;
 use crate::coverageinfo;
 use crate::debuginfo;
@@ -7,13 +9,17 @@ use crate::llvm_util;
 use crate::type_::Type;
 use crate::value::Value;
 
-use rustc_codegen_ssa::base::wants_msvc_seh;
+use libc::c_uint;
+use rustc_codegen_ssa::base::{wants_funclets, wants_msvc_seh};
+use rustc_codegen_ssa::common::PANIC_CALLSITE_ATTRS;
 use rustc_codegen_ssa::traits::*;
 use rustc_data_structures::base_n;
 use rustc_data_structures::const_cstr;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::small_c_str::SmallCStr;
+use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_middle::bug;
+use rustc_middle::middle::dependency_format::Linkage;
 use rustc_middle::mir::mono::CodegenUnit;
 use rustc_middle::ty::layout::{HasParamEnv, LayoutError, TyAndLayout};
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
@@ -21,6 +27,7 @@ use rustc_session::config::{CFGuard, CrateType, DebugInfo};
 use rustc_session::Session;
 use rustc_span::source_map::{Span, DUMMY_SP};
 use rustc_span::symbol::Symbol;
+use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{HasDataLayout, LayoutOf, PointeeInfo, Size, TargetDataLayout, VariantIdx};
 use rustc_target::spec::{HasTargetSpec, RelocModel, Target, TlsModel};
 
@@ -36,6 +43,8 @@ pub struct CodegenCx<'ll, 'tcx> {
     pub check_overflow: bool,
     pub use_dll_storage_attrs: bool,
     pub tls_model: llvm::ThreadLocalMode,
+    /// Cache of `attributes::llvm_target_features`, too costly to validate for each function
+    pub target_features: Vec<&'tcx str>,
 
     pub llmod: &'ll llvm::Module,
     pub llcx: &'ll llvm::Context,
@@ -46,6 +55,8 @@ pub struct CodegenCx<'ll, 'tcx> {
     /// Cache generated vtables
     pub vtables:
         RefCell<FxHashMap<(Ty<'tcx>, Option<ty::PolyExistentialTraitRef<'tcx>>), &'ll Value>>,
+    /// Cache of panic stubs generated by `-Z outline-panic-stubs`
+    pub panic_stubs: RefCell<FxHashMap<(Symbol, Span), &'ll Value>>,
     /// Cache of constant strings,
     pub const_cstr_cache: RefCell<FxHashMap<Symbol, &'ll Value>>,
 
@@ -73,6 +84,10 @@ pub struct CodegenCx<'ll, 'tcx> {
     /// See <http://llvm.org/docs/LangRef.html#the-llvm-used-global-variable> for details
     pub used_statics: RefCell<Vec<&'ll Value>>,
 
+    /// Which item each part of the module-level assembly came from, used to
+    /// point errors in it at the right `global_asm!`.
+    pub module_asm_srclocs: RefCell<Vec<ModuleAsmSrcLoc>>,
+
     pub lltypes: RefCell<FxHashMap<(Ty<'tcx>, Option<VariantIdx>), &'ll Type>>,
     pub scalar_lltypes: RefCell<FxHashMap<Ty<'tcx>, &'ll Type>>,
     pub pointee_infos: RefCell<FxHashMap<(Ty<'tcx>, Size), Option<PointeeInfo>>>,
@@ -89,6 +104,9 @@ pub struct CodegenCx<'ll, 'tcx> {
 
     /// A counter that is used for generating local symbol names
     local_gen_sym_counter: Cell<usize>,
+    /// Per-prefix counters used instead of `local_gen_sym_counter` with
+    /// `-Z deterministic-ir-names`
+    local_gen_sym_counters: RefCell<FxHashMap<String, usize>>,
 }
 
 fn to_llvm_tls_model(tls_model: TlsModel) -> llvm::ThreadLocalMode {
@@ -109,9 +127,13 @@ fn strip_x86_address_spaces(data_layout: String) -> String {
     data_layout.replace("-p270:32:32-p271:32:32-p272:64:64-", "-")
 }
 
+/// Creates an LLVM module set up for the session's target. `tm` is the target
+/// machine the module will be compiled with, which is also used to check our
+/// data layout against LLVM's.
 pub unsafe fn create_module(
     tcx: TyCtxt<'_>,
     llcx: &'ll llvm::Context,
+    tm: &llvm::TargetMachine,
     mod_name: &str,
 ) -> &'ll llvm::Module {
     let sess = tcx.sess;
@@ -130,9 +152,7 @@ pub unsafe fn create_module(
 
     // Ensure the data-layout values hardcoded remain the defaults.
     if sess.target.options.is_builtin {
-        let tm = crate::back::write::create_informational_target_machine(tcx.sess);
         llvm::LLVMRustSetDataLayoutFromTargetMachine(llmod, tm);
-        llvm::LLVMRustDisposeTargetMachine(tm);
 
         let llvm_data_layout = llvm::LLVMGetDataLayoutStr(llmod);
         let llvm_data_layout = str::from_utf8(CStr::from_ptr(llvm_data_layout).to_bytes())
@@ -189,6 +209,12 @@ pub unsafe fn create_module(
         llvm::LLVMRustAddModuleFlag(llmod, avoid_plt, 1);
     }
 
+    // Lets GlobalDCE remove the vtable entries that are never loaded through
+    // `llvm.type.checked.load`.
+    if sess.opts.debugging_opts.virtual_function_elimination {
+        llvm::LLVMRustAddModuleFlag(llmod, "Virtual Function Elim\0".as_ptr().cast(), 1);
+    }
+
     // Control Flow Guard is currently only supported by the MSVC linker on Windows.
     if sess.target.options.is_like_msvc {
         match sess.opts.cg.control_flow_guard {
@@ -204,6 +230,26 @@ pub unsafe fn create_module(
         }
     }
 
+    // Insert `llvm.ident` metadata recording the compiler that produced the module. On ELF
+    // targets it ends up in the `.comment` section, and on the wasm targets it gets hooked
+    // up to the "producer" sections `processed-by` information.
+    let rustc_producer = format!(
+        "rustc version {} (LLVM {}.{})",
+        option_env!("CFG_VERSION").expect("CFG_VERSION"),
+        llvm::LLVMRustVersionMajor(),
+        llvm::LLVMRustVersionMinor(),
+    );
+    let name_metadata = llvm::LLVMMDStringInContext(
+        llcx,
+        rustc_producer.as_ptr().cast(),
+        rustc_producer.as_bytes().len() as c_uint,
+    );
+    llvm::LLVMAddNamedMetadataOperand(
+        llmod,
+        const_cstr!("llvm.ident").as_ptr(),
+        llvm::LLVMMDNodeInContext(llcx, &name_metadata, 1),
+    );
+
     llmod
 }
 
@@ -295,16 +341,19 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
             check_overflow,
             use_dll_storage_attrs,
             tls_model,
+            target_features: attributes::llvm_target_features(tcx.sess).collect(),
             llmod,
             llcx,
             codegen_unit,
             instances: Default::default(),
             vtables: Default::default(),
+            panic_stubs: Default::default(),
             const_cstr_cache: Default::default(),
             const_unsized: Default::default(),
             const_globals: Default::default(),
             statics_to_rauw: RefCell::new(Vec::new()),
             used_statics: RefCell::new(Vec::new()),
+            module_asm_srclocs: RefCell::new(Vec::new()),
             lltypes: Default::default(),
             scalar_lltypes: Default::default(),
             pointee_infos: Default::default(),
@@ -316,6 +365,7 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
             rust_try_fn: Cell::new(None),
             intrinsics: Default::default(),
             local_gen_sym_counter: Cell::new(0),
+            local_gen_sym_counters: Default::default(),
         }
     }
 
@@ -323,6 +373,36 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
         &self.statics_to_rauw
     }
 
+    /// Returns `true` if the upstream crate `cnum` is linked as a Rust dylib
+    /// into every output of this session, in which case anything it defines
+    /// can be referenced through the import table.
+    crate fn is_linked_dynamically(&self, cnum: CrateNum) -> bool {
+        let formats = self.tcx.dependency_formats(LOCAL_CRATE);
+        !formats.is_empty()
+            && formats.iter().all(|(_, list)| {
+                list.get(cnum.as_usize() - 1) == Some(&Linkage::Dynamic)
+            })
+    }
+
+    /// Returns `true` if the upstream crate `cnum` is linked statically into
+    /// every output of this session, in which case nothing it defines is ever
+    /// reached through an import table.
+    crate fn is_linked_statically(&self, cnum: CrateNum) -> bool {
+        let formats = self.tcx.dependency_formats(LOCAL_CRATE);
+        !formats.is_empty()
+            && formats.iter().all(|(_, list)| {
+                list.get(cnum.as_usize() - 1) == Some(&Linkage::Static)
+            })
+    }
+
+    /// Returns the metadata string naming the type identifier `typeid`, as
+    /// used by `!type` metadata and the `llvm.type.*` intrinsics.
+    crate fn typeid_metadata(&self, typeid: &str) -> &'ll Value {
+        unsafe {
+            llvm::LLVMMDStringInContext(self.llcx, typeid.as_ptr().cast(), typeid.len() as c_uint)
+        }
+    }
+
     #[inline]
     pub fn coverage_context(&'a self) -> Option<&'a coverageinfo::CrateCoverageContext<'tcx>> {
         self.coverage_cx.as_ref()
@@ -337,6 +417,10 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         &self.vtables
     }
 
+    fn panic_stubs(&self) -> &RefCell<FxHashMap<(Symbol, Span), &'ll Value>> {
+        &self.panic_stubs
+    }
+
     fn get_fn(&self, instance: Instance<'tcx>) -> &'ll Value {
         get_fn(self, instance)
     }
@@ -371,7 +455,7 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         }
         let tcx = self.tcx;
         let llfn = match tcx.lang_items().eh_personality() {
-            Some(def_id) if !wants_msvc_seh(self.sess()) => self.get_fn_addr(
+            Some(def_id) if !wants_funclets(self.sess()) => self.get_fn_addr(
                 ty::Instance::resolve(
                     tcx,
                     ty::ParamEnv::reveal_all(),
@@ -384,6 +468,10 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             _ => {
                 let name = if wants_msvc_seh(self.sess()) {
                     "__CxxFrameHandler3"
+                } else if wants_funclets(self.sess()) {
+                    // LLVM recognizes this name as the wasm personality and
+                    // lowers funclets to the exception-handling proposal.
+                    "__gxx_wasm_personality_v0"
                 } else {
                     "rust_eh_personality"
                 };
@@ -445,6 +533,33 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             None
         }
     }
+
+    fn define_panic_stub(
+        &self,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        panic_fn: &'ll Value,
+        panic_fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        args: &[&'ll Value],
+    ) -> &'ll Value {
+        let name = self.generate_local_symbol_name("panic_stub");
+        let llfn = self.declare_fn(&name, fn_abi);
+        llvm::set_linkage(llfn, llvm::Linkage::InternalLinkage);
+        llvm::SetUnnamedAddress(llfn, llvm::UnnamedAddr::Global);
+        llvm::Attribute::Cold.apply_llfn(llvm::AttributePlace::Function, llfn);
+        llvm::Attribute::NoInline.apply_llfn(llvm::AttributePlace::Function, llfn);
+        if self.sess().must_emit_unwind_tables() {
+            attributes::emit_uwtable(llfn, true);
+        }
+        self.set_frame_pointer_elimination(llfn);
+        self.apply_target_cpu_attr(llfn);
+
+        let mut bx = Builder::new_block(self, llfn, "start");
+        let llret = bx.call(panic_fn, args, None);
+        bx.apply_attrs_callsite(panic_fn_abi, llret);
+        bx.apply_callsite_attrs(llret, PANIC_CALLSITE_ATTRS);
+        bx.unreachable();
+        llfn
+    }
 }
 
 impl CodegenCx<'b, 'tcx> {
@@ -505,6 +620,8 @@ impl CodegenCx<'b, 'tcx> {
         let t_i128 = self.type_i128();
         let t_f32 = self.type_f32();
         let t_f64 = self.type_f64();
+        let t_isize = self.type_isize();
+        let t_token = self.type_token();
 
         macro_rules! vector_types {
             ($id_out:ident: $elem_ty:ident, $len:expr) => {
@@ -815,6 +932,9 @@ impl CodegenCx<'b, 'tcx> {
         ifn!("llvm.localrecover", fn(i8p, i8p, t_i32) -> i8p);
         ifn!("llvm.x86.seh.recoverfp", fn(i8p, i8p) -> i8p);
 
+        ifn!("llvm.wasm.get.exception", fn(t_token) -> i8p);
+        ifn!("llvm.wasm.get.ehselector", fn(t_token) -> t_i32);
+
         ifn!("llvm.assume", fn(i1) -> void);
         ifn!("llvm.prefetch", fn(i8p, t_i32, t_i32, t_i32) -> void);
 
@@ -823,10 +943,24 @@ impl CodegenCx<'b, 'tcx> {
         ifn!("llvm.va_end", fn(i8p) -> void);
         ifn!("llvm.va_copy", fn(i8p, i8p) -> void);
 
+        // Not LLVM intrinsics, but library calls that LLVM recognizes. `bcmp` is only
+        // declared where `has_bcmp` says the C library provides it.
+        ifn!("memcmp", fn(i8p, i8p, t_isize) -> t_i32);
+        if self.has_bcmp() {
+            ifn!("bcmp", fn(i8p, i8p, t_isize) -> t_i32);
+        }
+
         if self.sess().opts.debugging_opts.instrument_coverage {
             ifn!("llvm.instrprof.increment", fn(i8p, t_i64, t_i32, t_i32) -> void);
         }
 
+        if self.sess().opts.debugging_opts.virtual_function_elimination {
+            ifn!(
+                "llvm.type.checked.load",
+                fn(i8p, t_i32, self.type_metadata()) -> mk_struct! {i8p, i1}
+            );
+        }
+
         if self.sess().opts.debuginfo != DebugInfo::None {
             ifn!("llvm.dbg.declare", fn(self.type_metadata(), self.type_metadata()) -> void);
             ifn!("llvm.dbg.value", fn(self.type_metadata(), t_i64, self.type_metadata()) -> void);
@@ -858,8 +992,20 @@ impl<'b, 'tcx> CodegenCx<'b, 'tcx> {
     /// Generates a new symbol name with the given prefix. This symbol name must
     /// only be used for definitions with `internal` or `private` linkage.
     pub fn generate_local_symbol_name(&self, prefix: &str) -> String {
-        let idx = self.local_gen_sym_counter.get();
-        self.local_gen_sym_counter.set(idx + 1);
+        // When deterministic names are requested, numbering each prefix
+        // separately keeps e.g. a newly added string constant from renaming
+        // every vtable and allocation that comes after it.
+        let idx = if self.tcx.sess.opts.debugging_opts.deterministic_ir_names {
+            let mut counters = self.local_gen_sym_counters.borrow_mut();
+            let counter = counters.entry(prefix.to_string()).or_insert(0);
+            let idx = *counter;
+            *counter += 1;
+            idx
+        } else {
+            let idx = self.local_gen_sym_counter.get();
+            self.local_gen_sym_counter.set(idx + 1);
+            idx
+        };
         // Include a '.' character, so there can be no accidental conflicts with
         // user defined names
         let mut name = String::with_capacity(prefix.len() + 6);
@@ -868,6 +1014,13 @@ impl<'b, 'tcx> CodegenCx<'b, 'tcx> {
         base_n::push_str(idx as u128, base_n::ALPHANUMERIC_ONLY, &mut name);
         name
     }
+
+    /// Whether the C library of the target provides `bcmp`. This follows LLVM's
+    /// own library info, which only assumes `bcmp` on Linux with glibc or musl.
+    pub fn has_bcmp(&self) -> bool {
+        let target = &self.tcx.sess.target;
+        target.target_os == "linux" && (target.target_env == "gnu" || target.target_env == "musl")
+    }
 }
 
 impl HasDataLayout for CodegenCx<'ll, 'tcx> {
@@ -6,10 +6,10 @@ use crate::builder::Builder;
 use crate::common::CodegenCx;
 use crate::value::Value;
 use rustc_codegen_ssa::traits::*;
-use rustc_middle::bug;
 use rustc_session::config::DebugInfo;
 
 use rustc_span::symbol::sym;
+use rustc_target::abi::Align;
 
 /// Inserts a side-effect free instruction sequence that makes sure that the
 /// .debug_gdb_scripts global is referenced, so it isn't removed by the linker.
@@ -20,10 +20,7 @@ pub fn insert_reference_to_gdb_debug_scripts_section_global(bx: &mut Builder<'_,
         // LLVM to keep around the reference to the global.
         let indices = [bx.const_i32(0), bx.const_i32(0)];
         let element = bx.inbounds_gep(gdb_debug_scripts_section, &indices);
-        let volative_load_instruction = bx.volatile_load(element);
-        unsafe {
-            llvm::LLVMSetAlignment(volative_load_instruction, 1);
-        }
+        bx.volatile_load(element, Align::from_bytes(1).unwrap());
     }
 }
 
@@ -45,7 +42,7 @@ pub fn get_or_insert_gdb_debug_scripts_section_global(cx: &CodegenCx<'ll, '_>) -
 
             let section_var = cx
                 .define_global(section_var_name, llvm_type)
-                .unwrap_or_else(|| bug!("symbol `{}` is already defined", section_var_name));
+                .unwrap_or_else(|err| cx.report_symbol_conflict(None, err));
             llvm::LLVMSetSection(section_var, section_name.as_ptr().cast());
             llvm::LLVMSetInitializer(section_var, cx.const_bytes(section_contents));
             llvm::LLVMSetGlobalConstant(section_var, llvm::True);
@@ -19,9 +19,20 @@ use crate::llvm::AttributePlace::Function;
 use crate::type_::Type;
 use crate::value::Value;
 use rustc_codegen_ssa::traits::*;
+use rustc_errors::FatalError;
 use rustc_middle::ty::Ty;
+use rustc_span::Span;
+use rustc_target::abi::AddressSpace;
+use rustc_target::spec::PanicStrategy;
 use tracing::debug;
 
+/// Returned by `define_global` when the requested symbol name already has a
+/// definition in the current module.
+pub struct SymbolDefError<'ll> {
+    pub name: String,
+    pub existing: &'ll Value,
+}
+
 /// Declare a function.
 ///
 /// If there’s a value with the same name already declared, the function will
@@ -46,6 +57,12 @@ fn declare_raw_fn(
         llvm::Attribute::NoRedZone.apply_llfn(Function, llfn);
     }
 
+    // With `-C panic=abort` nothing can unwind, including the C functions and
+    // intrinsics declared without an `FnAbi`.
+    if cx.tcx.sess.panic_strategy() == PanicStrategy::Abort {
+        llvm::Attribute::NoUnwind.apply_llfn(Function, llfn);
+    }
+
     attributes::default_optimisation_attrs(cx.tcx.sess, llfn);
     attributes::non_lazy_bind(cx.sess(), llfn);
     llfn
@@ -61,6 +78,28 @@ impl CodegenCx<'ll, 'tcx> {
         unsafe { llvm::LLVMRustGetOrInsertGlobal(self.llmod, name.as_ptr().cast(), name.len(), ty) }
     }
 
+    /// Declare a global value in the address space `address_space`.
+    ///
+    /// If there’s a value with the same name already declared, the function will
+    /// return its Value instead.
+    pub fn declare_global_in_addrspace(
+        &self,
+        name: &str,
+        ty: &'ll Type,
+        address_space: AddressSpace,
+    ) -> &'ll Value {
+        debug!("declare_global_in_addrspace(name={:?}, address_space={:?})", name, address_space);
+        unsafe {
+            llvm::LLVMRustGetOrInsertGlobalInAddrSpace(
+                self.llmod,
+                name.as_ptr().cast(),
+                name.len(),
+                ty,
+                address_space.0,
+            )
+        }
+    }
+
     /// Declare a C ABI function.
     ///
     /// Only use this for foreign function ABIs and glue. For Rust functions use
@@ -87,15 +126,59 @@ impl CodegenCx<'ll, 'tcx> {
     /// Declare a global with an intention to define it.
     ///
     /// Use this function when you intend to define a global. This function will
-    /// return `None` if the name already has a definition associated with it. In that
-    /// case an error should be reported to the user, because it usually happens due
-    /// to user’s fault (e.g., misuse of `#[no_mangle]` or `#[export_name]` attributes).
-    pub fn define_global(&self, name: &str, ty: &'ll Type) -> Option<&'ll Value> {
-        if self.get_defined_value(name).is_some() {
-            None
-        } else {
-            Some(self.declare_global(name, ty))
+    /// return an error if the name already has a definition associated with it. In
+    /// that case it should be reported to the user with `report_symbol_conflict`,
+    /// because it usually happens due to user’s fault (e.g., misuse of `#[no_mangle]`
+    /// or `#[export_name]` attributes).
+    pub fn define_global(
+        &self,
+        name: &str,
+        ty: &'ll Type,
+    ) -> Result<&'ll Value, SymbolDefError<'ll>> {
+        match self.get_defined_value(name) {
+            Some(existing) => Err(SymbolDefError { name: name.to_string(), existing }),
+            None => Ok(self.declare_global(name, ty)),
+        }
+    }
+
+    /// Reports a symbol conflict returned by `define_global` as a fatal error.
+    ///
+    /// `span` points at the item being defined, if there is one. If the existing
+    /// definition belongs to an item of this crate, it is pointed at as well.
+    pub fn report_symbol_conflict(&self, span: Option<Span>, err: SymbolDefError<'ll>) -> ! {
+        let existing = self
+            .instances
+            .borrow()
+            .iter()
+            .find(|&(_, &value)| value == err.existing)
+            .map(|(instance, _)| instance.def_id());
+        let existing_span = existing.map(|def_id| self.tcx.def_span(def_id));
+
+        let msg = format!("symbol `{}` is already defined", err.name);
+        let mut diag = match span.or(existing_span) {
+            Some(span) => self.sess().struct_span_fatal(span, &msg),
+            None => self.sess().struct_fatal(&msg),
+        };
+        if let (Some(_), Some(def_id), Some(existing_span)) = (span, existing, existing_span) {
+            diag.span_label(
+                existing_span,
+                format!(
+                    "the symbol is already defined by `{}` here",
+                    self.tcx.def_path_str(def_id)
+                ),
+            );
+        } else if let Some(def_id) = existing {
+            diag.note(&format!(
+                "`{}` uses a symbol name which is reserved for a compiler-generated value",
+                self.tcx.def_path_str(def_id)
+            ));
         }
+        diag.help(
+            "give one of the items a different symbol name with `#[export_name]`, \
+             or remove its `#[no_mangle]` or `#[linkage]` attribute",
+        );
+        diag.emit();
+        FatalError.raise()
     }
 
     /// Declare a private global
@@ -7,9 +7,9 @@ use crate::type_of::LayoutLlvmExt;
 use crate::va_arg::emit_va_arg;
 use crate::value::Value;
 
-use rustc_codegen_ssa::base::{compare_simd_types, wants_msvc_seh};
+use rustc_codegen_ssa::base::{compare_simd_types, wants_funclets, wants_msvc_seh};
 use rustc_codegen_ssa::common::span_invalid_monomorphization_error;
-use rustc_codegen_ssa::common::{IntPredicate, TypeKind};
+use rustc_codegen_ssa::common::{AtomicOrdering, AtomicRmwBinOp, IntPredicate, TypeKind};
 use rustc_codegen_ssa::mir::operand::OperandRef;
 use rustc_codegen_ssa::mir::place::PlaceRef;
 use rustc_codegen_ssa::traits::*;
@@ -18,7 +18,7 @@ use rustc_middle::ty::layout::{FnAbiExt, HasTyCtxt};
 use rustc_middle::ty::{self, Ty};
 use rustc_middle::{bug, span_bug};
 use rustc_span::{sym, symbol::kw, Span, Symbol};
-use rustc_target::abi::{self, HasDataLayout, LayoutOf, Primitive};
+use rustc_target::abi::{self, Align, HasDataLayout, LayoutOf, Primitive};
 use rustc_target::spec::PanicStrategy;
 
 use std::cmp::Ordering;
@@ -50,10 +50,6 @@ fn get_simple_intrinsic(cx: &CodegenCx<'ll, '_>, name: Symbol) -> Option<&'ll Va
         sym::fmaf64 => "llvm.fma.f64",
         sym::fabsf32 => "llvm.fabs.f32",
         sym::fabsf64 => "llvm.fabs.f64",
-        sym::minnumf32 => "llvm.minnum.f32",
-        sym::minnumf64 => "llvm.minnum.f64",
-        sym::maxnumf32 => "llvm.maxnum.f32",
-        sym::maxnumf64 => "llvm.maxnum.f64",
         sym::copysignf32 => "llvm.copysign.f32",
         sym::copysignf64 => "llvm.copysign.f64",
         sym::floorf32 => "llvm.floor.f32",
@@ -129,6 +125,29 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 let llfn = self.get_intrinsic(&("llvm.debugtrap"));
                 self.call(llfn, &[], None)
             }
+            sym::hit_counter => {
+                let counter = self.cx().hit_counter(span);
+                let one = self.const_usize(1);
+                let target = &self.sess().target;
+                if target.options.atomic_cas
+                    && target.max_atomic_width() >= u64::from(target.pointer_width)
+                {
+                    self.atomic_rmw(
+                        AtomicRmwBinOp::AtomicAdd,
+                        counter,
+                        one,
+                        AtomicOrdering::Monotonic,
+                    );
+                } else {
+                    // Without atomic read-modify-writes, concurrent increments
+                    // of the same counter may be lost.
+                    let align = self.tcx.data_layout.pointer_align.abi;
+                    let count = self.load(counter, align);
+                    let count = self.add(count, one);
+                    self.store(count, counter, align);
+                }
+                return;
+            }
             sym::va_copy => {
                 let intrinsic = self.cx().get_intrinsic(&("llvm.va_copy"));
                 self.call(intrinsic, &[args[0].immediate(), args[1].immediate()], None)
@@ -166,15 +185,12 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 if let PassMode::Cast(ty) = fn_abi.ret.mode {
                     ptr = self.pointercast(ptr, self.type_ptr_to(ty.llvm_type(self)));
                 }
-                let load = self.volatile_load(ptr);
                 let align = if name == sym::unaligned_volatile_load {
-                    1
+                    Align::from_bytes(1).unwrap()
                 } else {
-                    self.align_of(tp_ty).bytes() as u32
+                    self.align_of(tp_ty)
                 };
-                unsafe {
-                    llvm::LLVMSetAlignment(load, align);
-                }
+                let load = self.volatile_load(ptr, align);
                 self.to_immediate(load, self.layout_of(tp_ty))
             }
             sym::volatile_store => {
@@ -210,13 +226,8 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                     None,
                 )
             }
-            sym::ctlz
-            | sym::ctlz_nonzero
-            | sym::cttz
-            | sym::cttz_nonzero
-            | sym::ctpop
+            sym::ctpop
             | sym::bswap
-            | sym::bitreverse
             | sym::rotate_left
             | sym::rotate_right
             | sym::saturating_add
@@ -224,17 +235,6 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 let ty = arg_tys[0];
                 match int_type_width_signed(ty, self) {
                     Some((width, signed)) => match name {
-                        sym::ctlz | sym::cttz => {
-                            let y = self.const_bool(false);
-                            let llfn = self.get_intrinsic(&format!("llvm.{}.i{}", name, width));
-                            self.call(llfn, &[args[0].immediate(), y], None)
-                        }
-                        sym::ctlz_nonzero | sym::cttz_nonzero => {
-                            let y = self.const_bool(true);
-                            let llvm_name = &format!("llvm.{}.i{}", &name_str[..4], width);
-                            let llfn = self.get_intrinsic(llvm_name);
-                            self.call(llfn, &[args[0].immediate(), y], None)
-                        }
                         sym::ctpop => self.call(
                             self.get_intrinsic(&format!("llvm.ctpop.i{}", width)),
                             &[args[0].immediate()],
@@ -251,11 +251,6 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                                 )
                             }
                         }
-                        sym::bitreverse => self.call(
-                            self.get_intrinsic(&format!("llvm.bitreverse.i{}", width)),
-                            &[args[0].immediate()],
-                            None,
-                        ),
                         sym::rotate_left | sym::rotate_right => {
                             let is_left = name == sym::rotate_left;
                             let val = args[0].immediate();
@@ -367,6 +362,8 @@ fn try_intrinsic(
         bx.store(bx.const_i32(0), dest, ret_align);
     } else if wants_msvc_seh(bx.sess()) {
         codegen_msvc_try(bx, try_func, data, catch_func, dest);
+    } else if wants_funclets(bx.sess()) {
+        codegen_wasm_try(bx, try_func, data, catch_func, dest);
     } else if bx.sess().target.options.is_like_emscripten {
         codegen_emcc_try(bx, try_func, data, catch_func, dest);
     } else {
@@ -586,6 +583,87 @@ fn codegen_gnu_try(
     bx.store(ret, dest, i32_align);
 }
 
+// Definition of the `rust_try` function for the WebAssembly exception-handling
+// proposal. It uses the same funclet-based instructions as MSVC, but wasm has
+// no type descriptors to match on: every exception is caught by a single
+// catch-all handler, and the thrown object is recovered with an intrinsic.
+fn codegen_wasm_try(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    try_func: &'ll Value,
+    data: &'ll Value,
+    catch_func: &'ll Value,
+    dest: &'ll Value,
+) {
+    let llfn = get_rust_try_fn(bx, &mut |mut bx| {
+        bx.set_personality_fn(bx.eh_personality());
+        bx.sideeffect(false);
+
+        let mut normal = bx.build_sibling_block("normal");
+        let mut catchswitch = bx.build_sibling_block("catchswitch");
+        let mut catchpad = bx.build_sibling_block("catchpad");
+        let mut caught = bx.build_sibling_block("caught");
+
+        let try_func = llvm::get_param(bx.llfn(), 0);
+        let data = llvm::get_param(bx.llfn(), 1);
+        let catch_func = llvm::get_param(bx.llfn(), 2);
+
+        // We're generating an IR snippet that looks like:
+        //
+        //   declare i32 @rust_try(%try_func, %data, %catch_func) {
+        //      invoke %try_func(%data) to label %normal unwind label %catchswitch
+        //
+        //   normal:
+        //      ret i32 0
+        //
+        //   catchswitch:
+        //      %cs = catchswitch within none [%catchpad] unwind to caller
+        //
+        //   catchpad:
+        //      %tok = catchpad within %cs [null]
+        //      %ptr = call @llvm.wasm.get.exception(token %tok)
+        //      %sel = call @llvm.wasm.get.ehselector(token %tok)
+        //      call %catch_func(%data, %ptr)
+        //      catchret from %tok to label %caught
+        //
+        //   caught:
+        //      ret i32 1
+        //   }
+        //
+        bx.invoke(try_func, &[data], normal.llbb(), catchswitch.llbb(), None);
+
+        normal.ret(bx.const_i32(0));
+
+        let cs = catchswitch.catch_switch(None, None, 1);
+        catchswitch.add_handler(cs, catchpad.llbb());
+
+        let null = bx.const_null(bx.type_i8p());
+        let funclet = catchpad.catch_pad(cs, &[null]);
+
+        let ptr = catchpad.call(
+            bx.get_intrinsic("llvm.wasm.get.exception"),
+            &[funclet.cleanuppad()],
+            Some(&funclet),
+        );
+        // The selector is unused, but LLVM's wasm EH preparation expects it
+        // to be queried alongside the exception pointer.
+        catchpad.call(
+            bx.get_intrinsic("llvm.wasm.get.ehselector"),
+            &[funclet.cleanuppad()],
+            Some(&funclet),
+        );
+        catchpad.call(catch_func, &[data, ptr], Some(&funclet));
+        catchpad.catch_ret(&funclet, caught.llbb());
+
+        caught.ret(bx.const_i32(1));
+    });
+
+    // Note that no invoke is used here because by definition this function
+    // can't panic (that's what it's catching).
+    let ret = bx.call(llfn, &[try_func, data, catch_func], None);
+    let i32_align = bx.tcx().data_layout.i32_align.abi;
+    bx.store(ret, dest, i32_align);
+}
+
 // Variant of codegen_gnu_try used for emscripten where Rust panics are
 // implemented using C++ exceptions. Here we use exceptions of a specific type
 // (`struct rust_panic`) to represent Rust panics.
@@ -855,6 +933,12 @@ fn generic_simd_intrinsic(
         ));
     }
 
+    // Lane indices passed to `simd_shuffle*`, `simd_insert` and `simd_extract`
+    // are element indices, and so are LLVM's: lane `i` of a `#[repr(simd)]`
+    // type is its `i`th field, stored at the `i`th element-sized offset from
+    // the start of the vector on every target. They are therefore forwarded
+    // to LLVM unchanged on big-endian targets too; only bitcasts between
+    // vectors and integers (see `simd_bitmask`) observe the byte order.
     if name_str.starts_with("simd_shuffle") {
         let n: u64 = name_str["simd_shuffle".len()..].parse().unwrap_or_else(|_| {
             span_bug!(span, "bad `simd_shuffle` instruction only caught in codegen?")
@@ -883,17 +967,21 @@ fn generic_simd_intrinsic(
         let total_len = u128::from(in_len) * 2;
 
         let vector = args[2].immediate();
+        let elements = bx.const_vector_elements(vector);
 
         let indices: Option<Vec<_>> = (0..n)
             .map(|i| {
                 let arg_idx = i;
-                let val = bx.const_get_elt(vector, i as u64);
-                match bx.const_to_opt_u128(val, true) {
+                let idx = elements
+                    .as_ref()
+                    .and_then(|e| e.get(i as usize))
+                    .and_then(|&val| bx.const_to_opt_i128(val));
+                match idx {
                     None => {
                         emit_error!("shuffle index #{} is not a constant", arg_idx);
                         None
                     }
-                    Some(idx) if idx >= total_len => {
+                    Some(idx) if idx < 0 || idx as u128 >= total_len => {
                         emit_error!(
                             "shuffle index #{} is out of bounds (limit {})",
                             arg_idx,
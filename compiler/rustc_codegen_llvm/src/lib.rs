@@ -11,6 +11,7 @@
 #![feature(extern_types)]
 #![feature(in_band_lifetimes)]
 #![feature(nll)]
+#![feature(once_cell)]
 #![feature(or_patterns)]
 #![recursion_limit = "256"]
 
@@ -38,6 +39,8 @@ use std::sync::Arc;
 
 mod back {
     pub mod archive;
+    pub mod jit;
+    pub mod lld;
     pub mod lto;
     mod profiling;
     pub mod write;
@@ -187,7 +190,12 @@ impl WriteBackendMethods for LlvmCodegenBackend {
         config: &ModuleConfig,
         thin: bool,
     ) {
-        back::lto::run_pass_manager(cgcx, module, config, thin)
+        back::lto::run_pass_manager(cgcx, module, config, thin);
+        // The ThinLTO driver saves its own post-pipeline snapshot, so only
+        // the fat LTO module needs to be recorded here.
+        if !thin {
+            back::write::save_temp_bitcode(cgcx, module, "lto.after-pm");
+        }
     }
 }
 
@@ -286,6 +294,9 @@ impl CodegenBackend for LlvmCodegenBackend {
             if sess.opts.debugging_opts.llvm_time_trace {
                 llvm_util::time_trace_profiler_finish("llvm_timings.json");
             }
+            if sess.opts.debugging_opts.llvm_stats {
+                llvm_util::llvm_stats_finish(sess, "llvm_stats.json");
+            }
         });
 
         Ok((codegen_results, work_products))
@@ -297,19 +308,26 @@ impl CodegenBackend for LlvmCodegenBackend {
         codegen_results: CodegenResults,
         outputs: &OutputFilenames,
     ) -> Result<(), ErrorReported> {
+        if sess.opts.debugging_opts.jit_run {
+            return back::jit::run(sess, &codegen_results);
+        }
+
         // Run the linker on any artifacts that resulted from the LLVM run.
         // This should produce either a finished executable or library.
         sess.time("link_crate", || {
             use crate::back::archive::LlvmArchiveBuilder;
-            use rustc_codegen_ssa::back::link::link_binary;
+            use rustc_codegen_ssa::back::link::{link_binary, InProcessLld};
 
             let target_cpu = crate::llvm_util::target_cpu(sess);
+            let in_process_lld: Option<InProcessLld<'_>> =
+                if rustc_llvm::HAS_LLD { Some(&back::lld::link) } else { None };
             link_binary::<LlvmArchiveBuilder<'_>>(
                 sess,
                 &codegen_results,
                 outputs,
                 &codegen_results.crate_name.as_str(),
                 target_cpu,
+                in_process_lld,
             );
         });
 
@@ -321,6 +339,7 @@ pub struct ModuleLlvm {
     llcx: &'static mut llvm::Context,
     llmod_raw: *const llvm::Module,
     tm: &'static mut llvm::TargetMachine,
+    module_asm_srclocs: Vec<asm::ModuleAsmSrcLoc>,
 }
 
 unsafe impl Send for ModuleLlvm {}
@@ -330,16 +349,18 @@ impl ModuleLlvm {
     fn new(tcx: TyCtxt<'_>, mod_name: &str) -> Self {
         unsafe {
             let llcx = llvm::LLVMRustContextCreate(tcx.sess.fewer_names());
-            let llmod_raw = context::create_module(tcx, llcx, mod_name) as *const _;
-            ModuleLlvm { llmod_raw, llcx, tm: create_target_machine(tcx) }
+            let tm = create_target_machine(tcx);
+            let llmod_raw = context::create_module(tcx, llcx, tm, mod_name) as *const _;
+            ModuleLlvm { llmod_raw, llcx, tm, module_asm_srclocs: Vec::new() }
         }
     }
 
     fn new_metadata(tcx: TyCtxt<'_>, mod_name: &str) -> Self {
         unsafe {
             let llcx = llvm::LLVMRustContextCreate(tcx.sess.fewer_names());
-            let llmod_raw = context::create_module(tcx, llcx, mod_name) as *const _;
-            ModuleLlvm { llmod_raw, llcx, tm: create_informational_target_machine(tcx.sess) }
+            let tm = create_informational_target_machine(tcx.sess);
+            let llmod_raw = context::create_module(tcx, llcx, tm, mod_name) as *const _;
+            ModuleLlvm { llmod_raw, llcx, tm, module_asm_srclocs: Vec::new() }
         }
     }
 
@@ -360,7 +381,7 @@ impl ModuleLlvm {
                 }
             };
 
-            Ok(ModuleLlvm { llmod_raw, llcx, tm })
+            Ok(ModuleLlvm { llmod_raw, llcx, tm, module_asm_srclocs: Vec::new() })
         }
     }
 
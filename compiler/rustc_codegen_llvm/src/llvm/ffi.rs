@@ -355,6 +355,27 @@ impl SynchronizationScope {
     }
 }
 
+/// LLVMRustTailCallKind
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub enum TailCallKind {
+    None,
+    Tail,
+    MustTail,
+    NoTail,
+}
+
+impl TailCallKind {
+    pub fn from_generic(kind: rustc_codegen_ssa::common::TailCallKind) -> Self {
+        match kind {
+            rustc_codegen_ssa::common::TailCallKind::None => TailCallKind::None,
+            rustc_codegen_ssa::common::TailCallKind::Tail => TailCallKind::Tail,
+            rustc_codegen_ssa::common::TailCallKind::MustTail => TailCallKind::MustTail,
+            rustc_codegen_ssa::common::TailCallKind::NoTail => TailCallKind::NoTail,
+        }
+    }
+}
+
 /// LLVMRustFileType
 #[derive(Copy, Clone)]
 #[repr(C)]
@@ -379,6 +400,16 @@ pub enum MetadataType {
     MD_nontemporal = 9,
     MD_mem_parallel_loop_access = 10,
     MD_nonnull = 11,
+    MD_align = 17,
+    MD_type = 19,
+}
+
+/// LLVMRustVCallVisibility
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub enum VCallVisibility {
+    Public = 0,
+    LinkageUnit = 1,
 }
 
 /// LLVMRustAsmDialect
@@ -508,6 +539,52 @@ pub enum ArchiveKind {
     K_COFF,
 }
 
+/// LLVMRustLLDFlavor
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub enum LLDFlavor {
+    Elf,
+    Coff,
+    Wasm,
+}
+
+/// LLVMRustCOFFShortExport
+#[repr(C)]
+pub struct LLVMRustCOFFShortExport {
+    pub name: *const c_char,
+    /// The symbol referenced by calls, if it isn't `name`. Null otherwise.
+    pub symbol_name: *const c_char,
+    pub ordinal_present: bool,
+    /// Only meaningful if `ordinal_present` is true.
+    pub ordinal: u16,
+}
+
+impl LLVMRustCOFFShortExport {
+    pub fn new(
+        name: *const c_char,
+        symbol_name: *const c_char,
+        ordinal: Option<u16>,
+    ) -> LLVMRustCOFFShortExport {
+        LLVMRustCOFFShortExport {
+            name,
+            symbol_name,
+            ordinal_present: ordinal.is_some(),
+            ordinal: ordinal.unwrap_or(0),
+        }
+    }
+}
+
+/// The `IMAGE_FILE_MACHINE_*` constant of an import library, see
+/// `llvm::COFF::MachineTypes`.
+#[derive(Copy, Clone)]
+#[repr(u16)]
+pub enum LLVMMachineType {
+    AMD64 = 0x8664,
+    I386 = 0x14c,
+    ARM64 = 0xaa64,
+    ARMNT = 0x01c4,
+}
+
 /// LLVMRustPassKind
 #[derive(Copy, Clone, PartialEq, Debug)]
 #[repr(C)]
@@ -905,6 +982,7 @@ extern "C" {
     /// See Module::setModuleInlineAsm.
     pub fn LLVMSetModuleInlineAsm2(M: &Module, Asm: *const c_char, AsmLen: size_t);
     pub fn LLVMRustAppendModuleInlineAsm(M: &Module, Asm: *const c_char, AsmLen: size_t);
+    pub fn LLVMGetModuleInlineAsm(M: &Module, Len: &mut size_t) -> *const c_char;
 
     /// See llvm::LLVMTypeKind::getTypeID.
     pub fn LLVMRustGetTypeKind(Ty: &Type) -> TypeKind;
@@ -940,6 +1018,7 @@ extern "C" {
         ElementCount: c_uint,
         Packed: Bool,
     ) -> &'a Type;
+    pub fn LLVMCountStructElementTypes(StructTy: &Type) -> c_uint;
 
     // Operations on array, pointer, and vector types (sequence types)
     pub fn LLVMRustArrayType(ElementType: &Type, ElementCount: u64) -> &Type;
@@ -947,14 +1026,18 @@ extern "C" {
     pub fn LLVMVectorType(ElementType: &Type, ElementCount: c_uint) -> &Type;
 
     pub fn LLVMGetElementType(Ty: &Type) -> &Type;
+    pub fn LLVMGetArrayLength(ArrayTy: &Type) -> c_uint;
     pub fn LLVMGetVectorSize(VectorTy: &Type) -> c_uint;
+    pub fn LLVMRustGetVectorMinNumElements(VectorTy: &Type) -> c_uint;
 
     // Operations on other types
     pub fn LLVMVoidTypeInContext(C: &Context) -> &Type;
     pub fn LLVMRustMetadataTypeInContext(C: &Context) -> &Type;
+    pub fn LLVMTokenTypeInContext(C: &Context) -> &Type;
 
     // Operations on all values
     pub fn LLVMTypeOf(Val: &Value) -> &Type;
+    pub fn LLVMIsConstant(Val: &Value) -> Bool;
     pub fn LLVMGetValueName2(Val: &Value, Length: *mut size_t) -> *const c_char;
     pub fn LLVMSetValueName2(Val: &Value, Name: *const c_char, NameLen: size_t);
     pub fn LLVMReplaceAllUsesWith(OldVal: &'a Value, NewVal: &'a Value);
@@ -963,6 +1046,7 @@ extern "C" {
     // Operations on constants of any type
     pub fn LLVMConstNull(Ty: &Type) -> &Value;
     pub fn LLVMGetUndef(Ty: &Type) -> &Value;
+    pub fn LLVMIsNull(Val: &Value) -> Bool;
 
     // Operations on metadata
     pub fn LLVMMDStringInContext(C: &Context, Str: *const c_char, SLen: c_uint) -> &Value;
@@ -1013,11 +1097,16 @@ extern "C" {
     pub fn LLVMConstIntToPtr(ConstantVal: &'a Value, ToType: &'a Type) -> &'a Value;
     pub fn LLVMConstBitCast(ConstantVal: &'a Value, ToType: &'a Type) -> &'a Value;
     pub fn LLVMConstPointerCast(ConstantVal: &'a Value, ToType: &'a Type) -> &'a Value;
+    pub fn LLVMConstAddrSpaceCast(ConstantVal: &'a Value, ToType: &'a Type) -> &'a Value;
     pub fn LLVMConstExtractValue(
         AggConstant: &Value,
         IdxList: *const c_uint,
         NumIdx: c_uint,
     ) -> &Value;
+    pub fn LLVMConstExtractElement(
+        VectorConstant: &'a Value,
+        IndexConstant: &'a Value,
+    ) -> &'a Value;
 
     // Operations on global variables, functions, and aliases (globals)
     pub fn LLVMIsDeclaration(Global: &Value) -> Bool;
@@ -1032,6 +1121,7 @@ extern "C" {
 
     // Operations on global variables
     pub fn LLVMIsAGlobalVariable(GlobalVar: &Value) -> Option<&Value>;
+    pub fn LLVMRustStripPointerCasts(V: &Value) -> &Value;
     pub fn LLVMAddGlobal(M: &'a Module, Ty: &'a Type, Name: *const c_char) -> &'a Value;
     pub fn LLVMGetNamedGlobal(M: &Module, Name: *const c_char) -> Option<&Value>;
     pub fn LLVMRustGetOrInsertGlobal(
@@ -1040,6 +1130,13 @@ extern "C" {
         NameLen: size_t,
         T: &'a Type,
     ) -> &'a Value;
+    pub fn LLVMRustGetOrInsertGlobalInAddrSpace(
+        M: &'a Module,
+        Name: *const c_char,
+        NameLen: size_t,
+        T: &'a Type,
+        AddrSpace: c_uint,
+    ) -> &'a Value;
     pub fn LLVMRustInsertPrivateGlobal(M: &'a Module, T: &'a Type) -> &'a Value;
     pub fn LLVMGetFirstGlobal(M: &Module) -> Option<&Value>;
     pub fn LLVMGetNextGlobal(GlobalVar: &Value) -> Option<&Value>;
@@ -1056,6 +1153,18 @@ extern "C" {
         NameLen: size_t,
     ) -> Option<&Value>;
     pub fn LLVMSetTailCall(CallInst: &Value, IsTailCall: Bool);
+    pub fn LLVMRustCreateAnonymousAliasScopeDomain(
+        C: &'a Context,
+        Name: *const c_char,
+        NameLen: size_t,
+    ) -> &'a Value;
+    pub fn LLVMRustCreateAnonymousAliasScope(
+        C: &'a Context,
+        Domain: &'a Value,
+        Name: *const c_char,
+        NameLen: size_t,
+    ) -> &'a Value;
+    pub fn LLVMRustSetTailCallKind(CallInst: &Value, Kind: TailCallKind);
 
     // Operations on functions
     pub fn LLVMRustGetOrInsertFunction(
@@ -1068,6 +1177,7 @@ extern "C" {
     pub fn LLVMRustAddAlignmentAttr(Fn: &Value, index: c_uint, bytes: u32);
     pub fn LLVMRustAddDereferenceableAttr(Fn: &Value, index: c_uint, bytes: u64);
     pub fn LLVMRustAddDereferenceableOrNullAttr(Fn: &Value, index: c_uint, bytes: u64);
+    pub fn LLVMRustAddAllocSizeAttr(Fn: &Value, ElemSizeArg: c_uint);
     pub fn LLVMRustAddByValAttr(Fn: &Value, index: c_uint, ty: &Type);
     pub fn LLVMRustAddFunctionAttribute(Fn: &Value, index: c_uint, attr: Attribute);
     pub fn LLVMRustAddFunctionAttrStringValue(
@@ -1094,11 +1204,16 @@ extern "C" {
 
     // Operations on instructions
     pub fn LLVMIsAInstruction(Val: &Value) -> Option<&Value>;
+    pub fn LLVMIsALoadInst(Val: &Value) -> Option<&Value>;
+    pub fn LLVMIsAStoreInst(Val: &Value) -> Option<&Value>;
+    pub fn LLVMIsATruncInst(Val: &Value) -> Option<&Value>;
+    pub fn LLVMGetOperand(Val: &Value, Index: c_uint) -> &Value;
     pub fn LLVMGetFirstBasicBlock(Fn: &Value) -> &BasicBlock;
 
     // Operations on call sites
     pub fn LLVMSetInstructionCallConv(Instr: &Value, CC: c_uint);
     pub fn LLVMRustAddCallSiteAttribute(Instr: &Value, index: c_uint, attr: Attribute);
+    pub fn LLVMRustAddCallSiteAttrString(Instr: &Value, index: c_uint, Name: *const c_char);
     pub fn LLVMRustAddAlignmentCallSiteAttr(Instr: &Value, index: c_uint, bytes: u32);
     pub fn LLVMRustAddDereferenceableCallSiteAttr(Instr: &Value, index: c_uint, bytes: u64);
     pub fn LLVMRustAddDereferenceableOrNullCallSiteAttr(Instr: &Value, index: c_uint, bytes: u64);
@@ -1147,7 +1262,8 @@ extern "C" {
         NumArgs: c_uint,
         Then: &'a BasicBlock,
         Catch: &'a BasicBlock,
-        Bundle: Option<&OperandBundleDef<'a>>,
+        Bundles: *const &OperandBundleDef<'a>,
+        NumBundles: c_uint,
         Name: *const c_char,
     ) -> &'a Value;
     pub fn LLVMBuildLandingPad(
@@ -1508,7 +1624,8 @@ extern "C" {
         Fn: &'a Value,
         Args: *const &'a Value,
         NumArgs: c_uint,
-        Bundle: Option<&OperandBundleDef<'a>>,
+        Bundles: *const &OperandBundleDef<'a>,
+        NumBundles: c_uint,
     ) -> &'a Value;
     pub fn LLVMRustBuildMemCpy(
         B: &Builder<'a>,
@@ -1536,6 +1653,23 @@ extern "C" {
         Size: &'a Value,
         IsVolatile: bool,
     ) -> &'a Value;
+    pub fn LLVMRustBuildElementUnorderedAtomicMemCpy(
+        B: &Builder<'a>,
+        Dst: &'a Value,
+        DstAlign: c_uint,
+        Src: &'a Value,
+        SrcAlign: c_uint,
+        Size: &'a Value,
+        ElementSize: c_uint,
+    ) -> &'a Value;
+    pub fn LLVMRustBuildElementUnorderedAtomicMemSet(
+        B: &Builder<'a>,
+        Dst: &'a Value,
+        DstAlign: c_uint,
+        Val: &'a Value,
+        Size: &'a Value,
+        ElementSize: c_uint,
+    ) -> &'a Value;
     pub fn LLVMBuildSelect(
         B: &Builder<'a>,
         If: &'a Value,
@@ -1593,6 +1727,11 @@ extern "C" {
         Acc: &'a Value,
         Src: &'a Value,
     ) -> &'a Value;
+    pub fn LLVMRustBuildScalableVectorSplat(
+        B: &Builder<'a>,
+        MinNumElts: c_uint,
+        Elt: &'a Value,
+    ) -> &'a Value;
     pub fn LLVMRustBuildVectorReduceAdd(B: &Builder<'a>, Src: &'a Value) -> &'a Value;
     pub fn LLVMRustBuildVectorReduceMul(B: &Builder<'a>, Src: &'a Value) -> &'a Value;
     pub fn LLVMRustBuildVectorReduceAnd(B: &Builder<'a>, Src: &'a Value) -> &'a Value;
@@ -1677,6 +1816,10 @@ extern "C" {
 
     pub fn LLVMTimeTraceProfilerFinish(FileName: *const c_char);
 
+    pub fn LLVMRustEnableStatistics();
+    pub fn LLVMRustResetStatistics();
+    pub fn LLVMRustTakeStatisticsJSON(Str: &RustString);
+
     pub fn LLVMAddAnalysisPasses(T: &'a TargetMachine, PM: &PassManager<'a>);
 
     pub fn LLVMPassManagerBuilderCreate() -> &'static mut PassManagerBuilder;
@@ -2124,6 +2267,7 @@ extern "C" {
     );
 
     pub fn LLVMRustHasFeature(T: &TargetMachine, s: *const c_char) -> bool;
+    pub fn LLVMRustIsKnownFeature(Triple: *const c_char, s: *const c_char) -> bool;
 
     pub fn LLVMRustPrintTargetCPUs(T: &TargetMachine);
     pub fn LLVMRustPrintTargetFeatures(T: &TargetMachine);
@@ -2144,6 +2288,7 @@ extern "C" {
         Singlethread: bool,
         AsmComments: bool,
         EmitStackSizeSection: bool,
+        EmitAddrsig: bool,
         RelaxELFRelocations: bool,
         UseInitArray: bool,
     ) -> Option<&'static mut TargetMachine>;
@@ -2191,6 +2336,7 @@ extern "C" {
         DisableSimplifyLibCalls: bool,
         EmitLifetimeMarkers: bool,
         SanitizerOptions: Option<&SanitizerOptions>,
+        InsertGCOVProfiling: bool,
         PGOGenPath: *const c_char,
         PGOUsePath: *const c_char,
         llvm_selfprofiler: *mut c_void,
@@ -2283,6 +2429,14 @@ extern "C" {
         WriteSymbtab: bool,
         Kind: ArchiveKind,
     ) -> LLVMRustResult;
+    pub fn LLVMRustWriteImportLibrary(
+        ImportName: *const c_char,
+        Path: *const c_char,
+        Exports: *const LLVMRustCOFFShortExport,
+        NumExports: size_t,
+        Machine: u16,
+        MinGW: bool,
+    ) -> LLVMRustResult;
     pub fn LLVMRustArchiveMemberNew(
         Filename: *const c_char,
         Name: *const c_char,
@@ -2303,6 +2457,8 @@ extern "C" {
 
     pub fn LLVMRustSetComdat(M: &'a Module, V: &'a Value, Name: *const c_char, NameLen: size_t);
     pub fn LLVMRustUnsetComdat(V: &Value);
+    pub fn LLVMRustGlobalAddMetadata(V: &'a Value, KindID: c_uint, MD: &'a Value);
+    pub fn LLVMRustGlobalSetVCallVisibility(V: &Value, Visibility: VCallVisibility);
     pub fn LLVMRustSetModulePICLevel(M: &Module);
     pub fn LLVMRustSetModulePIELevel(M: &Module);
     pub fn LLVMRustModuleBufferCreate(M: &Module) -> &'static mut ModuleBuffer;
@@ -2370,4 +2526,23 @@ extern "C" {
         mod_id: *const c_char,
         data: &ThinLTOData,
     );
+
+    pub fn LLVMRustRunJIT(
+        Objects: *const *const c_char,
+        NumObjects: size_t,
+        Archives: *const *const c_char,
+        NumArchives: size_t,
+        Dylibs: *const *const c_char,
+        NumDylibs: size_t,
+        ProgramName: *const c_char,
+        ExitCode: &mut c_int,
+    ) -> bool;
+
+    pub fn LLVMRustLinkWithLLD(
+        Flavor: LLDFlavor,
+        Args: *const *const c_char,
+        NumArgs: size_t,
+        Out: &RustString,
+        Err: &RustString,
+    ) -> bool;
 }
@@ -3,22 +3,31 @@ use crate::llvm;
 use libc::c_int;
 use rustc_codegen_ssa::target_features::supported_target_features;
 use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_feature::UnstableFeatures;
 use rustc_middle::bug;
+use rustc_serialize::json::{self, Json};
 use rustc_session::config::PrintRequest;
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
 use rustc_target::spec::{MergeFunctions, PanicStrategy};
+use std::collections::BTreeMap;
 use std::ffi::CString;
+use std::lazy::SyncLazy;
 
 use std::slice;
 use std::str;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Once;
+use std::sync::{Mutex, Once};
+use std::{fs, mem};
 
 static POISONED: AtomicBool = AtomicBool::new(false);
 static INIT: Once = Once::new();
 
+/// The `-Z llvm-stats` counters of each stage of each module run so far.
+static LLVM_STATS: SyncLazy<Mutex<BTreeMap<String, json::Object>>> =
+    SyncLazy::new(Default::default);
+
 pub(crate) fn init(sess: &Session) {
     unsafe {
         // Before we touch LLVM, make sure that multithreading is enabled.
@@ -118,6 +127,22 @@ unsafe fn configure_llvm(sess: &Session) {
         llvm::LLVMTimeTraceProfilerInitialize();
     }
 
+    if sess.opts.debugging_opts.llvm_stats {
+        // LLVM's counters are global, so they can only be attributed to the
+        // module being optimized if modules are optimized one at a time.
+        if !sess.opts.debugging_opts.no_parallel_llvm {
+            sess.fatal("`-Z llvm-stats` requires `-Z no-parallel-llvm`");
+        }
+
+        llvm::LLVMRustEnableStatistics();
+    }
+
+    // `!vcall_visibility` metadata, which tells GlobalDCE which vtables it may
+    // shrink, only exists since LLVM 10.
+    if sess.opts.debugging_opts.virtual_function_elimination && get_major_version() < 10 {
+        sess.fatal("`-Z virtual-function-elimination` requires LLVM 10 or later");
+    }
+
     llvm::LLVMInitializePasses();
 
     rustc_llvm::initialize_available_targets();
@@ -134,6 +159,50 @@ pub fn time_trace_profiler_finish(file_name: &str) {
     }
 }
 
+/// Starts collecting LLVM's statistics counters for the `-Z llvm-stats` report
+/// of a stage of a module about to run.
+pub(crate) fn llvm_stats_start() {
+    unsafe {
+        llvm::LLVMRustResetStatistics();
+    }
+}
+
+/// Adds the statistics counters LLVM collected since `llvm_stats_start` to the
+/// `-Z llvm-stats` report as those of the `stage` of the module `module_name`.
+pub(crate) fn llvm_stats_record(module_name: &str, stage: &str) {
+    let stats = llvm::build_string(|s| unsafe { llvm::LLVMRustTakeStatisticsJSON(s) })
+        .expect("non-UTF-8 LLVM statistics");
+    let stats = json::from_str(&stats).expect("malformed LLVM statistics");
+    let mut modules = LLVM_STATS.lock().unwrap();
+    modules.entry(module_name.to_string()).or_default().insert(stage.to_string(), stats);
+}
+
+/// Writes the `-Z llvm-stats` report, with the counters of each stage of each
+/// module and their sums over all stages and modules.
+pub fn llvm_stats_finish(sess: &Session, file_name: &str) {
+    let modules = mem::take(&mut *LLVM_STATS.lock().unwrap());
+    let mut total = BTreeMap::new();
+    for stats in modules.values().flat_map(|stages| stages.values()) {
+        if let Json::Object(stats) = stats {
+            for (name, value) in stats {
+                // Timers are reported next to the counters, but aren't summed.
+                if let Some(value) = value.as_u64() {
+                    *total.entry(name.clone()).or_insert(0) += value;
+                }
+            }
+        }
+    }
+    let total = total.into_iter().map(|(name, value)| (name, Json::U64(value))).collect();
+    let modules = modules.into_iter().map(|(name, stages)| (name, Json::Object(stages))).collect();
+
+    let mut report = BTreeMap::new();
+    report.insert("modules".to_string(), Json::Object(modules));
+    report.insert("total".to_string(), Json::Object(total));
+    if let Err(err) = fs::write(file_name, Json::Object(report).pretty().to_string()) {
+        sess.err(&format!("failed to write `{}`: {}", file_name, err));
+    }
+}
+
 // WARNING: the features after applying `to_llvm_feature` must be known
 // to LLVM or the feature detection code will walk past the end of the feature
 // array, leading to crashes.
@@ -150,8 +219,13 @@ pub fn to_llvm_feature<'a>(sess: &Session, s: &'a str) -> &'a str {
     }
 }
 
+/// Returns the target features enabled for the session, which become the
+/// `cfg(target_feature)`s. They are those LLVM ends up with after applying the
+/// target's and `-C target-feature`'s features in order, including the ones
+/// they imply.
 pub fn target_features(sess: &Session) -> Vec<Symbol> {
     let target_machine = create_informational_target_machine(sess);
+    check_cmdline_target_features(sess);
     supported_target_features(sess)
         .iter()
         .filter_map(|&(feature, gate)| {
@@ -170,6 +244,54 @@ pub fn target_features(sess: &Session) -> Vec<Symbol> {
         .collect()
 }
 
+/// Returns whether `feature`, without a sign, is in the feature table of the target.
+fn is_known_feature(sess: &Session, feature: &str) -> bool {
+    let triple = SmallCStr::new(&sess.target.llvm_target);
+    let feature = CString::new(feature).unwrap();
+    unsafe { llvm::LLVMRustIsKnownFeature(triple.as_ptr(), feature.as_ptr()) }
+}
+
+/// Returns whether a `-C target-feature` entry is passed on to LLVM: it needs a
+/// sign and has to be in the feature table of the target, as LLVM otherwise
+/// complains about it each time it parses the feature string.
+/// `check_cmdline_target_features` warns about the dropped entries once.
+pub fn is_valid_cmdline_feature(sess: &Session, feature: &str) -> bool {
+    match feature.strip_prefix('+').or_else(|| feature.strip_prefix('-')) {
+        Some(name) => is_known_feature(sess, name),
+        None => false,
+    }
+}
+
+/// Warns about the `-C target-feature` entries that aren't passed on to LLVM:
+/// those without a sign and those missing from the target's feature table.
+fn check_cmdline_target_features(sess: &Session) {
+    for feature in sess.opts.cg.target_feature.split(',').filter(|f| !f.is_empty()) {
+        let name = match feature.strip_prefix('+').or_else(|| feature.strip_prefix('-')) {
+            Some(name) => name,
+            None => {
+                sess.struct_warn(&format!(
+                    "unknown sign for feature `{}` specified for `-C target-feature`",
+                    feature
+                ))
+                .note("features must begin with a `+` to enable or `-` to disable them")
+                .emit();
+                continue;
+            }
+        };
+        if name == "crt-static" {
+            continue;
+        }
+        if !is_known_feature(sess, name) {
+            sess.struct_warn(&format!(
+                "unknown feature `{}` specified for `-C target-feature`, it is ignored",
+                name
+            ))
+            .help("run `rustc --print target-features` to see the features of the target")
+            .emit();
+        }
+    }
+}
+
 pub fn print_version() {
     // Can be called without initializing LLVM
     unsafe {
@@ -10,6 +10,7 @@ pub use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::mir::mono::{Linkage, Visibility};
 use rustc_middle::ty::layout::FnAbiExt;
 use rustc_middle::ty::{self, Instance, TypeFoldable};
+use rustc_session::config::CrateType;
 use rustc_target::abi::LayoutOf;
 use tracing::debug;
 
@@ -25,11 +26,8 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         let ty = instance.ty(self.tcx, ty::ParamEnv::reveal_all());
         let llty = self.layout_of(ty).llvm_type(self);
 
-        let g = self.define_global(symbol_name, llty).unwrap_or_else(|| {
-            self.sess().span_fatal(
-                self.tcx.def_span(def_id),
-                &format!("symbol `{}` is already defined", symbol_name),
-            )
+        let g = self.define_global(symbol_name, llty).unwrap_or_else(|err| {
+            self.report_symbol_conflict(Some(self.tcx.def_span(def_id)), err)
         });
 
         unsafe {
@@ -37,6 +35,20 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             llvm::LLVMRustSetVisibility(g, base::visibility_to_llvm(visibility));
         }
 
+        // Downstream crates mark statics of an upstream Rust dylib as
+        // `dllimport` (see `get_static`), so export them from the dylib
+        // explicitly rather than leaving it to the linker to guess.
+        if self.use_dll_storage_attrs
+            && linkage == Linkage::External
+            && visibility == Visibility::Default
+            && self.tcx.sess.crate_types().contains(&CrateType::Dylib)
+            && self.tcx.is_reachable_non_generic(def_id)
+        {
+            unsafe {
+                llvm::LLVMSetDLLStorageClass(g, llvm::DLLStorageClass::DllExport);
+            }
+        }
+
         self.instances.borrow_mut().insert(instance, g);
     }
 
@@ -57,6 +57,10 @@ impl CodegenCx<'ll, 'tcx> {
         unsafe { llvm::LLVMRustMetadataTypeInContext(self.llcx) }
     }
 
+    crate fn type_token(&self) -> &'ll Type {
+        unsafe { llvm::LLVMTokenTypeInContext(self.llcx) }
+    }
+
     ///x Creates an integer type with the given number of bits, e.g., i24
     crate fn type_ix(&self, num_bits: u64) -> &'ll Type {
         unsafe { llvm::LLVMIntTypeInContext(self.llcx, num_bits as c_uint) }
@@ -208,7 +212,7 @@ impl BaseTypeMethods<'tcx> for CodegenCx<'ll, 'tcx> {
     }
 
     fn vector_length(&self, ty: &'ll Type) -> usize {
-        unsafe { llvm::LLVMGetVectorSize(ty) as usize }
+        unsafe { llvm::LLVMRustGetVectorMinNumElements(ty) as usize }
     }
 
     fn float_width(&self, ty: &'ll Type) -> usize {
@@ -0,0 +1,421 @@
+//! Generation of the ABI tests requested by `-Z emit-abi-tests`.
+//!
+//! For every `extern "C"` function of the crate, a C header declares the
+//! function along with the `#[repr(C)]` types it uses, and a C file asserts
+//! that the C compiler lays out those types with the sizes, alignments and
+//! field offsets rustc computed. Compiling that file with the C compiler of
+//! the target, the `cc`-like driver rustc links with, then catches any drift
+//! between the two.
+
+use crate::back::link::linker_and_flavor;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir as hir;
+use rustc_hir::def::CtorKind;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_middle::ty::layout::TyAndLayout;
+use rustc_middle::ty::subst::SubstsRef;
+use rustc_middle::ty::{self, Instance, ParamEnv, Ty, TyCtxt};
+use rustc_session::config::OutputFilenames;
+use rustc_session::Session;
+use rustc_target::abi::{Abi, Integer, Primitive};
+use rustc_target::spec::abi::Abi as SpecAbi;
+use rustc_target::spec::LinkerFlavor;
+
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// C keywords that are valid Rust field names.
+const C_KEYWORDS: &[&str] = &[
+    "auto", "case", "char", "default", "double", "float", "goto", "inline", "int", "long",
+    "register", "restrict", "short", "signed", "sizeof", "switch", "typedef", "union", "unsigned",
+    "void", "volatile",
+];
+
+/// Writes `<crate>.abi-tests.h` and `<crate>.abi-tests.c` next to the other
+/// outputs, then compiles the latter with the target's C compiler.
+pub fn emit_abi_tests(tcx: TyCtxt<'_>, outputs: &OutputFilenames) -> io::Result<()> {
+    let mut tests = AbiTests {
+        tcx,
+        names: FxHashMap::default(),
+        identifiers: FxHashSet::default(),
+        types: String::new(),
+        functions: String::new(),
+        checks: String::new(),
+    };
+
+    for item in tcx.hir().krate().items.values() {
+        match item.kind {
+            hir::ItemKind::ForeignMod(ref foreign_mod) if foreign_mod.abi == SpecAbi::C => {
+                for foreign_item in foreign_mod.items {
+                    if let hir::ForeignItemKind::Fn(..) = foreign_item.kind {
+                        let def_id = tcx.hir().local_def_id(foreign_item.hir_id).to_def_id();
+                        let name = tcx
+                            .codegen_fn_attrs(def_id)
+                            .link_name
+                            .unwrap_or(foreign_item.ident.name);
+                        tests.function(def_id, &name.as_str());
+                    }
+                }
+            }
+            hir::ItemKind::Fn(ref sig, ..) if sig.header.abi == SpecAbi::C => {
+                tests.defined_function(tcx.hir().local_def_id(item.hir_id).to_def_id());
+            }
+            hir::ItemKind::Impl { items, .. } => {
+                for impl_item_ref in items {
+                    let impl_item = tcx.hir().impl_item(impl_item_ref.id);
+                    if let hir::ImplItemKind::Fn(ref sig, _) = impl_item.kind {
+                        if sig.header.abi == SpecAbi::C {
+                            let def_id = tcx.hir().local_def_id(impl_item.hir_id).to_def_id();
+                            tests.defined_function(def_id);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let header = outputs.with_extension("abi-tests.h");
+    let guard =
+        format!("{}_ABI_TESTS_H", tcx.crate_name(LOCAL_CRATE)).to_uppercase().replace('-', "_");
+    fs::write(
+        &header,
+        format!(
+            "// Generated by `rustc -Z emit-abi-tests`.\n\n\
+             #ifndef {guard}\n#define {guard}\n\n#include <stdint.h>\n\n{}{}#endif\n",
+            tests.types,
+            tests.functions,
+            guard = guard,
+        ),
+    )?;
+
+    let checks = outputs.with_extension("abi-tests.c");
+    let header_name = header.file_name().unwrap().to_string_lossy();
+    fs::write(
+        &checks,
+        format!(
+            "// Generated by `rustc -Z emit-abi-tests`.\n\n\
+             #include <stddef.h>\n#include \"{}\"\n\n{}",
+            header_name, tests.checks,
+        ),
+    )?;
+
+    compile_checks(tcx.sess, &checks);
+    Ok(())
+}
+
+/// Compiles the checks to an object, which fails if any layout differs. This
+/// uses the `cc`-like driver that links for the target, along with the
+/// target's arguments for it such as `-m32`, so that the C layouts are those
+/// of the target even when cross-compiling.
+fn compile_checks(sess: &Session, checks: &Path) {
+    let (cc, flavor) = linker_and_flavor(sess);
+    if flavor != LinkerFlavor::Gcc {
+        sess.warn(&format!(
+            "the ABI tests are only compiled when linking with a C compiler, not {:?}",
+            cc
+        ));
+        return;
+    }
+
+    let object = checks.with_extension("o");
+    let mut cmd = Command::new(&cc);
+    if let Some(args) = sess.target.options.pre_link_args.get(&flavor) {
+        cmd.args(args);
+    }
+    cmd.arg("-std=c11").arg("-c").arg(checks).arg("-o").arg(&object);
+    match cmd.output() {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            sess.struct_err("the C layout of some types differs from the Rust one")
+                .note(&format!("{:?} failed with {}", cmd, output.status))
+                .note(&String::from_utf8_lossy(&output.stderr))
+                .emit();
+        }
+        Err(e) => {
+            sess.warn(&format!("could not run {:?} to compile the ABI tests: {}", cc, e));
+        }
+    }
+}
+
+struct AbiTests<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The C type names of the ADTs declared so far.
+    names: FxHashMap<DefId, String>,
+    /// The identifiers of the declared types, to avoid clashes.
+    identifiers: FxHashSet<String>,
+    /// Type definitions of the header, each after the ones it depends on.
+    types: String,
+    /// Function declarations of the header.
+    functions: String,
+    /// The `_Static_assert`s compiled to compare the layouts.
+    checks: String,
+}
+
+/// A type without a C equivalent. The function using it is left out.
+struct Unsupported<'tcx>(Ty<'tcx>);
+
+impl AbiTests<'tcx> {
+    /// Adds a function defined in Rust, unless it is generic.
+    fn defined_function(&mut self, def_id: DefId) {
+        if !self.tcx.generics_of(def_id).requires_monomorphization(self.tcx) {
+            let name = self.tcx.symbol_name(Instance::mono(self.tcx, def_id)).name;
+            self.function(def_id, name);
+        }
+    }
+
+    fn function(&mut self, def_id: DefId, name: &str) {
+        match self.function_declaration(def_id, name) {
+            Ok(declaration) => writeln!(self.functions, "{};\n", declaration).unwrap(),
+            Err(Unsupported(ty)) => writeln!(
+                self.functions,
+                "// `{}` is left out: `{}` has no C equivalent.\n",
+                name, ty
+            )
+            .unwrap(),
+        }
+    }
+
+    fn function_declaration(
+        &mut self,
+        def_id: DefId,
+        name: &str,
+    ) -> Result<String, Unsupported<'tcx>> {
+        let sig = self.tcx.fn_sig(def_id);
+        let sig = self.tcx.normalize_erasing_late_bound_regions(ParamEnv::reveal_all(), &sig);
+
+        let mut params = Vec::new();
+        for (i, &ty) in sig.inputs().iter().enumerate() {
+            // C arrays decay to pointers when passed.
+            if let ty::Array(..) = ty.kind() {
+                return Err(Unsupported(ty));
+            }
+            params.push(self.declarator(ty, &format!("arg{}", i))?);
+        }
+        if sig.c_variadic {
+            params.push("...".to_string());
+        } else if params.is_empty() {
+            params.push("void".to_string());
+        }
+
+        let function = format!("{}({})", name, params.join(", "));
+        let output = sig.output();
+        if output.is_unit() || output.is_never() {
+            Ok(format!("void {}", function))
+        } else if let ty::Array(..) = output.kind() {
+            Err(Unsupported(output))
+        } else {
+            self.declarator(output, &function)
+        }
+    }
+
+    /// Returns the C declaration of `name` with the type `ty`.
+    fn declarator(&mut self, ty: Ty<'tcx>, name: &str) -> Result<String, Unsupported<'tcx>> {
+        let type_name = match *ty.kind() {
+            ty::Bool => "_Bool".to_string(),
+            ty::Char => "uint32_t".to_string(),
+            ty::Float(ty::FloatTy::F32) => "float".to_string(),
+            ty::Float(ty::FloatTy::F64) => "double".to_string(),
+            // Pointers to unsized types aren't scalars and are rejected.
+            ty::Int(_) | ty::Uint(_) | ty::RawPtr(_) | ty::Ref(..) => self.scalar_type(ty)?,
+            ty::FnPtr(_) => return Ok(format!("void (*{})(void)", name)),
+            ty::Array(elem, len) => {
+                let len = len.eval_usize(self.tcx, ParamEnv::reveal_all());
+                return self.declarator(elem, &format!("{}[{}]", name, len));
+            }
+            ty::Adt(adt, substs) if adt.repr.transparent() => {
+                let field_ty = self.transparent_field(ty, adt, substs)?;
+                return self.declarator(field_ty, name);
+            }
+            ty::Adt(adt, substs) => self.adt(ty, adt, substs)?,
+            _ => return Err(Unsupported(ty)),
+        };
+        Ok(format!("{} {}", type_name, name))
+    }
+
+    /// Returns the C type of a type laid out as a single integer or pointer.
+    fn scalar_type(&self, ty: Ty<'tcx>) -> Result<String, Unsupported<'tcx>> {
+        let layout = self.layout(ty)?;
+        let value = match layout.abi {
+            Abi::Scalar(ref scalar) => scalar.value,
+            _ => return Err(Unsupported(ty)),
+        };
+        let name = match value {
+            Primitive::Int(Integer::I128, _) if self.tcx.sess.target.pointer_width < 64 => {
+                return Err(Unsupported(ty));
+            }
+            Primitive::Int(Integer::I128, true) => "__int128",
+            Primitive::Int(Integer::I128, false) => "unsigned __int128",
+            Primitive::Int(int, signed) => {
+                let bits = int.size().bits();
+                return Ok(format!("{}int{}_t", if signed { "" } else { "u" }, bits));
+            }
+            Primitive::Pointer => "void *",
+            _ => return Err(Unsupported(ty)),
+        };
+        Ok(name.to_string())
+    }
+
+    /// Returns the C type of an ADT, declaring it first if needed.
+    fn adt(
+        &mut self,
+        ty: Ty<'tcx>,
+        adt: &'tcx ty::AdtDef,
+        substs: SubstsRef<'tcx>,
+    ) -> Result<String, Unsupported<'tcx>> {
+        if let Some(name) = self.names.get(&adt.did) {
+            return Ok(name.clone());
+        }
+
+        // Types like `Option<&T>` or `NonZeroU32` are passed like the scalar they
+        // contain, but other Rust layouts have no C equivalent.
+        let has_c_layout = adt.repr.c() || (adt.is_enum() && adt.repr.int.is_some());
+        if !has_c_layout {
+            return self.scalar_type(ty);
+        }
+        if !substs.is_empty() || (adt.is_enum() && !adt.is_payloadfree()) {
+            return Err(Unsupported(ty));
+        }
+
+        let layout = self.layout(ty)?;
+        let mut identifier = self.tcx.item_name(adt.did).to_string();
+        while !self.identifiers.insert(identifier.clone()) {
+            identifier.push('_');
+        }
+
+        let name = if adt.is_enum() {
+            self.declare_enum(adt, &identifier, layout)?
+        } else {
+            self.declare_struct(adt, &identifier, layout, substs)?
+        };
+        writeln!(
+            self.checks,
+            "_Static_assert(sizeof({name}) == {}, \"size of `{}`\");\n\
+             _Static_assert(_Alignof({name}) == {}, \"alignment of `{}`\");\n",
+            layout.size.bytes(),
+            identifier,
+            layout.align.abi.bytes(),
+            identifier,
+            name = name,
+        )
+        .unwrap();
+        self.names.insert(adt.did, name.clone());
+        Ok(name)
+    }
+
+    fn declare_enum(
+        &mut self,
+        adt: &'tcx ty::AdtDef,
+        identifier: &str,
+        layout: TyAndLayout<'tcx>,
+    ) -> Result<String, Unsupported<'tcx>> {
+        let mut variants = String::new();
+        for (variant_index, discr) in adt.discriminants(self.tcx) {
+            let variant = &adt.variants[variant_index];
+            writeln!(variants, "    {}_{} = {},", identifier, variant.ident, discr).unwrap();
+        }
+
+        // A C `enum` has the size of an `int`, like `#[repr(C)]` ones, so an
+        // integer type stands for those with an explicit `#[repr(inttype)]`.
+        if adt.repr.int.is_some() {
+            let int = self.scalar_type(layout.ty)?;
+            writeln!(self.types, "typedef {} {};\n\nenum {{\n{}}};\n", int, identifier, variants)
+                .unwrap();
+            Ok(identifier.to_string())
+        } else {
+            writeln!(self.types, "enum {} {{\n{}}};\n", identifier, variants).unwrap();
+            Ok(format!("enum {}", identifier))
+        }
+    }
+
+    fn declare_struct(
+        &mut self,
+        adt: &'tcx ty::AdtDef,
+        identifier: &str,
+        layout: TyAndLayout<'tcx>,
+        substs: SubstsRef<'tcx>,
+    ) -> Result<String, Unsupported<'tcx>> {
+        let name = format!("{} {}", if adt.is_union() { "union" } else { "struct" }, identifier);
+        let variant = adt.non_enum_variant();
+
+        let mut fields = String::new();
+        let mut offset_checks = String::new();
+        for (i, field) in variant.fields.iter().enumerate() {
+            let field_ty = field.ty(self.tcx, substs);
+            // C has no zero-sized types, and leaving them out doesn't move
+            // the other fields.
+            if self.layout(field_ty)?.is_zst() {
+                continue;
+            }
+            let mut field_name = match variant.ctor_kind {
+                CtorKind::Fn => format!("_{}", i),
+                _ => field.ident.to_string(),
+            };
+            if C_KEYWORDS.contains(&&field_name[..]) {
+                field_name.push('_');
+            }
+            writeln!(fields, "    {};", self.declarator(field_ty, &field_name)?).unwrap();
+            writeln!(
+                offset_checks,
+                "_Static_assert(offsetof({}, {}) == {}, \"offset of `{}::{}`\");",
+                name,
+                field_name,
+                layout.fields.offset(i).bytes(),
+                identifier,
+                field.ident,
+            )
+            .unwrap();
+        }
+
+        let mut attributes = Vec::new();
+        if let Some(align) = adt.repr.align {
+            attributes.push(format!("aligned({})", align.bytes()));
+        }
+        let pack = adt.repr.pack.map(|pack| pack.bytes());
+        if pack == Some(1) {
+            attributes.push("packed".to_string());
+        }
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" __attribute__(({}))", attributes.join(", "))
+        };
+
+        let definition = format!("{} {{\n{}}}{};\n", name, fields, attributes);
+        match pack {
+            Some(pack) if pack > 1 => writeln!(
+                self.types,
+                "#pragma pack(push, {})\n{}#pragma pack(pop)\n",
+                pack, definition
+            ),
+            _ => writeln!(self.types, "{}", definition),
+        }
+        .unwrap();
+        self.checks.push_str(&offset_checks);
+        Ok(name)
+    }
+
+    /// Returns the type of the field a `#[repr(transparent)]` ADT is passed as.
+    fn transparent_field(
+        &self,
+        ty: Ty<'tcx>,
+        adt: &'tcx ty::AdtDef,
+        substs: SubstsRef<'tcx>,
+    ) -> Result<Ty<'tcx>, Unsupported<'tcx>> {
+        for field in &adt.non_enum_variant().fields {
+            let field_ty = field.ty(self.tcx, substs);
+            if !self.layout(field_ty)?.is_zst() {
+                return Ok(field_ty);
+            }
+        }
+        Err(Unsupported(ty))
+    }
+
+    fn layout(&self, ty: Ty<'tcx>) -> Result<TyAndLayout<'tcx>, Unsupported<'tcx>> {
+        self.tcx.layout_of(ParamEnv::reveal_all().and(ty)).map_err(|_| Unsupported(ty))
+    }
+}
@@ -1,3 +1,4 @@
+use rustc_middle::middle::cstore::DllImport;
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
 
@@ -51,4 +52,13 @@ pub trait ArchiveBuilder<'a> {
     fn update_symbols(&mut self);
 
     fn build(self);
+
+    /// Writes an import library for the DLL `lib_name` to `output`, exporting
+    /// `dll_imports`. Used for `kind = "raw-dylib"` libraries.
+    fn create_dll_import_lib(
+        sess: &Session,
+        lib_name: &str,
+        dll_imports: &[DllImport],
+        output: &Path,
+    );
 }
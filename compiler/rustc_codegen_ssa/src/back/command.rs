@@ -134,6 +134,14 @@ impl Command {
         &self.args
     }
 
+    pub fn get_env(&self) -> &[(OsString, OsString)] {
+        &self.env
+    }
+
+    pub fn get_env_remove(&self) -> &[OsString] {
+        &self.env_remove
+    }
+
     pub fn take_args(&mut self) -> Vec<OsString> {
         mem::take(&mut self.args)
     }
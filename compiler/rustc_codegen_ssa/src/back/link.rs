@@ -1,8 +1,9 @@
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
 use rustc_data_structures::temp_dir::MaybeTempDir;
+use rustc_errors::DiagnosticBuilder;
 use rustc_fs_util::fix_windows_verbatim_for_gcc;
-use rustc_hir::def_id::CrateNum;
-use rustc_middle::middle::cstore::{EncodedMetadata, LibSource, NativeLib};
+use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
+use rustc_middle::middle::cstore::{DllImport, EncodedMetadata, LibSource, NativeLib};
 use rustc_middle::middle::dependency_format::Linkage;
 use rustc_session::config::{self, CFGuard, CrateType, DebugInfo};
 use rustc_session::config::{OutputFilenames, OutputType, PrintRequest, SanitizerSet};
@@ -37,17 +38,53 @@ pub fn remove(sess: &Session, path: &Path) {
     }
 }
 
+/// What a linker running inside the compiler process reports back.
+pub struct InProcessLinkOutput {
+    pub success: bool,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Runs LLD inside the compiler process for `-Z in-process-lld`, given the LLD
+/// flavor and the arguments that would otherwise be passed to `rust-lld`.
+pub type InProcessLld<'a> = &'a dyn Fn(LldFlavor, &[OsString]) -> InProcessLinkOutput;
+
 /// Performs the linkage portion of the compilation phase. This will generate all
 /// of the requested outputs for this compilation session.
+///
+/// Backends that can link with LLD without spawning a process pass it as
+/// `in_process_lld`.
 pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
     sess: &'a Session,
     codegen_results: &CodegenResults,
     outputs: &OutputFilenames,
     crate_name: &str,
     target_cpu: &str,
+    in_process_lld: Option<InProcessLld<'_>>,
 ) {
     let _timer = sess.timer("link_binary");
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
+
+    sess.time("link_binary_check_files_are_writeable", || {
+        for obj in codegen_results.modules.iter().filter_map(|m| m.object.as_ref()) {
+            check_file_is_writeable(obj, sess);
+        }
+    });
+
+    // All crate types share a single temporary directory. The codegen
+    // artifacts are the same for every output, so intermediate files that
+    // don't depend on the crate type (such as upstream rlibs with their LTO'd
+    // objects stripped) only need to be produced once.
+    let tmpdir = if outputs.outputs.should_codegen() {
+        let tmpdir = TempFileBuilder::new()
+            .prefix("rustc")
+            .tempdir()
+            .unwrap_or_else(|err| sess.fatal(&format!("couldn't create a temp dir: {}", err)));
+        Some(MaybeTempDir::new(tmpdir, sess.opts.cg.save_temps))
+    } else {
+        None
+    };
+
     for &crate_type in sess.crate_types().iter() {
         // Ignore executable crates if we have -Z no-codegen, as they will error.
         if (sess.opts.debugging_opts.no_codegen || !sess.opts.output_types.should_codegen())
@@ -65,27 +102,16 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
             );
         }
 
-        sess.time("link_binary_check_files_are_writeable", || {
-            for obj in codegen_results.modules.iter().filter_map(|m| m.object.as_ref()) {
-                check_file_is_writeable(obj, sess);
-            }
-        });
-
-        if outputs.outputs.should_codegen() {
-            let tmpdir = TempFileBuilder::new()
-                .prefix("rustc")
-                .tempdir()
-                .unwrap_or_else(|err| sess.fatal(&format!("couldn't create a temp dir: {}", err)));
-            let path = MaybeTempDir::new(tmpdir, sess.opts.cg.save_temps);
+        if let Some(ref path) = tmpdir {
             let out_filename = out_filename(sess, crate_type, outputs, crate_name);
             match crate_type {
                 CrateType::Rlib => {
                     let _timer = sess.timer("link_rlib");
-                    link_rlib::<B>(sess, codegen_results, RlibFlavor::Normal, &out_filename, &path)
+                    link_rlib::<B>(sess, codegen_results, RlibFlavor::Normal, &out_filename, path)
                         .build();
                 }
                 CrateType::Staticlib => {
-                    link_staticlib::<B>(sess, codegen_results, &out_filename, &path);
+                    link_staticlib::<B>(sess, codegen_results, &out_filename, path);
                 }
                 _ => {
                     link_natively::<B>(
@@ -95,6 +121,7 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
                         codegen_results,
                         path.as_ref(),
                         target_cpu,
+                        in_process_lld,
                     );
                 }
             }
@@ -460,6 +487,7 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     codegen_results: &CodegenResults,
     tmpdir: &Path,
     target_cpu: &str,
+    in_process_lld: Option<InProcessLld<'_>>,
 ) {
     info!("preparing {:?} to {:?}", crate_type, out_filename);
     let (linker_path, flavor) = linker_and_flavor(sess);
@@ -490,6 +518,27 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     // May have not found libraries in the right formats.
     sess.abort_if_errors();
 
+    if sess.opts.debugging_opts.in_process_lld {
+        // LLD reads variables like `LIB` and `LIBPATH` from the environment, which we
+        // can't set for a library call without affecting the whole process. `PATH` is
+        // always set, but only matters for finding the linker to spawn.
+        let changes_env =
+            cmd.get_env().iter().any(|(k, _)| k != "PATH") || !cmd.get_env_remove().is_empty();
+        match (in_process_lld, flavor) {
+            _ if changes_env => sess.warn(
+                "`-Z in-process-lld` cannot apply the environment variables of the linker \
+                 command, spawning the linker instead",
+            ),
+            (Some(lld), LinkerFlavor::Lld(lld_flavor)) if lld_flavor != LldFlavor::Ld64 => {
+                return link_with_in_process_lld(sess, lld, lld_flavor, &cmd, codegen_results);
+            }
+            _ => sess.warn(
+                "`-Z in-process-lld` is not supported for this linker flavor or by \
+                 this codegen backend, spawning the linker instead",
+            ),
+        }
+    }
+
     // Invoke the system linker
     info!("{:?}", &cmd);
     let retry_on_segfault = env::var("RUSTC_RETRY_LINKER_ON_SEGFAULT").is_ok();
@@ -718,6 +767,9 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             }
             info!("linker stderr:\n{}", escape_string(&prog.stderr));
             info!("linker stdout:\n{}", escape_string(&prog.stdout));
+            if sess.opts.debugging_opts.print_unused_monomorphizations {
+                print_unused_monomorphizations(codegen_results, &prog.stderr);
+            }
         }
         Err(e) => {
             let linker_not_found = e.kind() == io::ErrorKind::NotFound;
@@ -768,6 +820,61 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
     }
 }
 
+fn link_with_in_process_lld(
+    sess: &Session,
+    lld: InProcessLld<'_>,
+    flavor: LldFlavor,
+    cmd: &Command,
+    codegen_results: &CodegenResults,
+) {
+    info!("in-process lld: {:?}", cmd);
+    let output = sess.time("run_linker", || lld(flavor, cmd.get_args()));
+    info!("linker stdout:\n{}", String::from_utf8_lossy(&output.stdout));
+
+    // LLD prints each diagnostic as a `<driver>: error: ...` or
+    // `<driver>: warning: ...` line, followed by `>>> ` lines pointing at the
+    // objects and symbols involved. Turn each of them into a diagnostic of its
+    // own, with those locations as notes.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut reported_error = false;
+    let mut diag: Option<DiagnosticBuilder<'_>> = None;
+    for line in stderr.lines() {
+        if let (Some(diag), Some(location)) = (&mut diag, line.strip_prefix(">>> ")) {
+            diag.note(location.trim());
+            continue;
+        }
+        if let Some(diag) = diag.take() {
+            diag.emit();
+        }
+        let message = line.splitn(2, ": ").nth(1).unwrap_or(line);
+        if let Some(message) = message.strip_prefix("error: ") {
+            reported_error = true;
+            diag = Some(sess.struct_err(&format!("linker error: {}", message)));
+        } else if let Some(message) = message.strip_prefix("warning: ") {
+            diag = Some(sess.struct_warn(&format!("linker warning: {}", message)));
+        } else {
+            info!("linker stderr: {}", line);
+        }
+    }
+    if let Some(diag) = diag {
+        diag.emit();
+    }
+
+    if !output.success {
+        if !reported_error {
+            sess.struct_err("linking with in-process LLD failed")
+                .note(&format!("{:?}", cmd))
+                .emit();
+        }
+        sess.abort_if_errors();
+    }
+    if sess.opts.debugging_opts.print_unused_monomorphizations {
+        let mut out = output.stderr;
+        out.extend(&output.stdout);
+        print_unused_monomorphizations(codegen_results, &out);
+    }
+}
+
 fn link_sanitizers(sess: &Session, crate_type: CrateType, linker: &mut dyn Linker) {
     // On macOS the runtimes are distributed as dylibs which should be linked to
     // both executables and dynamic shared objects. Everywhere else the runtimes
@@ -832,6 +939,37 @@ fn link_sanitizer_runtime(sess: &Session, linker: &mut dyn Linker, name: &str) {
     }
 }
 
+/// Atomic operations wider than the target supports are expanded by LLVM to
+/// the `__atomic_*` libcalls, which on Linux are provided by libatomic. It is
+/// only linked when the local crate or a statically linked upstream crate
+/// uses such atomics, so that other links don't depend on libatomic being
+/// available.
+fn link_libatomic(
+    sess: &Session,
+    crate_type: CrateType,
+    codegen_results: &CodegenResults,
+    linker: &mut dyn Linker,
+) {
+    if sess.target.target_os != "linux" {
+        return;
+    }
+
+    let crate_info = &codegen_results.crate_info;
+    let (_, data) = crate_info
+        .dependency_formats
+        .iter()
+        .find(|(ty, _)| *ty == crate_type)
+        .expect("failed to find crate type in dependency format list");
+    let needs_libatomic = crate_info.needs_atomic_libcalls.contains(&LOCAL_CRATE)
+        || data.iter().enumerate().any(|(i, &linkage)| {
+            linkage == Linkage::Static
+                && crate_info.needs_atomic_libcalls.contains(&CrateNum::new(i + 1))
+        });
+    if needs_libatomic {
+        linker.link_dylib(Symbol::intern("atomic"));
+    }
+}
+
 /// Returns a boolean indicating whether the specified crate should be ignored
 /// during LTO.
 ///
@@ -850,7 +988,7 @@ pub fn ignored_for_lto(sess: &Session, info: &CrateInfo, cnum: CrateNum) -> bool
         && (info.compiler_builtins == Some(cnum) || info.is_no_builtins.contains(&cnum))
 }
 
-fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
+pub fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
     fn infer_from(
         sess: &Session,
         linker: Option<PathBuf>,
@@ -901,6 +1039,10 @@ fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
                     || stem.ends_with("-clang")
                 {
                     LinkerFlavor::Gcc
+                } else if stem == "wasm-ld" || stem.ends_with("-wasm-ld") {
+                    // Checked before the `-ld` suffix below, which would pick the
+                    // GNU `ld` flavor for it.
+                    LinkerFlavor::Lld(LldFlavor::Wasm)
                 } else if stem == "ld" || stem == "ld.lld" || stem.ends_with("-ld") {
                     LinkerFlavor::Ld
                 } else if stem == "link" || stem == "lld-link" {
@@ -935,6 +1077,46 @@ fn linker_and_flavor(sess: &Session) -> (PathBuf, LinkerFlavor) {
     bug!("Not enough information provided to determine how to invoke the linker");
 }
 
+/// Prints the functions generated for the local crate whose sections the linker
+/// reported as removed with `--print-gc-sections`.
+fn print_unused_monomorphizations(codegen_results: &CodegenResults, linker_stderr: &[u8]) {
+    let local_objects: FxHashSet<&Path> =
+        codegen_results.modules.iter().filter_map(|m| m.object.as_deref()).collect();
+
+    let stderr = String::from_utf8_lossy(linker_stderr);
+    let mut unused: Vec<String> = stderr
+        .lines()
+        .filter_map(parse_removed_section)
+        .filter(|&(_, file)| local_objects.contains(Path::new(file)))
+        .filter_map(|(section, _)| section.strip_prefix(".text."))
+        .map(|symbol| format!("{:#}", rustc_demangle::demangle(symbol)))
+        .collect();
+    unused.sort();
+    unused.dedup();
+
+    for name in unused {
+        println!("unused monomorphization: {}", name);
+    }
+}
+
+/// Splits a line of `--print-gc-sections` output into the removed section and
+/// the file it came from. GNU ld prints
+/// `removing unused section '<section>' in file '<file>'`, and lld prints
+/// `removing unused section <file>:(<section>)`.
+fn parse_removed_section(line: &str) -> Option<(&str, &str)> {
+    const PREFIX: &str = "removing unused section ";
+    let rest = &line[line.find(PREFIX)? + PREFIX.len()..];
+    if let Some(rest) = rest.strip_prefix('\'') {
+        let (section, rest) = rest.split_at(rest.find('\'')?);
+        let file = rest.strip_prefix("' in file '")?.strip_suffix('\'')?;
+        Some((section, file))
+    } else {
+        let (file, rest) = rest.split_at(rest.rfind(":(")?);
+        let section = rest.strip_prefix(":(")?.strip_suffix(')')?;
+        Some((section, file))
+    }
+}
+
 /// Returns a boolean indicating whether we should preserve the object files on
 /// the filesystem for their debug information. This is often useful with
 /// split-dwarf like schemes.
@@ -1433,6 +1615,7 @@ fn link_local_crate_native_libs_and_dependent_crate_libs<'a, B: ArchiveBuilder<'
     add_upstream_rust_crates::<B>(cmd, sess, codegen_results, crate_type, tmpdir);
     if sess.opts.debugging_opts.link_native_libraries {
         add_upstream_native_libraries(cmd, sess, codegen_results, crate_type);
+        add_raw_dylib_import_libraries::<B>(cmd, sess, codegen_results, tmpdir);
     }
 }
 
@@ -1636,6 +1819,9 @@ fn linker_with_args<'a, B: ArchiveBuilder<'a>>(
         tmpdir,
     );
 
+    // OBJECT-FILES-NO, AUDIT-ORDER
+    link_libatomic(sess, crate_type, codegen_results, cmd);
+
     // OBJECT-FILES-NO, AUDIT-ORDER
     if sess.opts.cg.profile_generate.enabled() || sess.opts.debugging_opts.instrument_coverage {
         cmd.pgo_gen();
@@ -1646,6 +1832,11 @@ fn linker_with_args<'a, B: ArchiveBuilder<'a>>(
         cmd.control_flow_guard();
     }
 
+    // OBJECT-FILES-NO, AUDIT-ORDER
+    if sess.opts.debugging_opts.hotpatch {
+        cmd.hotpatch();
+    }
+
     // OBJECT-FILES-NO, AUDIT-ORDER
     add_rpath_args(cmd, sess, codegen_results, out_filename);
 
@@ -1709,10 +1900,8 @@ fn add_local_native_libraries(
             NativeLibKind::Framework => cmd.link_framework(name),
             NativeLibKind::StaticNoBundle => cmd.link_staticlib(name),
             NativeLibKind::StaticBundle => cmd.link_whole_staticlib(name, &search_path),
-            NativeLibKind::RawDylib => {
-                // FIXME(#58713): Proper handling for raw dylibs.
-                bug!("raw_dylib feature not yet implemented");
-            }
+            // Linked through the import libraries made by `add_raw_dylib_import_libraries`
+            NativeLibKind::RawDylib => {}
         }
     }
 }
@@ -1910,64 +2099,72 @@ fn add_upstream_rust_crates<'a, B: ArchiveBuilder<'a>>(
         let name = cratepath.file_name().unwrap().to_str().unwrap();
         let name = &name[3..name.len() - 5]; // chop off lib/.rlib
 
-        sess.prof.generic_activity_with_arg("link_altering_rlib", name).run(|| {
-            let mut archive = <B as ArchiveBuilder>::new(sess, &dst, Some(cratepath));
-            archive.update_symbols();
+        // The altered rlib doesn't depend on the crate type being linked, so
+        // if an earlier output of this session already produced it in the
+        // shared temporary directory we can link against that copy directly.
+        let any_objects = dst.exists()
+            || sess.prof.generic_activity_with_arg("link_altering_rlib", name).run(|| {
+                let mut archive = <B as ArchiveBuilder>::new(sess, &dst, Some(cratepath));
+                archive.update_symbols();
+
+                let mut any_objects = false;
+                for f in archive.src_files() {
+                    if f == METADATA_FILENAME {
+                        archive.remove_file(&f);
+                        continue;
+                    }
 
-            let mut any_objects = false;
-            for f in archive.src_files() {
-                if f == METADATA_FILENAME {
-                    archive.remove_file(&f);
-                    continue;
-                }
+                    let canonical = f.replace("-", "_");
+                    let canonical_name = name.replace("-", "_");
 
-                let canonical = f.replace("-", "_");
-                let canonical_name = name.replace("-", "_");
+                    let is_rust_object =
+                        canonical.starts_with(&canonical_name) && looks_like_rust_object_file(&f);
 
-                let is_rust_object =
-                    canonical.starts_with(&canonical_name) && looks_like_rust_object_file(&f);
+                    // If we've been requested to skip all native object files
+                    // (those not generated by the rust compiler) then we can skip
+                    // this file. See above for why we may want to do this.
+                    let skip_because_cfg_say_so = skip_native && !is_rust_object;
 
-                // If we've been requested to skip all native object files
-                // (those not generated by the rust compiler) then we can skip
-                // this file. See above for why we may want to do this.
-                let skip_because_cfg_say_so = skip_native && !is_rust_object;
+                    // If we're performing LTO and this is a rust-generated object
+                    // file, then we don't need the object file as it's part of the
+                    // LTO module. Note that `#![no_builtins]` is excluded from LTO,
+                    // though, so we let that object file slide.
+                    let skip_because_lto = are_upstream_rust_objects_already_included(sess)
+                        && is_rust_object
+                        && (sess.target.options.no_builtins
+                            || !codegen_results.crate_info.is_no_builtins.contains(&cnum));
 
-                // If we're performing LTO and this is a rust-generated object
-                // file, then we don't need the object file as it's part of the
-                // LTO module. Note that `#![no_builtins]` is excluded from LTO,
-                // though, so we let that object file slide.
-                let skip_because_lto = are_upstream_rust_objects_already_included(sess)
-                    && is_rust_object
-                    && (sess.target.options.no_builtins
-                        || !codegen_results.crate_info.is_no_builtins.contains(&cnum));
+                    if skip_because_cfg_say_so || skip_because_lto {
+                        archive.remove_file(&f);
+                    } else {
+                        any_objects = true;
+                    }
+                }
 
-                if skip_because_cfg_say_so || skip_because_lto {
-                    archive.remove_file(&f);
-                } else {
-                    any_objects = true;
+                if any_objects {
+                    archive.build();
                 }
-            }
+                any_objects
+            });
 
-            if !any_objects {
-                return;
-            }
-            archive.build();
-
-            // If we're creating a dylib, then we need to include the
-            // whole of each object in our archive into that artifact. This is
-            // because a `dylib` can be reused as an intermediate artifact.
-            //
-            // Note, though, that we don't want to include the whole of a
-            // compiler-builtins crate (e.g., compiler-rt) because it'll get
-            // repeatedly linked anyway.
-            if crate_type == CrateType::Dylib
-                && codegen_results.crate_info.compiler_builtins != Some(cnum)
-            {
-                cmd.link_whole_rlib(&fix_windows_verbatim_for_gcc(&dst));
-            } else {
-                cmd.link_rlib(&fix_windows_verbatim_for_gcc(&dst));
-            }
-        });
+        if !any_objects {
+            return;
+        }
+
+        // If we're creating a dylib, then we need to include the
+        // whole of each object in our archive into that artifact. This is
+        // because a `dylib` can be reused as an intermediate artifact.
+        //
+        // Note, though, that we don't want to include the whole of a
+        // compiler-builtins crate (e.g., compiler-rt) because it'll get
+        // repeatedly linked anyway.
+        if crate_type == CrateType::Dylib
+            && codegen_results.crate_info.compiler_builtins != Some(cnum)
+        {
+            cmd.link_whole_rlib(&fix_windows_verbatim_for_gcc(&dst));
+        } else {
+            cmd.link_rlib(&fix_windows_verbatim_for_gcc(&dst));
+        }
     }
 
     // Same thing as above, but for dynamic crates instead of static crates.
@@ -2052,13 +2249,80 @@ fn add_upstream_native_libraries(
                 // already included them when we included the rust library
                 // previously
                 NativeLibKind::StaticBundle => {}
-                NativeLibKind::RawDylib => {
-                    // FIXME(#58713): Proper handling for raw dylibs.
-                    bug!("raw_dylib feature not yet implemented");
+                // Linked through the import libraries made by
+                // `add_raw_dylib_import_libraries`
+                NativeLibKind::RawDylib => {}
+            }
+        }
+    }
+}
+
+/// Synthesizes an import library for every DLL named by a `kind = "raw-dylib"`
+/// library of the local crate or an upstream crate, so that the DLL can be
+/// linked against without a `.lib` file for it being installed.
+fn add_raw_dylib_import_libraries<'a, B: ArchiveBuilder<'a>>(
+    cmd: &mut dyn Linker,
+    sess: &'a Session,
+    codegen_results: &CodegenResults,
+    tmpdir: &Path,
+) {
+    let crate_info = &codegen_results.crate_info;
+    let upstream_libs = crate_info
+        .used_crates_static
+        .iter()
+        .flat_map(|&(cnum, _)| crate_info.native_libraries[&cnum].iter());
+    let libs = crate_info.used_libraries.iter().chain(upstream_libs);
+
+    for (dll_name, dll_imports) in collate_raw_dylibs(sess, libs) {
+        let output = tmpdir.join(format!("{}.lib", dll_name));
+        B::create_dll_import_lib(sess, &dll_name, &dll_imports, &output);
+        cmd.add_object(&output);
+    }
+}
+
+/// Groups the imports of all `kind = "raw-dylib"` libraries by DLL name,
+/// removing duplicate imports of the same function.
+fn collate_raw_dylibs<'a>(
+    sess: &Session,
+    libs: impl Iterator<Item = &'a NativeLib>,
+) -> Vec<(String, Vec<DllImport>)> {
+    let mut dylib_table: FxIndexMap<String, FxIndexMap<Symbol, &DllImport>> = Default::default();
+
+    for lib in libs.filter(|lib| lib.kind == NativeLibKind::RawDylib && relevant_lib(sess, lib)) {
+        if !sess.target.options.is_like_windows {
+            sess.fatal("`raw-dylib` libraries are only supported on Windows targets");
+        }
+        let name = match lib.name {
+            Some(name) => name.as_str(),
+            None => continue,
+        };
+        let name = if name.ends_with(".dll") { name.to_string() } else { format!("{}.dll", name) };
+        let imports = dylib_table.entry(name.clone()).or_default();
+        for import in &lib.dll_imports {
+            if let Some(old_import) = imports.insert(import.name, import) {
+                if old_import.ordinal != import.ordinal {
+                    sess.err(&format!(
+                        "multiple imports of `{}` from `{}` have different ordinals",
+                        import.name, name
+                    ));
+                }
+                if old_import.calling_convention != import.calling_convention {
+                    sess.err(&format!(
+                        "multiple imports of `{}` from `{}` have different calling conventions",
+                        import.name, name
+                    ));
                 }
             }
         }
     }
+    sess.abort_if_errors();
+
+    dylib_table
+        .into_iter()
+        .map(|(name, imports)| {
+            (name, imports.into_iter().map(|(_, import)| import.clone()).collect())
+        })
+        .collect()
 }
 
 fn relevant_lib(sess: &Session, lib: &NativeLib) -> bool {
@@ -121,6 +121,7 @@ pub trait Linker {
     fn optimize(&mut self);
     fn pgo_gen(&mut self);
     fn control_flow_guard(&mut self);
+    fn hotpatch(&mut self) {}
     fn debuginfo(&mut self, strip: Strip);
     fn no_crt_objects(&mut self);
     fn no_default_libraries(&mut self);
@@ -184,7 +185,7 @@ impl<'a> GccLinker<'a> {
         // * On OSX they have their own linker, not binutils'
         // * For WebAssembly the only functional linker is LLD, which doesn't
         //   support hint flags
-        !self.sess.target.options.is_like_osx && self.sess.target.arch != "wasm32"
+        !self.sess.target.options.is_like_osx && !self.sess.target.options.is_like_wasm
     }
 
     // Some platforms take hints about whether a library is static or dynamic.
@@ -436,6 +437,9 @@ impl<'a> Linker for GccLinker<'a> {
         // reduction.
         } else if !keep_metadata {
             self.linker_arg("--gc-sections");
+            if self.sess.opts.debugging_opts.print_unused_monomorphizations {
+                self.linker_arg("--print-gc-sections");
+            }
         }
     }
 
@@ -766,6 +770,16 @@ impl<'a> Linker for MsvcLinker<'a> {
         self.cmd.arg("/guard:cf");
     }
 
+    fn hotpatch(&mut self) {
+        // Leaves room before each function for the long jump that the
+        // two-byte hotpatch instruction at its start branches to. Like the
+        // instruction itself, this only exists on x86 and x86_64.
+        let arch = &self.sess.target.arch;
+        if arch == "x86" || arch == "x86_64" {
+            self.cmd.arg("/FUNCTIONPADMIN");
+        }
+    }
+
     fn debuginfo(&mut self, strip: Strip) {
         match strip {
             Strip::None => {
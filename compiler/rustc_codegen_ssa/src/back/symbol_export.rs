@@ -9,13 +9,16 @@ use rustc_hir::Node;
 use rustc_index::vec::IndexVec;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::exported_symbols::{
-    metadata_symbol_name, ExportedSymbol, SymbolExportLevel,
+    abi_checksum_guard_name, metadata_symbol_name, ExportedSymbol, SymbolExportLevel,
+    ABI_CHECKSUM_GUARD_PREFIX, ABI_CHECKSUM_SYMBOL_NAME,
 };
+use rustc_middle::mir::{self, mono::MonoItem};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
 use rustc_middle::ty::Instance;
-use rustc_middle::ty::{SymbolName, TyCtxt};
-use rustc_session::config::{CrateType, SanitizerSet};
+use rustc_middle::ty::{self, SymbolName, TyCtxt};
+use rustc_session::config::{CrateType, OomStrategy, SanitizerSet};
+use rustc_target::spec::abi::Abi;
 
 pub fn threshold(tcx: TyCtxt<'_>) -> SymbolExportLevel {
     crates_export_threshold(&tcx.sess.crate_types())
@@ -140,6 +143,19 @@ fn reachable_non_generics_provider(tcx: TyCtxt<'_>, cnum: CrateNum) -> DefIdMap<
         reachable_non_generics.insert(id, SymbolExportLevel::C);
     }
 
+    // The artifact linking a MIR-only rlib codegens only what the rlib exports,
+    // so its `#[used]` statics have to be exported to be kept.
+    if tcx.sess.is_mir_only_rlib() {
+        for item in tcx.hir().krate().items.values() {
+            let def_id = tcx.hir().local_def_id(item.hir_id).to_def_id();
+            if let hir::ItemKind::Static(..) = item.kind {
+                if tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::USED) {
+                    reachable_non_generics.entry(def_id).or_insert(SymbolExportLevel::Rust);
+                }
+            }
+        }
+    }
+
     reachable_non_generics
 }
 
@@ -173,6 +189,14 @@ fn exported_symbols_provider_local(
         .map(|(&def_id, &level)| (ExportedSymbol::NonGeneric(def_id), level))
         .collect();
 
+    // A MIR-only rlib has no object code of its own: the artifact linking it
+    // codegens exactly these items (see `collect_roots`), and nothing else
+    // can be shared with downstream crates.
+    if tcx.sess.is_mir_only_rlib() {
+        symbols.sort_by_cached_key(|s| s.0.symbol_name_for_local_instance(tcx));
+        return tcx.arena.alloc_from_iter(symbols);
+    }
+
     if tcx.entry_fn(LOCAL_CRATE).is_some() {
         let exported_symbol = ExportedSymbol::NoDefId(SymbolName::new(tcx, "main"));
 
@@ -186,6 +210,9 @@ fn exported_symbols_provider_local(
 
             symbols.push((exported_symbol, SymbolExportLevel::Rust));
         }
+
+        let exported_symbol = ExportedSymbol::NoDefId(SymbolName::new(tcx, OomStrategy::SYMBOL));
+        symbols.push((exported_symbol, SymbolExportLevel::Rust));
     }
 
     if tcx.sess.opts.debugging_opts.instrument_coverage
@@ -213,6 +240,16 @@ fn exported_symbols_provider_local(
         }));
     }
 
+    if tcx.sess.opts.debugging_opts.embed_abi_checksum {
+        let exported_symbol =
+            ExportedSymbol::NoDefId(SymbolName::new(tcx, ABI_CHECKSUM_SYMBOL_NAME));
+        symbols.push((exported_symbol, SymbolExportLevel::C));
+
+        let guard_name = abi_checksum_guard_name(tcx, LOCAL_CRATE);
+        let exported_symbol = ExportedSymbol::NoDefId(SymbolName::new(tcx, &guard_name));
+        symbols.push((exported_symbol, SymbolExportLevel::Rust));
+    }
+
     if tcx.sess.crate_types().contains(&CrateType::Dylib) {
         let symbol_name = metadata_symbol_name(tcx);
         let exported_symbol = ExportedSymbol::NoDefId(SymbolName::new(tcx, &symbol_name));
@@ -357,6 +394,61 @@ fn upstream_drop_glue_for_provider<'tcx>(
     }
 }
 
+fn upstream_abi_checksum_crates_provider(tcx: TyCtxt<'_>, cnum: CrateNum) -> &[CrateNum] {
+    debug_assert!(cnum == LOCAL_CRATE);
+
+    tcx.arena.alloc_from_iter(tcx.crates().iter().copied().filter(|&cnum| {
+        tcx.exported_symbols(cnum).iter().any(|(symbol, _)| match symbol {
+            ExportedSymbol::NoDefId(symbol_name) => {
+                symbol_name.name.starts_with(ABI_CHECKSUM_GUARD_PREFIX)
+            }
+            _ => false,
+        })
+    }))
+}
+
+fn needs_atomic_libcalls_provider(tcx: TyCtxt<'_>, cnum: CrateNum) -> bool {
+    debug_assert!(cnum == LOCAL_CRATE);
+
+    let max_atomic_width = tcx.sess.target.max_atomic_width();
+    let (_, cgus) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+    cgus.iter().flat_map(|cgu| cgu.items().keys()).any(|item| {
+        let instance = match *item {
+            MonoItem::Fn(instance) => instance,
+            MonoItem::Static(_) | MonoItem::GlobalAsm(_) => return false,
+        };
+        let body = tcx.instance_mir(instance.def);
+        body.basic_blocks().iter().any(|data| {
+            let func = match data.terminator().kind {
+                mir::TerminatorKind::Call { ref func, .. } => func,
+                _ => return false,
+            };
+            let param_env = ty::ParamEnv::reveal_all();
+            let func_ty = func.ty(body, tcx);
+            let func_ty = match instance.substs_for_mir_body() {
+                Some(substs) => {
+                    tcx.subst_and_normalize_erasing_regions(substs, param_env, &func_ty)
+                }
+                None => tcx.normalize_erasing_regions(param_env, func_ty),
+            };
+            let (def_id, substs) = match *func_ty.kind() {
+                ty::FnDef(def_id, substs) => (def_id, substs),
+                _ => return false,
+            };
+            if tcx.fn_sig(def_id).abi() != Abi::RustIntrinsic
+                || !tcx.item_name(def_id).as_str().starts_with("atomic_")
+            {
+                return false;
+            }
+            // Fences have no operand type.
+            substs.types().next().map_or(false, |ty| {
+                tcx.layout_of(param_env.and(ty))
+                    .map_or(false, |layout| layout.size.bits() > max_atomic_width)
+            })
+        })
+    })
+}
+
 fn is_unreachable_local_definition_provider(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     if let Some(def_id) = def_id.as_local() {
         !tcx.reachable_set(LOCAL_CRATE).contains(&def_id)
@@ -372,6 +464,8 @@ pub fn provide(providers: &mut Providers) {
     providers.upstream_monomorphizations = upstream_monomorphizations_provider;
     providers.is_unreachable_local_definition = is_unreachable_local_definition_provider;
     providers.upstream_drop_glue_for = upstream_drop_glue_for_provider;
+    providers.upstream_abi_checksum_crates = upstream_abi_checksum_crates_provider;
+    providers.needs_atomic_libcalls = needs_atomic_libcalls_provider;
 }
 
 pub fn provide_extern(providers: &mut Providers) {
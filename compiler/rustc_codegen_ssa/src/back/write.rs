@@ -109,6 +109,7 @@ pub struct ModuleConfig {
     pub inline_threshold: Option<usize>,
     pub new_llvm_pass_manager: bool,
     pub emit_lifetime_markers: bool,
+    pub virtual_function_elimination: bool,
 }
 
 impl ModuleConfig {
@@ -264,6 +265,7 @@ impl ModuleConfig {
             inline_threshold: sess.opts.cg.inline_threshold,
             new_llvm_pass_manager: sess.opts.debugging_opts.new_llvm_pass_manager,
             emit_lifetime_markers: sess.emit_lifetime_markers(),
+            virtual_function_elimination: sess.opts.debugging_opts.virtual_function_elimination,
         }
     }
 
@@ -413,7 +415,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
     let sess = tcx.sess;
 
     let crate_name = tcx.crate_name(LOCAL_CRATE);
-    let no_builtins = tcx.sess.contains_name(&tcx.hir().krate().item.attrs, sym::no_builtins);
+    let no_builtins = tcx.is_no_builtins(LOCAL_CRATE);
     let is_compiler_builtins =
         tcx.sess.contains_name(&tcx.hir().krate().item.attrs, sym::compiler_builtins);
     let subsystem = tcx
@@ -39,7 +39,7 @@ use rustc_middle::middle::cstore::{self, LinkagePreference};
 use rustc_middle::middle::lang_items;
 use rustc_middle::mir::mono::{CodegenUnit, CodegenUnitNameBuilder, MonoItem};
 use rustc_middle::ty::layout::{HasTyCtxt, TyAndLayout};
-use rustc_middle::ty::layout::{FAT_PTR_ADDR, FAT_PTR_EXTRA};
+use rustc_middle::ty::layout::{PointerMetadataKind, FAT_PTR_ADDR, FAT_PTR_EXTRA};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::cgu_reuse_tracker::CguReuse;
@@ -49,6 +49,7 @@ use rustc_session::Session;
 use rustc_span::Span;
 use rustc_symbol_mangling::test as symbol_names_test;
 use rustc_target::abi::{Align, LayoutOf, VariantIdx};
+use rustc_target::spec::EhStrategy;
 
 use std::cmp;
 use std::ops::{Deref, DerefMut};
@@ -153,17 +154,17 @@ pub fn unsized_info<'tcx, Cx: CodegenMethods<'tcx>>(
 ) -> Cx::Value {
     let (source, target) =
         cx.tcx().struct_lockstep_tails_erasing_lifetimes(source, target, cx.param_env());
-    match (source.kind(), target.kind()) {
-        (&ty::Array(_, len), &ty::Slice(_)) => {
+    match (PointerMetadataKind::of_tail(target), source.kind(), target.kind()) {
+        (Some(PointerMetadataKind::Length), &ty::Array(_, len), _) => {
             cx.const_usize(len.eval_usize(cx.tcx(), ty::ParamEnv::reveal_all()))
         }
-        (&ty::Dynamic(..), &ty::Dynamic(..)) => {
+        (Some(PointerMetadataKind::Vtable), &ty::Dynamic(..), _) => {
             // For now, upcasts are limited to changes in marker
             // traits, and hence never actually require an actual
             // change to the vtable.
             old_info.expect("unsized_info: missing old info for trait upcast")
         }
-        (_, &ty::Dynamic(ref data, ..)) => {
+        (Some(PointerMetadataKind::Vtable), _, &ty::Dynamic(ref data, ..)) => {
             let vtable_ptr = cx.layout_of(cx.tcx().mk_mut_ptr(target)).field(cx, FAT_PTR_EXTRA);
             cx.const_ptrcast(
                 meth::get_vtable(cx, source, data.principal()),
@@ -327,7 +328,13 @@ fn cast_shift_rhs<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
 /// currently uses SEH-ish unwinding with DWARF info tables to the side (same as
 /// 64-bit MinGW) instead of "full SEH".
 pub fn wants_msvc_seh(sess: &Session) -> bool {
-    sess.target.options.is_like_msvc
+    sess.eh_strategy() == EhStrategy::MsvcSeh
+}
+
+/// Returns `true` if cleanups are lowered to funclets (`cleanuppad` and
+/// friends) rather than to landing pads.
+pub fn wants_funclets(sess: &Session) -> bool {
+    matches!(sess.eh_strategy(), EhStrategy::MsvcSeh | EhStrategy::Wasm)
 }
 
 pub fn memcpy_ty<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
@@ -485,8 +492,12 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     metadata: EncodedMetadata,
     need_metadata_module: bool,
 ) -> OngoingCodegen<B> {
-    // Skip crate items and just output metadata in -Z no-codegen mode.
-    if tcx.sess.opts.debugging_opts.no_codegen || !tcx.sess.opts.output_types.should_codegen() {
+    // Skip crate items and just output metadata in -Z no-codegen mode, or if
+    // codegen is deferred to downstream crates with -Z mir-only-rlibs.
+    if tcx.sess.opts.debugging_opts.no_codegen
+        || !tcx.sess.opts.output_types.should_codegen()
+        || tcx.sess.is_mir_only_rlib()
+    {
         let ongoing_codegen = start_async_codegen(backend, tcx, metadata, 1);
 
         ongoing_codegen.codegen_finished(tcx);
@@ -770,6 +781,7 @@ impl CrateInfo {
             compiler_builtins: None,
             profiler_runtime: None,
             is_no_builtins: Default::default(),
+            needs_atomic_libcalls: Default::default(),
             native_libraries: Default::default(),
             used_libraries: tcx.native_libraries(LOCAL_CRATE),
             link_args: tcx.link_args(LOCAL_CRATE),
@@ -807,6 +819,9 @@ impl CrateInfo {
             if tcx.is_no_builtins(cnum) {
                 info.is_no_builtins.insert(cnum);
             }
+            if tcx.needs_atomic_libcalls(cnum) {
+                info.needs_atomic_libcalls.insert(cnum);
+            }
             let missing = tcx.missing_lang_items(cnum);
             for &item in missing.iter() {
                 if let Ok(id) = lang_items.require(item) {
@@ -820,6 +835,10 @@ impl CrateInfo {
             info.missing_lang_items.insert(cnum, missing);
         }
 
+        if tcx.needs_atomic_libcalls(LOCAL_CRATE) {
+            info.needs_atomic_libcalls.insert(LOCAL_CRATE);
+        }
+
         info
     }
 }
@@ -44,6 +44,30 @@ pub enum RealPredicate {
     RealPredicateTrue,
 }
 
+/// Attributes that can be attached to an individual call site, on top of
+/// those implied by the callee's ABI.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CallSiteAttr {
+    Cold,
+    NoInline,
+    NoReturn,
+}
+
+/// The call-site attributes put on every call to a panic entry point.
+pub const PANIC_CALLSITE_ATTRS: &[CallSiteAttr] = &[CallSiteAttr::Cold, CallSiteAttr::NoReturn];
+
+/// How a call may be turned into a tail call.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TailCallKind {
+    None,
+    Tail,
+    /// The call must be emitted as a tail call. The caller and callee must have
+    /// matching ABIs, and the call must be immediately followed by a return of
+    /// its result.
+    MustTail,
+    NoTail,
+}
+
 pub enum AtomicRmwBinOp {
     AtomicXchg,
     AtomicAdd,
@@ -186,6 +210,11 @@ pub fn shift_mask_val<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
                 shift_mask_val(bx, bx.element_type(llty), bx.element_type(mask_llty), invert);
             bx.vector_splat(bx.vector_length(mask_llty), mask)
         }
+        TypeKind::ScalableVector => {
+            let mask =
+                shift_mask_val(bx, bx.element_type(llty), bx.element_type(mask_llty), invert);
+            bx.scalable_vector_splat(bx.vector_length(mask_llty), mask)
+        }
         _ => bug!("shift_mask_val: expected Integer or Vector, found {:?}", kind),
     }
 }
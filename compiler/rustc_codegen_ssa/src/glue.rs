@@ -5,6 +5,7 @@
 use crate::common::IntPredicate;
 use crate::meth;
 use crate::traits::*;
+use rustc_middle::ty::layout::PointerMetadataKind;
 use rustc_middle::ty::{self, Ty};
 
 pub fn size_and_align_of_dst<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
@@ -19,13 +20,13 @@ pub fn size_and_align_of_dst<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         let align = bx.const_usize(layout.align.abi.bytes());
         return (size, align);
     }
-    match t.kind() {
-        ty::Dynamic(..) => {
+    match PointerMetadataKind::of_tail(t) {
+        Some(PointerMetadataKind::Vtable) => {
             // load size/align from vtable
             let vtable = info.unwrap();
             (meth::SIZE.get_usize(bx, vtable), meth::ALIGN.get_usize(bx, vtable))
         }
-        ty::Slice(_) | ty::Str => {
+        Some(PointerMetadataKind::Length) => {
             let unit = layout.field(bx, 0);
             // The info in this case is the length of the str, so the size is that
             // times the unit size.
@@ -32,6 +32,7 @@ use rustc_session::config::{OutputFilenames, OutputType, RUST_CGU_EXT};
 use rustc_span::symbol::Symbol;
 use std::path::{Path, PathBuf};
 
+pub mod abi_tests;
 pub mod back;
 pub mod base;
 pub mod common;
@@ -115,6 +116,7 @@ pub struct CrateInfo {
     pub compiler_builtins: Option<CrateNum>,
     pub profiler_runtime: Option<CrateNum>,
     pub is_no_builtins: FxHashSet<CrateNum>,
+    pub needs_atomic_libcalls: FxHashSet<CrateNum>,
     pub native_libraries: FxHashMap<CrateNum, Lrc<Vec<NativeLib>>>,
     pub crate_name: FxHashMap<CrateNum, String>,
     pub used_libraries: Lrc<Vec<NativeLib>>,
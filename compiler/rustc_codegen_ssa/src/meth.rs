@@ -1,6 +1,8 @@
 use crate::traits::*;
 
-use rustc_middle::ty::{self, Instance, Ty};
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_session::config::CrateType;
 use rustc_target::abi::call::FnAbi;
 
 #[derive(Copy, Clone, Debug)]
@@ -15,16 +17,29 @@ impl<'a, 'tcx> VirtualIndex {
         VirtualIndex(index as u64 + 3)
     }
 
+    /// Loads the function at this index of `llvtable`, a vtable of the trait
+    /// object type `ty`.
     pub fn get_fn<Bx: BuilderMethods<'a, 'tcx>>(
         self,
         bx: &mut Bx,
         llvtable: Bx::Value,
+        ty: Ty<'tcx>,
         fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
     ) -> Bx::Value {
         // Load the data pointer from the object.
         debug!("get_fn({:?}, {:?})", llvtable, self);
 
-        let llvtable = bx.pointercast(llvtable, bx.type_ptr_to(bx.fn_ptr_backend_type(fn_abi)));
+        let llty = bx.fn_ptr_backend_type(fn_abi);
+        if bx.sess().opts.debugging_opts.virtual_function_elimination {
+            if let Some(trait_ref) = principal_of_dyn(ty) {
+                let typeid = typeid_for_trait_ref(bx.tcx(), trait_ref);
+                let vtable_byte_offset = self.0 * bx.tcx().data_layout.pointer_size.bytes();
+                let func = bx.type_checked_load(llvtable, vtable_byte_offset, &typeid);
+                return bx.pointercast(func, llty);
+            }
+        }
+
+        let llvtable = bx.pointercast(llvtable, bx.type_ptr_to(llty));
         let ptr_align = bx.tcx().data_layout.pointer_align.abi;
         let gep = bx.inbounds_gep(llvtable, &[bx.const_usize(self.0)]);
         let ptr = bx.load(gep, ptr_align);
@@ -48,10 +63,59 @@ impl<'a, 'tcx> VirtualIndex {
         let ptr = bx.load(gep, usize_align);
         // Vtable loads are invariant.
         bx.set_invariant_load(ptr);
+        // No type is larger than `isize::MAX` bytes, and alignments are
+        // nonzero and at most 2^29.
+        if self.0 == SIZE.0 {
+            let pointer_size = bx.tcx().data_layout.pointer_size;
+            bx.range_metadata(ptr, 0..(1 << (pointer_size.bits() - 1)));
+        } else if self.0 == ALIGN.0 {
+            bx.range_metadata(ptr, 1..(1 << 29) + 1);
+        }
         ptr
     }
 }
 
+fn principal_of_dyn<'tcx>(ty: Ty<'tcx>) -> Option<ty::PolyExistentialTraitRef<'tcx>> {
+    match ty.kind() {
+        ty::Dynamic(data, _) => data.principal(),
+        _ => bug!("expected a trait object type, found {:?}", ty),
+    }
+}
+
+/// Returns the identifier under which the vtables of `trait_ref` are
+/// registered with the backend, which is the same in every crate.
+pub fn typeid_for_trait_ref<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_ref: ty::PolyExistentialTraitRef<'tcx>,
+) -> String {
+    // Auto traits don't change the layout of a vtable, so only the principal
+    // trait is hashed.
+    let predicates = trait_ref.map_bound(|trait_ref| {
+        tcx.intern_existential_predicates(&[ty::ExistentialPredicate::Trait(trait_ref)])
+    });
+    let ty = tcx.mk_dynamic(predicates, tcx.lifetimes.re_erased);
+    format!("rust.vtable.{:016x}", tcx.type_id_hash(ty))
+}
+
+/// Whether every call through the vtables of `trait_ref` is codegened in this
+/// crate graph. That is only known for traits of the local crate that other
+/// crates can't name, as the vtables of other traits (e.g. `dyn Error`) may be
+/// called from prebuilt code, such as the standard library.
+fn vtable_calls_are_local<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_ref: ty::PolyExistentialTraitRef<'tcx>,
+) -> bool {
+    let def_id = match trait_ref.def_id().as_local() {
+        Some(def_id) => def_id,
+        None => return false,
+    };
+    if tcx.sess.crate_types().iter().all(|ty| *ty == CrateType::Executable) {
+        return true;
+    }
+    let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+    !tcx.privacy_access_levels(LOCAL_CRATE).is_reachable(hir_id)
+}
+
 /// Creates a dynamic vtable for the given type and vtable origin.
 /// This is used only for objects.
 ///
@@ -120,6 +184,13 @@ pub fn get_vtable<'tcx, Cx: CodegenMethods<'tcx>>(
     let vtable = cx.static_addr_of(vtable_const, align, Some("vtable"));
 
     cx.create_vtable_metadata(ty, vtable);
+    if cx.sess().opts.debugging_opts.virtual_function_elimination {
+        if let Some(trait_ref) = trait_ref {
+            let typeid = typeid_for_trait_ref(tcx, trait_ref);
+            let local_calls_only = vtable_calls_are_local(tcx, trait_ref);
+            cx.add_vtable_type_metadata(vtable, &typeid, local_calls_only);
+        }
+    }
 
     cx.vtables().borrow_mut().insert((ty, trait_ref), vtable);
     vtable
@@ -13,7 +13,8 @@ use rustc_middle::mir::visit::{
 use rustc_middle::mir::{self, Location, TerminatorKind};
 use rustc_middle::ty;
 use rustc_middle::ty::layout::HasTyCtxt;
-use rustc_target::abi::LayoutOf;
+use rustc_session::config::OptLevel;
+use rustc_target::abi::{LayoutOf, PointerKind, Size};
 
 pub fn non_ssa_locals<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     fx: &FunctionCx<'a, 'tcx, Bx>,
@@ -324,6 +325,151 @@ impl<'mir, 'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> Visitor<'tcx>
     }
 }
 
+/// The alias scopes of the memory accessed by a function, see `alias_scopes`.
+pub struct AliasScopes<V> {
+    /// The scope of the memory behind each local, if any, as an index into
+    /// `nodes`.
+    pub scopes: IndexVec<mir::Local, Option<usize>>,
+    /// The backend's scope nodes, all in a domain of their own. They are
+    /// anonymous rather than identified by name, so that every inlined copy of
+    /// the function gets fresh scopes instead of sharing them with the others.
+    pub nodes: Vec<V>,
+}
+
+/// Assigns an alias scope to each reference argument that is `noalias` (see
+/// `PointerKind`), and propagates it to the locals that are only ever
+/// assigned a copy or a reborrow of such an argument. The memory behind two
+/// locals in different scopes is disjoint for the whole call, which LLVM can
+/// only see for itself if the accesses go through the arguments directly.
+pub fn alias_scopes<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
+    fx: &FunctionCx<'a, 'tcx, Bx>,
+    bx: &mut Bx,
+) -> AliasScopes<Bx::Value> {
+    let mir = fx.mir;
+    let mut scopes = IndexVec::from_elem(None, &mir.local_decls);
+
+    // LLVM doesn't look at the scopes without optimizations.
+    if fx.cx.sess().opts.optimize == OptLevel::No {
+        return AliasScopes { scopes, nodes: Vec::new() };
+    }
+
+    let mut analyzer = AliasAnalyzer {
+        sources: IndexVec::from_elem(None, &mir.local_decls),
+        disqualified: BitSet::new_empty(mir.local_decls.len()),
+    };
+    // Arguments get assigned to by means of the function being called.
+    for arg in mir.args_iter() {
+        analyzer.sources[arg] = Some(arg);
+    }
+    analyzer.visit_body(&mir);
+
+    let mut count = 0;
+    for arg in mir.args_iter() {
+        let ty = fx.monomorphize(&mir.local_decls[arg].ty);
+        if analyzer.disqualified.contains(arg) || !matches!(ty.kind(), ty::Ref(..)) {
+            continue;
+        }
+        let layout = fx.cx.layout_of(ty);
+        match layout.pointee_info_at(fx.cx, Size::ZERO).and_then(|pointee| pointee.safe) {
+            Some(PointerKind::Frozen | PointerKind::UniqueBorrowed) => {
+                scopes[arg] = Some(count);
+                count += 1;
+            }
+            _ => {}
+        }
+    }
+
+    // A single scope doesn't tell LLVM anything.
+    if count < 2 {
+        return AliasScopes {
+            scopes: IndexVec::from_elem(None, &mir.local_decls),
+            nodes: Vec::new(),
+        };
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (local, &source) in analyzer.sources.iter_enumerated() {
+            if scopes[local].is_some() || analyzer.disqualified.contains(local) {
+                continue;
+            }
+            if let Some(scope) = source.and_then(|source| scopes[source]) {
+                scopes[local] = Some(scope);
+                changed = true;
+            }
+        }
+    }
+
+    debug!("alias_scopes({:?}) = {:?}", fx.instance, scopes);
+    let domain = fx.cx.tcx().symbol_name(fx.instance).name;
+    AliasScopes { scopes, nodes: bx.alias_scope_domain(domain, count) }
+}
+
+struct AliasAnalyzer {
+    /// The local each local is a copy or reborrow of, for locals assigned to
+    /// exactly once.
+    sources: IndexVec<mir::Local, Option<mir::Local>>,
+    /// Locals assigned to more than once or from anything else, and locals
+    /// whose own storage is borrowed (and could be written through).
+    disqualified: BitSet<mir::Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for AliasAnalyzer {
+    fn visit_assign(
+        &mut self,
+        place: &mir::Place<'tcx>,
+        rvalue: &mir::Rvalue<'tcx>,
+        location: Location,
+    ) {
+        if let Some(local) = place.as_local() {
+            let source = match *rvalue {
+                mir::Rvalue::Use(
+                    mir::Operand::Copy(ref source) | mir::Operand::Move(ref source),
+                ) => source.as_local(),
+                mir::Rvalue::Ref(_, _, ref borrowed) => match borrowed.as_ref().projection {
+                    [mir::ProjectionElem::Deref, rest @ ..]
+                        if !rest.contains(&mir::ProjectionElem::Deref) =>
+                    {
+                        Some(borrowed.local)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            match (self.sources[local], source) {
+                (None, Some(source)) => self.sources[local] = Some(source),
+                _ => {
+                    self.disqualified.insert(local);
+                }
+            }
+        } else {
+            self.visit_place(place, PlaceContext::MutatingUse(MutatingUseContext::Store), location);
+        }
+
+        self.visit_rvalue(rvalue, location);
+    }
+
+    fn visit_place(&mut self, place: &mir::Place<'tcx>, context: PlaceContext, _: Location) {
+        // Going through a reference leaves the local holding it untouched.
+        if place.projection.first() == Some(&mir::ProjectionElem::Deref) {
+            return;
+        }
+        match context {
+            PlaceContext::MutatingUse(_)
+            | PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::SharedBorrow
+                | NonMutatingUseContext::UniqueBorrow
+                | NonMutatingUseContext::ShallowBorrow
+                | NonMutatingUseContext::AddressOf,
+            ) => {
+                self.disqualified.insert(place.local);
+            }
+            PlaceContext::NonMutatingUse(_) | PlaceContext::NonUse(_) => {}
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum CleanupKind {
     NotCleanup,
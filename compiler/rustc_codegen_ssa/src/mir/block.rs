@@ -1,28 +1,35 @@
+use super::naked_asm::asm_const_to_str;
 use super::operand::OperandRef;
 use super::operand::OperandValue::{Immediate, Pair, Ref};
 use super::place::PlaceRef;
 use super::{FunctionCx, LocalRef};
 
 use crate::base;
-use crate::common::{self, IntPredicate};
+use crate::common::{self, CallSiteAttr, IntPredicate, TailCallKind, PANIC_CALLSITE_ATTRS};
 use crate::meth;
 use crate::traits::*;
 use crate::MemFlags;
 
 use rustc_ast as ast;
+use rustc_hir as hir;
 use rustc_hir::lang_items::LangItem;
 use rustc_index::vec::Idx;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::interpret::ConstValue;
 use rustc_middle::mir::AssertKind;
 use rustc_middle::mir::{self, SwitchTargets};
-use rustc_middle::ty::layout::{FnAbiExt, HasTyCtxt};
+use rustc_middle::ty::layout::{FnAbiExt, HasTyCtxt, TyAndLayout};
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, Instance, Ty, TypeFoldable};
+use rustc_session::config::OverflowTrap;
 use rustc_span::source_map::Span;
 use rustc_span::{sym, Symbol};
 use rustc_target::abi::call::{ArgAbi, FnAbi, PassMode};
-use rustc_target::abi::{self, LayoutOf};
+use rustc_target::abi::{self, LayoutOf, Size};
 use rustc_target::spec::abi::Abi;
+use rustc_target::spec::EhStrategy;
+
+use std::iter;
 
 /// Used by `FunctionCx::codegen_terminator` for emitting common patterns
 /// e.g., creating a basic block, calling a function, etc.
@@ -55,7 +62,7 @@ impl<'a, 'tcx> TerminatorCodegenHelper<'tcx> {
         let target_funclet = fx.cleanup_kinds[target].funclet_bb(target);
         match (self.funclet_bb, target_funclet) {
             (None, None) => (lltarget, false),
-            (Some(f), Some(t_f)) if f == t_f || !base::wants_msvc_seh(fx.cx.tcx().sess) => {
+            (Some(f), Some(t_f)) if f == t_f || !base::wants_funclets(fx.cx.tcx().sess) => {
                 (lltarget, false)
             }
             // jump *into* cleanup - need a landing pad if GNU
@@ -103,6 +110,7 @@ impl<'a, 'tcx> TerminatorCodegenHelper<'tcx> {
 
     /// Call `fn_ptr` of `fn_abi` with the arguments `llargs`, the optional
     /// return destination `destination` and the cleanup function `cleanup`.
+    /// `callsite_attrs` are added to the emitted call or invoke.
     fn do_call<Bx: BuilderMethods<'a, 'tcx>>(
         &self,
         fx: &mut FunctionCx<'a, 'tcx, Bx>,
@@ -112,18 +120,32 @@ impl<'a, 'tcx> TerminatorCodegenHelper<'tcx> {
         llargs: &[Bx::Value],
         destination: Option<(ReturnDest<'tcx, Bx::Value>, mir::BasicBlock)>,
         cleanup: Option<mir::BasicBlock>,
+        callsite_attrs: &[CallSiteAttr],
     ) {
         // If there is a cleanup block and the function we're calling can unwind, then
-        // do an invoke, otherwise do a call.
-        if let Some(cleanup) = cleanup.filter(|_| fn_abi.can_unwind) {
+        // do an invoke, otherwise do a call. Targets that cannot unwind never invoke.
+        let can_unwind = fn_abi.can_unwind && fx.cx.sess().eh_strategy() != EhStrategy::None;
+        let operand_bundle = fx.cx.tcx().codegen_fn_attrs(fx.instance.def_id()).operand_bundle;
+        if let Some(cleanup) = cleanup.filter(|_| can_unwind) {
             let ret_bx = if let Some((_, target)) = destination {
                 fx.blocks[target]
             } else {
                 fx.unreachable_block()
             };
-            let invokeret =
-                bx.invoke(fn_ptr, &llargs, ret_bx, self.llblock(fx, cleanup), self.funclet(fx));
+            let catch = self.llblock(fx, cleanup);
+            let invokeret = match operand_bundle {
+                Some(tag) => bx.invoke_with_operand_bundle(
+                    fn_ptr,
+                    &llargs,
+                    ret_bx,
+                    catch,
+                    self.funclet(fx),
+                    &tag.as_str(),
+                ),
+                None => bx.invoke(fn_ptr, &llargs, ret_bx, catch, self.funclet(fx)),
+            };
             bx.apply_attrs_callsite(&fn_abi, invokeret);
+            bx.apply_callsite_attrs(invokeret, callsite_attrs);
 
             if let Some((ret_dest, target)) = destination {
                 let mut ret_bx = fx.build_block(target);
@@ -131,14 +153,20 @@ impl<'a, 'tcx> TerminatorCodegenHelper<'tcx> {
                 fx.store_return(&mut ret_bx, ret_dest, &fn_abi.ret, invokeret);
             }
         } else {
-            let llret = bx.call(fn_ptr, &llargs, self.funclet(fx));
+            let llret = match operand_bundle {
+                Some(tag) => {
+                    bx.call_with_operand_bundle(fn_ptr, &llargs, self.funclet(fx), &tag.as_str())
+                }
+                None => bx.call(fn_ptr, &llargs, self.funclet(fx)),
+            };
             bx.apply_attrs_callsite(&fn_abi, llret);
+            bx.apply_callsite_attrs(llret, callsite_attrs);
             if fx.mir[self.bb].is_cleanup {
                 // Cleanup is always the cold path. Don't inline
                 // drop glue. Also, when there is a deeply-nested
                 // struct, there are "symmetry" issues that cause
                 // exponential inlining - see issue #41696.
-                bx.do_not_inline(llret);
+                bx.apply_callsite_attrs(llret, &[CallSiteAttr::NoInline]);
             }
 
             if let Some((ret_dest, target)) = destination {
@@ -263,7 +291,11 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             PassMode::Direct(_) | PassMode::Pair(..) => {
                 let op = self.codegen_consume(&mut bx, mir::Place::return_place().as_ref());
                 if let Ref(llval, _, align) = op.val {
-                    bx.load(llval, align)
+                    let load = bx.load(llval, align);
+                    if let abi::Abi::Scalar(ref scalar) = op.layout.abi {
+                        bx.scalar_load_metadata(load, scalar, op.layout, Size::ZERO);
+                    }
+                    load
                 } else {
                     op.immediate_or_packed_pair(&mut bx)
                 }
@@ -336,7 +368,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 let fn_abi = FnAbi::of_instance(&bx, virtual_drop, &[]);
                 let vtable = args[1];
                 args = &args[..1];
-                (meth::DESTRUCTOR.get_fn(&mut bx, vtable, &fn_abi), fn_abi)
+                (meth::DESTRUCTOR.get_fn(&mut bx, vtable, ty, &fn_abi), fn_abi)
             }
             _ => (bx.get_fn_addr(drop_fn), FnAbi::of_instance(&bx, drop_fn, &[])),
         };
@@ -349,6 +381,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             args,
             Some((ReturnDest::Nothing, target)),
             unwind,
+            &[],
         );
     }
 
@@ -404,6 +437,33 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         bx = panic_block;
         self.set_debug_loc(&mut bx, terminator.source_info);
 
+        // With `-Z overflow-trap=abort`, failed overflow checks don't go
+        // through the panic machinery at all.
+        if let AssertKind::Overflow(..) | AssertKind::OverflowNeg(_) = *msg {
+            if bx.tcx().sess.opts.debugging_opts.overflow_trap == OverflowTrap::Abort {
+                bx.abort();
+                bx.unreachable();
+                return;
+            }
+        }
+
+        // Apart from bounds checks, the panic arguments are all constants, so
+        // with `-Z outline-panic-stubs` the whole call can be moved into a stub
+        // shared by every assertion with the same message and location.
+        let stub_location_span = match msg {
+            AssertKind::BoundsCheck { .. } => None,
+            _ if bx.tcx().sess.opts.debugging_opts.outline_panic_stubs => {
+                self.caller_location_span(terminator.source_info)
+            }
+            _ => None,
+        };
+        if let Some(location_span) = stub_location_span {
+            let msg_str = Symbol::intern(msg.description());
+            let (fn_abi, llfn) = self.get_panic_stub(&mut bx, msg_str, location_span);
+            helper.do_call(self, &mut bx, fn_abi, llfn, &[], None, cleanup, PANIC_CALLSITE_ATTRS);
+            return;
+        }
+
         // Get the location information.
         let location = self.get_caller_location(&mut bx, terminator.source_info).immediate();
 
@@ -432,7 +492,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         let llfn = bx.get_fn_addr(instance);
 
         // Codegen the actual panic invoke/call.
-        helper.do_call(self, &mut bx, fn_abi, llfn, &args, None, cleanup);
+        helper.do_call(self, &mut bx, fn_abi, llfn, &args, None, cleanup, PANIC_CALLSITE_ATTRS);
     }
 
     /// Returns `true` if this is indeed a panic intrinsic and codegen is done.
@@ -506,6 +566,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     &[msg.0, msg.1, location],
                     destination.as_ref().map(|(_, bb)| (ReturnDest::Nothing, *bb)),
                     cleanup,
+                    PANIC_CALLSITE_ATTRS,
                 );
             } else {
                 // a NOP
@@ -571,12 +632,14 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             _ => None,
         };
 
-        let extra_args = &args[sig.inputs().skip_binder().len()..];
+        let first_extra_arg = sig.inputs().skip_binder().len();
+        let extra_args = &args[first_extra_arg..];
         let extra_args = extra_args
             .iter()
             .map(|op_arg| {
                 let op_ty = op_arg.ty(self.mir, bx.tcx());
-                self.monomorphize(&op_ty)
+                let op_ty = self.monomorphize(&op_ty);
+                Self::c_variadic_promoted_ty(&bx, op_ty).unwrap_or(op_ty)
             })
             .collect::<Vec<_>>();
 
@@ -691,6 +754,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 &args,
                 dest,
                 span,
+                helper.funclet(self),
             );
 
             if let ReturnDest::IndirectOperand(dst, _) = ret_dest {
@@ -719,6 +783,9 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             let mut op = self.codegen_operand(&mut bx, arg);
 
             if let (0, Some(ty::InstanceDef::Virtual(_, idx))) = (i, def) {
+                // Virtual instances are never polymorphized, so `Self` is the
+                // trait object type.
+                let dyn_ty = instance.unwrap().substs.type_at(0);
                 if let Pair(..) = op.val {
                     // In the case of Rc<Self>, we need to explicitly pass a
                     // *mut RcBox<Self> with a Scalar (not ScalarPair) ABI. This is a hack
@@ -749,7 +816,8 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     match op.val {
                         Pair(data_ptr, meta) => {
                             llfn = Some(
-                                meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, &fn_abi),
+                                meth::VirtualIndex::from_index(idx)
+                                    .get_fn(&mut bx, meta, dyn_ty, &fn_abi),
                             );
                             llargs.push(data_ptr);
                             continue 'make_args;
@@ -758,7 +826,9 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     }
                 } else if let Ref(data_ptr, Some(meta), _) = op.val {
                     // by-value dynamic dispatch
-                    llfn = Some(meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, &fn_abi));
+                    llfn = Some(
+                        meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, dyn_ty, &fn_abi),
+                    );
                     llargs.push(data_ptr);
                     continue;
                 } else {
@@ -778,6 +848,10 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 _ => {}
             }
 
+            if i >= first_extra_arg {
+                op = Self::promote_c_variadic_arg(&mut bx, op, fn_abi.args[i].layout);
+            }
+
             self.codegen_argument(&mut bx, op, &mut llargs, &fn_abi.args[i]);
         }
         if let Some(tup) = untuple {
@@ -817,6 +891,30 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         if let Some((_, target)) = destination.as_ref() {
             helper.maybe_sideeffect(self.mir, &mut bx, &[*target]);
         }
+
+        if self.is_must_tail_call(&helper, destination) {
+            match self.must_tail_mismatch(&fn_abi, cleanup) {
+                None => {
+                    let llret = bx.call(fn_ptr, &llargs, helper.funclet(self));
+                    bx.apply_attrs_callsite(&fn_abi, llret);
+                    bx.set_tail_call_kind(llret, TailCallKind::MustTail);
+                    // The matching ABIs make the callee's return value, or the
+                    // `sret` pointer it was given, ours as well.
+                    match fn_abi.ret.mode {
+                        PassMode::Ignore | PassMode::Indirect(..) => bx.ret_void(),
+                        PassMode::Direct(_) | PassMode::Pair(..) | PassMode::Cast(_) => {
+                            bx.ret(llret)
+                        }
+                    }
+                    return;
+                }
+                Some(reason) => {
+                    let msg = format!("cannot emit a guaranteed tail call: {}", reason);
+                    bx.tcx().sess.span_err(span, &msg);
+                }
+            }
+        }
+
         helper.do_call(
             self,
             &mut bx,
@@ -825,9 +923,72 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             &llargs,
             destination.as_ref().map(|&(_, target)| (ret_dest, target)),
             cleanup,
+            &[],
         );
     }
 
+    /// Returns whether the call in `helper`'s block is in tail position in a
+    /// `#[rustc_must_tail]` function: its result goes straight to the return
+    /// place, and its target block returns without doing anything else.
+    fn is_must_tail_call(
+        &self,
+        helper: &TerminatorCodegenHelper<'tcx>,
+        destination: &Option<(mir::Place<'tcx>, mir::BasicBlock)>,
+    ) -> bool {
+        let attrs = self.cx.tcx().codegen_fn_attrs(self.instance.def_id());
+        if !attrs.flags.contains(CodegenFnAttrFlags::MUST_TAIL) || self.mir[helper.bb].is_cleanup {
+            return false;
+        }
+        let (dest, target) = match *destination {
+            Some(destination) => destination,
+            None => return false,
+        };
+        let target = &self.mir[target];
+        dest.as_local() == Some(mir::RETURN_PLACE)
+            && target.statements.iter().all(|statement| {
+                matches!(
+                    statement.kind,
+                    mir::StatementKind::StorageLive(_)
+                        | mir::StatementKind::StorageDead(_)
+                        | mir::StatementKind::Nop
+                )
+            })
+            && matches!(target.terminator().kind, mir::TerminatorKind::Return)
+    }
+
+    /// Returns why a call with `fn_abi` can't be a guaranteed tail call of the
+    /// current function, if it can't. LLVM's `musttail` needs the caller and
+    /// callee ABIs to match, and the caller's frame is gone once the callee
+    /// runs, so nothing may be passed by a pointer into it or need dropping
+    /// on unwind.
+    fn must_tail_mismatch(
+        &self,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        cleanup: Option<mir::BasicBlock>,
+    ) -> Option<&'static str> {
+        let same_abi = |a: &ArgAbi<'tcx, Ty<'tcx>>, b: &ArgAbi<'tcx, Ty<'tcx>>| {
+            a.mode == b.mode && a.layout.size == b.layout.size && a.layout.align == b.layout.align
+        };
+        if fn_abi.c_variadic || self.fn_abi.c_variadic {
+            Some("C-variadic functions can't be tail called")
+        } else if fn_abi.conv != self.fn_abi.conv
+            || fn_abi.args.len() != self.fn_abi.args.len()
+            || !same_abi(&fn_abi.ret, &self.fn_abi.ret)
+            || !fn_abi.args.iter().zip(&self.fn_abi.args).all(|(a, b)| same_abi(a, b))
+        {
+            Some("the callee's ABI doesn't match the caller's")
+        } else if fn_abi.args.iter().any(|arg| arg.is_indirect()) {
+            Some("arguments passed indirectly would point into the caller's frame")
+        } else if cleanup.is_some()
+            && fn_abi.can_unwind
+            && self.cx.sess().eh_strategy() != EhStrategy::None
+        {
+            Some("the caller has values to drop if the callee unwinds")
+        } else {
+            None
+        }
+    }
+
     fn codegen_asm_terminator(
         &mut self,
         helper: TerminatorCodegenHelper<'tcx>,
@@ -874,26 +1035,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                             ),
                         };
                         let value = scalar.assert_bits(size);
-                        let string = match ty.kind() {
-                            ty::Uint(_) => value.to_string(),
-                            ty::Int(int_ty) => {
-                                match int_ty.normalize(bx.tcx().sess.target.pointer_width) {
-                                    ast::IntTy::I8 => (value as i8).to_string(),
-                                    ast::IntTy::I16 => (value as i16).to_string(),
-                                    ast::IntTy::I32 => (value as i32).to_string(),
-                                    ast::IntTy::I64 => (value as i64).to_string(),
-                                    ast::IntTy::I128 => (value as i128).to_string(),
-                                    ast::IntTy::Isize => unreachable!(),
-                                }
-                            }
-                            ty::Float(ast::FloatTy::F32) => {
-                                f32::from_bits(value as u32).to_string()
-                            }
-                            ty::Float(ast::FloatTy::F64) => {
-                                f64::from_bits(value as u64).to_string()
-                            }
-                            _ => span_bug!(span, "asm const has bad type {}", ty),
-                        };
+                        let string = asm_const_to_str(bx.tcx(), span, value, ty);
                         InlineAsmOperandRef::Const { string }
                     } else {
                         span_bug!(span, "asm const is not a constant");
@@ -938,6 +1080,8 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
 
         debug!("codegen_block({:?}={:?})", bb, data);
 
+        self.discriminants.clear();
+        self.funclet_bb = self.cleanup_kinds[bb].funclet_bb(bb);
         for statement in &data.statements {
             bx = self.codegen_statement(bx, statement);
         }
@@ -1062,6 +1206,96 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         }
     }
 
+    /// Returns the type that a value of type `ty` passed through the `...` of
+    /// a C-variadic function is promoted to by C's default argument
+    /// promotions: `float` is passed as `double`, and integers narrower than
+    /// `int` as `int` or `unsigned int`.
+    ///
+    /// Typeck rejects the primitive types this applies to, but newtypes and
+    /// enums around them get through and would otherwise be passed in a form
+    /// the callee's `va_arg` does not expect. Other aggregates are never
+    /// promoted, even if they have a scalar ABI, as C doesn't promote structs.
+    fn c_variadic_promoted_ty(bx: &Bx, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        let tcx = bx.tcx();
+        if !Self::is_c_variadic_promotable(bx, ty) {
+            return None;
+        }
+        let layout = bx.layout_of(ty);
+        let scalar = match layout.abi {
+            abi::Abi::Scalar(ref scalar) => scalar,
+            _ => return None,
+        };
+        match scalar.value {
+            abi::F32 => Some(tcx.types.f64),
+            abi::Int(int, signed) => {
+                let (c_int, c_uint, c_int_bits) = match &tcx.sess.target.target_c_int_width[..] {
+                    "16" => (tcx.types.i16, tcx.types.u16, 16),
+                    "32" => (tcx.types.i32, tcx.types.u32, 32),
+                    "64" => (tcx.types.i64, tcx.types.u64, 64),
+                    width => bug!("Unsupported target_c_int_width: {}", width),
+                };
+                if int.size().bits() >= c_int_bits {
+                    None
+                } else if signed {
+                    Some(c_int)
+                } else {
+                    Some(c_uint)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether `ty` is passed like a C arithmetic type: a primitive
+    /// integer, float or `bool`, a fieldless enum, or a `repr(transparent)`
+    /// wrapper around one of those.
+    fn is_c_variadic_promotable(bx: &Bx, ty: Ty<'tcx>) -> bool {
+        match *ty.kind() {
+            ty::Int(_) | ty::Uint(_) | ty::Float(_) | ty::Bool => true,
+            ty::Adt(def, _) if def.is_enum() => def.is_payloadfree(),
+            ty::Adt(def, substs) if def.repr.transparent() => def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .map(|field| {
+                    let field_ty = field.ty(bx.tcx(), substs);
+                    bx.tcx().normalize_erasing_regions(ty::ParamEnv::reveal_all(), field_ty)
+                })
+                .find(|&field_ty| !bx.layout_of(field_ty).is_zst())
+                .map_or(false, |field_ty| Self::is_c_variadic_promotable(bx, field_ty)),
+            _ => false,
+        }
+    }
+
+    /// Converts `op` to `promoted`, the type computed for it by
+    /// `c_variadic_promoted_ty`.
+    fn promote_c_variadic_arg(
+        bx: &mut Bx,
+        op: OperandRef<'tcx, Bx::Value>,
+        promoted: TyAndLayout<'tcx>,
+    ) -> OperandRef<'tcx, Bx::Value> {
+        if op.layout.ty == promoted.ty {
+            return op;
+        }
+
+        let llval = match op.val {
+            Immediate(llval) => llval,
+            Ref(llptr, None, align) => {
+                bx.load_operand(PlaceRef::new_sized_aligned(llptr, op.layout, align)).immediate()
+            }
+            _ => bug!("unexpected C-variadic argument {:?}", op),
+        };
+        let llty = bx.cx().immediate_backend_type(promoted);
+        let llval = match promoted.abi {
+            abi::Abi::Scalar(abi::Scalar { value: abi::F64, .. }) => bx.fpext(llval, llty),
+            abi::Abi::Scalar(abi::Scalar { value: abi::Int(_, signed), .. }) => {
+                bx.intcast(llval, llty, signed)
+            }
+            _ => bug!("unexpected promoted C-variadic argument type {}", promoted.ty),
+        };
+        OperandRef { val: Immediate(llval), layout: promoted }
+    }
+
     fn codegen_argument(
         &mut self,
         bx: &mut Bx,
@@ -1099,10 +1333,17 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         }
 
         // Force by-ref if we have to load through a cast pointer.
+        let mut cast_scratch = None;
         let (mut llval, align, by_ref) = match op.val {
             Immediate(_) | Pair(..) => match arg.mode {
                 PassMode::Indirect(..) | PassMode::Cast(_) => {
                     let scratch = PlaceRef::alloca(bx, arg.layout);
+                    if let PassMode::Cast(_) = arg.mode {
+                        // The scratch slot is only needed until it is reloaded
+                        // as the cast type below, so let LLVM reuse it.
+                        scratch.storage_live(bx);
+                        cast_scratch = Some(scratch);
+                    }
                     op.val.store(bx, scratch);
                     (scratch.llval, scratch.align, true)
                 }
@@ -1136,6 +1377,9 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             if let PassMode::Cast(ty) = arg.mode {
                 let addr = bx.pointercast(llval, bx.type_ptr_to(bx.cast_backend_type(&ty)));
                 llval = bx.load(addr, align.min(arg.layout.align.abi));
+                if let Some(scratch) = cast_scratch {
+                    scratch.storage_dead(bx);
+                }
             } else {
                 // We can't use `PlaceRef::load` here because the argument
                 // may have a type we don't treat as immediate, but the ABI
@@ -1144,9 +1388,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 // of the `OperandValue::Immediate` we need for the call.
                 llval = bx.load(llval, align);
                 if let abi::Abi::Scalar(ref scalar) = arg.layout.abi {
-                    if scalar.is_bool() {
-                        bx.range_metadata(llval, 0..2);
-                    }
+                    bx.scalar_load_metadata(llval, scalar, arg.layout, Size::ZERO);
                 }
                 // We store bools as `i8` so we need to truncate to `i1`.
                 llval = bx.to_immediate(llval, arg.layout);
@@ -1187,20 +1429,19 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
     fn get_caller_location(
         &mut self,
         bx: &mut Bx,
-        mut source_info: mir::SourceInfo,
+        source_info: mir::SourceInfo,
     ) -> OperandRef<'tcx, Bx::Value> {
-        let tcx = bx.tcx();
+        match self.caller_location_span(source_info) {
+            Some(span) => span_to_caller_location(bx, span),
+            None => self.caller_location.unwrap(),
+        }
+    }
 
-        let mut span_to_caller_location = |span: Span| {
-            let topmost = span.ctxt().outer_expn().expansion_cause().unwrap_or(span);
-            let caller = tcx.sess.source_map().lookup_char_pos(topmost.lo());
-            let const_loc = tcx.const_caller_location((
-                Symbol::intern(&caller.file.name.to_string()),
-                caller.line as u32,
-                caller.col_display as u32 + 1,
-            ));
-            OperandRef::from_const(bx, const_loc, bx.tcx().caller_location_ty())
-        };
+    /// Returns the span whose location `get_caller_location` reports for
+    /// `source_info`, or `None` if it reports this function's own implicit
+    /// `#[track_caller]` argument instead.
+    fn caller_location_span(&self, mut source_info: mir::SourceInfo) -> Option<Span> {
+        let tcx = self.cx.tcx();
 
         // Walk up the `SourceScope`s, in case some of them are from MIR inlining.
         // If so, the starting `source_info.span` is in the innermost inlined
@@ -1213,7 +1454,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 // Stop inside the most nested non-`#[track_caller]` function,
                 // before ever reaching its caller (which is irrelevant).
                 if !callee.def.requires_caller_location(tcx) {
-                    return span_to_caller_location(source_info.span);
+                    return Some(source_info.span);
                 }
                 source_info.span = callsite_span;
             }
@@ -1226,7 +1467,49 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         }
 
         // No inlined `SourceScope`s, or all of them were `#[track_caller]`.
-        self.caller_location.unwrap_or_else(|| span_to_caller_location(source_info.span))
+        match self.caller_location {
+            Some(_) => None,
+            None => Some(source_info.span),
+        }
+    }
+
+    /// Returns the shared stub that panics with `msg` at the location of `span`,
+    /// defining it in the current codegen unit the first time it is requested.
+    fn get_panic_stub(
+        &self,
+        bx: &mut Bx,
+        msg: Symbol,
+        span: Span,
+    ) -> (FnAbi<'tcx, Ty<'tcx>>, Bx::Value) {
+        let tcx = bx.tcx();
+        let sig =
+            tcx.mk_fn_sig(iter::empty(), tcx.types.never, false, hir::Unsafety::Normal, Abi::Rust);
+        let fn_abi = FnAbi::of_fn_ptr(bx, ty::Binder::dummy(sig), &[]);
+
+        if let Some(&llfn) = self.cx.panic_stubs().borrow().get(&(msg, span)) {
+            return (fn_abi, llfn);
+        }
+
+        // The arguments are all constants, so they can be built from `bx`
+        // even though the call is made from the stub.
+        let location = span_to_caller_location(bx, span).immediate();
+        let msg_str = bx.const_str(msg);
+
+        // It's `pub fn panic(expr: &str)`, with the wide reference being passed
+        // as two arguments, and `#[track_caller]` adds an implicit third argument.
+        let def_id = common::langcall(tcx, Some(span), "", LangItem::Panic);
+        let instance = ty::Instance::mono(tcx, def_id);
+        let panic_fn_abi = FnAbi::of_instance(bx, instance, &[]);
+        let llpanic = bx.get_fn_addr(instance);
+        let llfn = self.cx.define_panic_stub(
+            &fn_abi,
+            llpanic,
+            &panic_fn_abi,
+            &[msg_str.0, msg_str.1, location],
+        );
+
+        self.cx.panic_stubs().borrow_mut().insert((msg, span), llfn);
+        (fn_abi, llfn)
     }
 
     fn get_personality_slot(&mut self, bx: &mut Bx) -> PlaceRef<'tcx, Bx::Value> {
@@ -1258,7 +1541,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
     }
 
     fn landing_pad_uncached(&mut self, target_bb: Bx::BasicBlock) -> Bx::BasicBlock {
-        if base::wants_msvc_seh(self.cx.sess()) {
+        if base::wants_funclets(self.cx.sess()) {
             span_bug!(self.mir.span, "landing pad was not inserted?")
         }
 
@@ -1442,6 +1725,23 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
     }
 }
 
+/// Builds the constant `&'static Location<'static>` that `#[track_caller]`
+/// functions observe for a call at `span`.
+fn span_to_caller_location<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
+    bx: &mut Bx,
+    span: Span,
+) -> OperandRef<'tcx, Bx::Value> {
+    let tcx = bx.tcx();
+    let topmost = span.ctxt().outer_expn().expansion_cause().unwrap_or(span);
+    let caller = tcx.sess.source_map().lookup_char_pos(topmost.lo());
+    let const_loc = tcx.const_caller_location((
+        Symbol::intern(&caller.file.name.to_string()),
+        caller.line as u32,
+        caller.col_display as u32 + 1,
+    ));
+    OperandRef::from_const(bx, const_loc, tcx.caller_location_ty())
+}
+
 enum ReturnDest<'tcx, V> {
     // Do nothing; the return value is indirect or ignored.
     Nothing,
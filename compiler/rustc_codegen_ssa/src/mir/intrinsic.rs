@@ -8,7 +8,8 @@ use crate::MemFlags;
 
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::{sym, Span};
-use rustc_target::abi::call::{FnAbi, PassMode};
+use rustc_target::abi::call::{FnAbi, PassMode, Reg, RegKind};
+use rustc_target::abi::{Abi, Align, HasDataLayout};
 
 fn copy_intrinsic<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     bx: &mut Bx,
@@ -55,6 +56,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         args: &[OperandRef<'tcx, Bx::Value>],
         llresult: Bx::Value,
         span: Span,
+        funclet: Option<&Bx::Funclet>,
     ) {
         let callee_ty = instance.ty(bx.tcx(), ty::ParamEnv::reveal_all());
 
@@ -205,6 +207,38 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 );
                 return;
             }
+            sym::unordered_atomic_copy_memory | sym::unordered_atomic_set_memory => {
+                let ty = substs.type_at(0);
+                let layout = bx.layout_of(ty);
+                let (size, align) = (layout.size, layout.align.abi);
+                if !size.bytes().is_power_of_two()
+                    || size.bits() > bx.tcx().sess.target.max_atomic_width()
+                    || size.bytes() > align.bytes()
+                {
+                    span_invalid_monomorphization_error(
+                        bx.tcx().sess,
+                        span,
+                        &format!(
+                            "invalid monomorphization of `{}` intrinsic: `{}` with size {} and \
+                             alignment {} cannot be accessed with an unordered atomic",
+                            name,
+                            ty,
+                            size.bytes(),
+                            align.bytes()
+                        ),
+                    );
+                    return;
+                }
+                let total = bx.mul(bx.const_usize(size.bytes()), args[2].immediate());
+                if name == sym::unordered_atomic_copy_memory {
+                    let (dst, src) = (args[0].immediate(), args[1].immediate());
+                    bx.atomic_element_memcpy(dst, align, src, align, total, size);
+                } else {
+                    let (dst, val) = (args[0].immediate(), args[1].immediate());
+                    bx.atomic_element_memset(dst, val, total, align, size);
+                }
+                return;
+            }
             sym::volatile_store => {
                 let dst = args[0].deref(bx.cx());
                 args[1].val.volatile_store(bx, dst);
@@ -228,7 +262,12 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             | sym::unchecked_add
             | sym::unchecked_sub
             | sym::unchecked_mul
-            | sym::exact_div => {
+            | sym::exact_div
+            | sym::ctlz
+            | sym::ctlz_nonzero
+            | sym::cttz
+            | sym::cttz_nonzero
+            | sym::bitreverse => {
                 let ty = arg_tys[0];
                 match int_type_width_signed(ty, bx.tcx()) {
                     Some((_width, signed)) => match name {
@@ -241,8 +280,13 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                                 sym::mul_with_overflow => OverflowOp::Mul,
                                 _ => bug!(),
                             };
-                            let (val, overflow) =
-                                bx.checked_binop(op, ty, args[0].immediate(), args[1].immediate());
+                            let (val, overflow) = bx.checked_binop(
+                                op,
+                                ty,
+                                args[0].immediate(),
+                                args[1].immediate(),
+                                funclet,
+                            );
                             // Convert `i1` to a `bool`, and write it to the out parameter
                             let val = bx.from_immediate(val);
                             let overflow = bx.from_immediate(overflow);
@@ -307,6 +351,11 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                                 bx.unchecked_umul(args[0].immediate(), args[1].immediate())
                             }
                         }
+                        sym::ctlz => bx.ctlz(args[0].immediate(), false),
+                        sym::ctlz_nonzero => bx.ctlz(args[0].immediate(), true),
+                        sym::cttz => bx.cttz(args[0].immediate(), false),
+                        sym::cttz_nonzero => bx.cttz(args[0].immediate(), true),
+                        sym::bitreverse => bx.bitreverse(args[0].immediate()),
                         _ => bug!(),
                     },
                     None => {
@@ -347,6 +396,8 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     }
                 }
             }
+            sym::minnumf32 | sym::minnumf64 => bx.minnum(args[0].immediate(), args[1].immediate()),
+            sym::maxnumf32 | sym::maxnumf64 => bx.maxnum(args[0].immediate(), args[1].immediate()),
 
             sym::float_to_int_unchecked => {
                 if float_type_width(arg_tys[0]).is_none() {
@@ -465,8 +516,10 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     "load" => {
                         let ty = substs.type_at(0);
                         if int_type_width_signed(ty, bx.tcx()).is_some() {
-                            let size = bx.layout_of(ty).size;
-                            bx.atomic_load(args[0].immediate(), order, size)
+                            // LLVM requires the alignment of atomic loads to be at least
+                            // the size of the type.
+                            let align = Align::from_bytes(bx.layout_of(ty).size.bytes()).unwrap();
+                            bx.atomic_load(args[0].immediate(), order, align)
                         } else {
                             return invalid_monomorphization(ty);
                         }
@@ -475,8 +528,10 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     "store" => {
                         let ty = substs.type_at(0);
                         if int_type_width_signed(ty, bx.tcx()).is_some() {
-                            let size = bx.layout_of(ty).size;
-                            bx.atomic_store(args[1].immediate(), args[0].immediate(), order, size);
+                            // LLVM requires the alignment of atomic stores to be at least
+                            // the size of the type.
+                            let align = Align::from_bytes(bx.layout_of(ty).size.bytes()).unwrap();
+                            bx.atomic_store(args[1].immediate(), args[0].immediate(), order, align);
                             return;
                         } else {
                             return invalid_monomorphization(ty);
@@ -536,6 +591,48 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 }
             }
 
+            sym::raw_eq => {
+                let tp_ty = substs.type_at(0);
+                let layout = bx.layout_of(tp_ty).layout;
+                let use_integer_compare = match layout.abi {
+                    Abi::Scalar(_) | Abi::ScalarPair(_, _) => true,
+                    Abi::Uninhabited | Abi::Vector { .. } => false,
+                    Abi::Aggregate { .. } => {
+                        // Small aggregates are passed around as integers by the
+                        // Rust ABI (see `FnAbi::adjust_for_abi`), so the same
+                        // threshold keeps this to one or two register loads.
+                        layout.size <= bx.cx().data_layout().pointer_size * 2
+                    }
+                };
+
+                let a = args[0].immediate();
+                let b = args[1].immediate();
+                if layout.size.bytes() == 0 {
+                    bx.const_bool(true)
+                } else if use_integer_compare {
+                    let reg = Reg { kind: RegKind::Integer, size: layout.size };
+                    let integer_ty = bx.reg_backend_type(&reg);
+                    let ptr_ty = bx.type_ptr_to(integer_ty);
+                    let a_ptr = bx.bitcast(a, ptr_ty);
+                    let a_val = bx.load(a_ptr, layout.align.abi);
+                    let b_ptr = bx.bitcast(b, ptr_ty);
+                    let b_val = bx.load(b_ptr, layout.align.abi);
+                    bx.icmp(IntPredicate::IntEQ, a_val, b_val)
+                } else {
+                    let n = bx.const_usize(layout.size.bytes());
+                    let cmp = bx.bcmp(a, b, n);
+                    bx.icmp(IntPredicate::IntEQ, cmp, bx.const_i32(0))
+                }
+            }
+
+            sym::raw_eq_bytes => {
+                let a = args[0].immediate();
+                let b = args[1].immediate();
+                let n = args[2].immediate();
+                let cmp = bx.bcmp(a, b, n);
+                bx.icmp(IntPredicate::IntEQ, cmp, bx.const_i32(0))
+            }
+
             sym::ptr_offset_from => {
                 let ty = substs.type_at(0);
                 let pointee_size = bx.layout_of(ty).size;
@@ -1,12 +1,15 @@
 use crate::base;
 use crate::traits::*;
+use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::ErrorReported;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::ErrorHandled;
 use rustc_middle::ty::layout::{FnAbiExt, HasTyCtxt, TyAndLayout};
 use rustc_middle::ty::{self, Instance, Ty, TypeFoldable};
 use rustc_target::abi::call::{FnAbi, PassMode};
 use rustc_target::abi::HasDataLayout;
+use rustc_target::spec::PanicStrategy;
 
 use std::iter;
 
@@ -84,6 +87,21 @@ pub struct FunctionCx<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> {
 
     /// Caller location propagated if this function has `#[track_caller]`.
     caller_location: Option<OperandRef<'tcx, Bx::Value>>,
+
+    /// Discriminants already read in the current basic block, keyed by the
+    /// place they were read from and the type they were cast to. Cleared
+    /// whenever a statement may write to memory, and at the start of every
+    /// block, so that e.g. nested `match`es on the same enum only decode its
+    /// tag once.
+    discriminants: FxHashMap<(mir::Place<'tcx>, Ty<'tcx>), Bx::Value>,
+
+    /// The funclet of the basic block whose statements are being codegened,
+    /// for the library calls that statements may lower to.
+    funclet_bb: Option<mir::BasicBlock>,
+
+    /// The alias scopes of the memory behind reference-typed locals, attached
+    /// to loads and stores through them.
+    alias_scopes: Option<analyze::AliasScopes<Bx::Value>>,
 }
 
 impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
@@ -149,11 +167,24 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
 
     let mut bx = Bx::new_block(cx, llfn, "start");
 
-    if mir.basic_blocks().iter().any(|bb| bb.is_cleanup) {
+    // A naked function is a single `asm!` block (see `rustc_passes::naked_functions`),
+    // so it must not get any code of ours: no personality, no stack slots, no
+    // copies of its arguments.
+    let naked =
+        cx.tcx().codegen_fn_attrs(instance.def_id()).flags.contains(CodegenFnAttrFlags::NAKED);
+
+    // With `-C panic=abort` no call has an unwind edge, so cleanup blocks are
+    // unreachable and never lowered, and there's no need for a personality.
+    if !naked
+        && cx.sess().panic_strategy() == PanicStrategy::Unwind
+        && mir.basic_blocks().iter().any(|bb| bb.is_cleanup)
+    {
         bx.set_personality_fn(cx.eh_personality());
     }
 
-    bx.sideeffect(false);
+    if !naked {
+        bx.sideeffect(false);
+    }
 
     let cleanup_kinds = analyze::cleanup_kinds(&mir);
     // Allocate a `Block` for every basic block, except
@@ -188,9 +219,13 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         debug_context,
         per_local_var_debug_info: None,
         caller_location: None,
+        discriminants: Default::default(),
+        funclet_bb: None,
+        alias_scopes: None,
     };
 
     fx.per_local_var_debug_info = fx.compute_per_local_var_debug_info();
+    fx.alias_scopes = Some(analyze::alias_scopes(&fx, &mut bx));
 
     for const_ in &mir.required_consts {
         if let Err(err) = fx.eval_mir_constant(const_) {
@@ -207,7 +242,16 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     let memory_locals = analyze::non_ssa_locals(&fx);
 
     // Allocate variable and temp allocas
-    fx.locals = {
+    fx.locals = if naked {
+        // The body can't refer to any local, so none of them needs a value.
+        mir.local_decls
+            .iter()
+            .map(|decl| {
+                let layout = bx.layout_of(fx.monomorphize(&decl.ty));
+                LocalRef::new_operand(&mut bx, layout)
+            })
+            .collect()
+    } else {
         let args = arg_local_refs(&mut bx, &mut fx, &memory_locals);
 
         let mut allocate_local = |local| {
@@ -285,7 +329,7 @@ fn create_funclets<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
         .zip(cleanup_kinds)
         .map(|((bb, &llbb), cleanup_kind)| {
             match *cleanup_kind {
-                CleanupKind::Funclet if base::wants_msvc_seh(bx.sess()) => {}
+                CleanupKind::Funclet if base::wants_funclets(bx.sess()) => {}
                 _ => return (None, None),
             }
 
@@ -323,12 +367,17 @@ fn create_funclets<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
                     // The "null" here is actually a RTTI type descriptor for the
                     // C++ personality function, but `catch (...)` has no type so
                     // it's null. The 64 here is actually a bitfield which
-                    // represents that this is a catch-all block.
+                    // represents that this is a catch-all block. Wasm's catch-all
+                    // only takes the null type descriptor.
                     let null = bx.const_null(
                         bx.type_i8p_ext(bx.cx().data_layout().instruction_address_space),
                     );
-                    let sixty_four = bx.const_i32(64);
-                    funclet = cp_bx.catch_pad(cs, &[null, sixty_four, null]);
+                    funclet = if base::wants_msvc_seh(bx.sess()) {
+                        let sixty_four = bx.const_i32(64);
+                        cp_bx.catch_pad(cs, &[null, sixty_four, null])
+                    } else {
+                        cp_bx.catch_pad(cs, &[null])
+                    };
                     cp_bx.br(llbb);
                 }
                 _ => {
@@ -487,6 +536,7 @@ pub mod constant;
 pub mod coverageinfo;
 pub mod debuginfo;
 mod intrinsic;
+pub mod naked_asm;
 pub mod operand;
 pub mod place;
 mod rvalue;
@@ -0,0 +1,186 @@
+//! Emission of `#[naked]` functions as module-level assembly.
+//!
+//! A naked function whose body passed `rustc_passes::naked_functions` is a
+//! single `asm!` block with the `noreturn` option. When that block does not
+//! need anything from LLVM (i.e. it only has `const` operands), we can emit
+//! the function verbatim in the module's assembly instead of asking LLVM to
+//! codegen a function without prologue and epilogue. This guarantees that the
+//! bytes in the object file are exactly the ones written by the user, which is
+//! what vector tables and system call stubs rely on.
+
+use rustc_ast as ast;
+use rustc_ast::{InlineAsmOptions, InlineAsmTemplatePiece};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir;
+use rustc_middle::mir::mono::{InstantiationMode, Linkage, MonoItem, Visibility};
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_span::Span;
+
+use std::fmt::Write;
+
+/// Returns the `asm!` block making up the body of a naked function, if the
+/// function can be emitted as module-level assembly on the current target.
+fn module_asm_body<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+) -> Option<(&'tcx [InlineAsmTemplatePiece], &'tcx [mir::InlineAsmOperand<'tcx>], InlineAsmOptions)>
+{
+    let def_id = instance.def_id();
+    let attrs = tcx.codegen_fn_attrs(def_id);
+    if !attrs.flags.contains(CodegenFnAttrFlags::NAKED) || attrs.linkage.is_some() {
+        return None;
+    }
+
+    // Only ELF targets whose assembly syntax we know how to wrap a function
+    // in are supported for now.
+    let target = &tcx.sess.target;
+    if target.options.is_like_osx || target.options.is_like_windows {
+        return None;
+    }
+    if !["x86", "x86_64", "aarch64", "riscv32", "riscv64"].contains(&&target.arch[..]) {
+        return None;
+    }
+
+    // The symbol must be defined exactly once, which is not the case for
+    // generic functions or functions copied into every codegen unit.
+    if !matches!(instance.def, ty::InstanceDef::Item(_)) {
+        return None;
+    }
+    if instance.substs.non_erasable_generics().next().is_some() {
+        return None;
+    }
+    match MonoItem::Fn(instance).instantiation_mode(tcx) {
+        InstantiationMode::GloballyShared { may_conflict: false } => {}
+        _ => return None,
+    }
+
+    let mir = tcx.instance_mir(instance.def);
+    let mut asm = None;
+    for data in mir.basic_blocks() {
+        match data.terminator().kind {
+            mir::TerminatorKind::InlineAsm { template, ref operands, options, .. } => {
+                if asm.is_some() {
+                    return None;
+                }
+                asm = Some((template, &operands[..], options));
+            }
+            mir::TerminatorKind::Unreachable | mir::TerminatorKind::Goto { .. } => {}
+            _ => return None,
+        }
+    }
+
+    let (template, operands, options) = asm?;
+    if !options.contains(InlineAsmOptions::NORETURN) {
+        return None;
+    }
+    // `sym` operands are left to LLVM: it has to know about the reference to
+    // keep the referenced item alive.
+    if !operands.iter().all(|op| matches!(op, mir::InlineAsmOperand::Const { .. })) {
+        return None;
+    }
+    Some((template, operands, options))
+}
+
+/// Whether `instance` is a naked function emitted as module-level assembly
+/// rather than as an LLVM function.
+pub fn is_module_asm<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+    module_asm_body(tcx, instance).is_some()
+}
+
+/// Renders the module-level assembly defining the naked function `instance`
+/// under `symbol_name`. Must only be called if `is_module_asm` holds.
+pub fn module_asm<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    symbol_name: &str,
+    linkage: Linkage,
+    visibility: Visibility,
+) -> String {
+    let (template, operands, options) = module_asm_body(tcx, instance)
+        .unwrap_or_else(|| bug!("naked function {:?} can't be emitted as module asm", instance));
+    let span = tcx.def_span(instance.def_id());
+    let target = &tcx.sess.target;
+    let is_x86 = target.arch == "x86" || target.arch == "x86_64";
+    let function_sections =
+        tcx.sess.opts.debugging_opts.function_sections.unwrap_or(target.options.function_sections);
+
+    let mut asm = String::new();
+    if function_sections {
+        writeln!(asm, ".pushsection \".text.{}\",\"ax\",@progbits", symbol_name).unwrap();
+    } else {
+        writeln!(asm, ".pushsection .text,\"ax\",@progbits").unwrap();
+    }
+    let visibility = match linkage {
+        Linkage::External => visibility,
+        // The definition is invisible to LLVM, so ThinLTO may import a caller
+        // of an internal naked function into another module. Keep the symbol
+        // global, but hidden, so that such references still resolve.
+        Linkage::Internal | Linkage::Private => Visibility::Hidden,
+        _ => bug!("unexpected linkage {:?} for naked function {:?}", linkage, instance),
+    };
+    writeln!(asm, ".globl \"{}\"", symbol_name).unwrap();
+    match visibility {
+        Visibility::Default => {}
+        Visibility::Hidden => writeln!(asm, ".hidden \"{}\"", symbol_name).unwrap(),
+        Visibility::Protected => writeln!(asm, ".protected \"{}\"", symbol_name).unwrap(),
+    }
+    writeln!(asm, ".type \"{}\",@function", symbol_name).unwrap();
+    writeln!(asm, "\"{}\":", symbol_name).unwrap();
+
+    if is_x86 && !options.contains(InlineAsmOptions::ATT_SYNTAX) {
+        writeln!(asm, ".intel_syntax noprefix").unwrap();
+    }
+    for piece in template {
+        match *piece {
+            InlineAsmTemplatePiece::String(ref s) => asm.push_str(s),
+            InlineAsmTemplatePiece::Placeholder { operand_idx, modifier: _, span } => {
+                match operands[operand_idx] {
+                    mir::InlineAsmOperand::Const {
+                        value: mir::Operand::Constant(ref constant),
+                    } => {
+                        let ty = constant.literal.ty;
+                        let value = constant
+                            .literal
+                            .try_eval_bits(tcx, ty::ParamEnv::reveal_all(), ty)
+                            .unwrap_or_else(|| span_bug!(span, "asm const cannot be resolved"));
+                        asm.push_str(&asm_const_to_str(tcx, span, value, ty));
+                    }
+                    _ => span_bug!(span, "unexpected operand in naked function asm"),
+                }
+            }
+        }
+    }
+    asm.push('\n');
+    if is_x86 && !options.contains(InlineAsmOptions::ATT_SYNTAX) {
+        writeln!(asm, ".att_syntax").unwrap();
+    }
+
+    writeln!(asm, ".size \"{}\", . - \"{}\"", symbol_name, symbol_name).unwrap();
+    writeln!(asm, ".popsection").unwrap();
+    debug!("module asm for naked function {:?} at {:?}:\n{}", instance, span, asm);
+    asm
+}
+
+/// Formats the value of an `asm!` `const` operand of type `ty`, given as raw
+/// bits, the way it is spliced into the assembly template.
+pub(crate) fn asm_const_to_str<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    span: Span,
+    value: u128,
+    ty: Ty<'tcx>,
+) -> String {
+    match ty.kind() {
+        ty::Uint(_) => value.to_string(),
+        ty::Int(int_ty) => match int_ty.normalize(tcx.sess.target.pointer_width) {
+            ast::IntTy::I8 => (value as i8).to_string(),
+            ast::IntTy::I16 => (value as i16).to_string(),
+            ast::IntTy::I32 => (value as i32).to_string(),
+            ast::IntTy::I64 => (value as i64).to_string(),
+            ast::IntTy::I128 => (value as i128).to_string(),
+            ast::IntTy::Isize => unreachable!(),
+        },
+        ty::Float(ast::FloatTy::F32) => f32::from_bits(value as u32).to_string(),
+        ty::Float(ast::FloatTy::F64) => f64::from_bits(value as u64).to_string(),
+        _ => span_bug!(span, "asm const has bad type {}", ty),
+    }
+}
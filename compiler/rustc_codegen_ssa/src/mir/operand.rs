@@ -249,7 +249,7 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
         bx: &mut Bx,
         dest: PlaceRef<'tcx, V>,
     ) {
-        self.store_with_flags(bx, dest, MemFlags::empty());
+        self.store_with_flags(bx, dest, MemFlags::empty(), |_, _| {});
     }
 
     pub fn volatile_store<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
@@ -257,7 +257,7 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
         bx: &mut Bx,
         dest: PlaceRef<'tcx, V>,
     ) {
-        self.store_with_flags(bx, dest, MemFlags::VOLATILE);
+        self.store_with_flags(bx, dest, MemFlags::VOLATILE, |_, _| {});
     }
 
     pub fn unaligned_volatile_store<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
@@ -265,7 +265,7 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
         bx: &mut Bx,
         dest: PlaceRef<'tcx, V>,
     ) {
-        self.store_with_flags(bx, dest, MemFlags::VOLATILE | MemFlags::UNALIGNED);
+        self.store_with_flags(bx, dest, MemFlags::VOLATILE | MemFlags::UNALIGNED, |_, _| {});
     }
 
     pub fn nontemporal_store<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
@@ -273,7 +273,19 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
         bx: &mut Bx,
         dest: PlaceRef<'tcx, V>,
     ) {
-        self.store_with_flags(bx, dest, MemFlags::NONTEMPORAL);
+        self.store_with_flags(bx, dest, MemFlags::NONTEMPORAL, |_, _| {});
+    }
+
+    /// Like `store`, but calls `on_store` with each store instruction emitted,
+    /// e.g. to attach metadata to it. Values in memory are copied with
+    /// `memcpy` instead, for which `on_store` isn't called.
+    pub fn store_and_inspect<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
+        self,
+        bx: &mut Bx,
+        dest: PlaceRef<'tcx, V>,
+        on_store: impl FnMut(&mut Bx, V),
+    ) {
+        self.store_with_flags(bx, dest, MemFlags::empty(), on_store);
     }
 
     fn store_with_flags<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
@@ -281,6 +293,7 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
         bx: &mut Bx,
         dest: PlaceRef<'tcx, V>,
         flags: MemFlags,
+        mut on_store: impl FnMut(&mut Bx, V),
     ) {
         debug!("OperandRef::store: operand={:?}, dest={:?}", self, dest);
         // Avoid generating stores of zero-sized values, because the only way to have a zero-sized
@@ -297,7 +310,12 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
             }
             OperandValue::Immediate(s) => {
                 let val = bx.from_immediate(s);
-                bx.store_with_flags(val, dest.llval, dest.align, flags);
+                let store = if dest.needs_unaligned_access() && flags.is_empty() {
+                    bx.store_unaligned(val, dest.llval)
+                } else {
+                    bx.store_with_flags(val, dest.llval, dest.align, flags)
+                };
+                on_store(bx, store);
             }
             OperandValue::Pair(a, b) => {
                 let (a_scalar, b_scalar) = match dest.layout.abi {
@@ -305,16 +323,27 @@ impl<'a, 'tcx, V: CodegenObject> OperandValue<V> {
                     _ => bug!("store_with_flags: invalid ScalarPair layout: {:#?}", dest.layout),
                 };
                 let b_offset = a_scalar.value.size(bx).align_to(b_scalar.value.align(bx).abi);
+                let unaligned = dest.needs_unaligned_access() && flags.is_empty();
 
                 let llptr = bx.struct_gep(dest.llval, 0);
                 let val = bx.from_immediate(a);
                 let align = dest.align;
-                bx.store_with_flags(val, llptr, align, flags);
+                let store = if unaligned {
+                    bx.store_unaligned(val, llptr)
+                } else {
+                    bx.store_with_flags(val, llptr, align, flags)
+                };
+                on_store(bx, store);
 
                 let llptr = bx.struct_gep(dest.llval, 1);
                 let val = bx.from_immediate(b);
                 let align = dest.align.restrict_for_offset(b_offset);
-                bx.store_with_flags(val, llptr, align, flags);
+                let store = if unaligned {
+                    bx.store_unaligned(val, llptr)
+                } else {
+                    bx.store_with_flags(val, llptr, align, flags)
+                };
+                on_store(bx, store);
             }
         }
     }
@@ -423,7 +452,18 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         // for most places, to consume them we just load them
         // out from their home
         let place = self.codegen_place(bx, place_ref);
-        bx.load_operand(place)
+        let operand = bx.load_operand(place);
+        if let Some(scope) = self.alias_scope(place_ref) {
+            match operand.val {
+                OperandValue::Immediate(a) => self.set_alias_scope(bx, a, scope),
+                OperandValue::Pair(a, b) => {
+                    self.set_alias_scope(bx, a, scope);
+                    self.set_alias_scope(bx, b, scope);
+                }
+                OperandValue::Ref(..) => {}
+            }
+        }
+        operand
     }
 
     pub fn codegen_operand(
@@ -8,7 +8,7 @@ use crate::MemFlags;
 
 use rustc_middle::mir;
 use rustc_middle::mir::tcx::PlaceTy;
-use rustc_middle::ty::layout::{HasTyCtxt, TyAndLayout};
+use rustc_middle::ty::layout::{HasTyCtxt, PointerMetadataKind, TyAndLayout};
 use rustc_middle::ty::{self, Ty};
 use rustc_target::abi::{Abi, Align, FieldsShape, Int, TagEncoding};
 use rustc_target::abi::{LayoutOf, VariantIdx, Variants};
@@ -67,6 +67,13 @@ impl<'a, 'tcx, V: CodegenObject> PlaceRef<'tcx, V> {
         Self::alloca(bx, ptr_layout)
     }
 
+    /// Returns `true` if this place is byte-aligned while its type needs more,
+    /// as is the case for fields of `#[repr(packed)]` types. Loads and stores
+    /// of such places go through `load_unaligned` and `store_unaligned`.
+    pub fn needs_unaligned_access(&self) -> bool {
+        self.align.bytes() == 1 && self.layout.align.abi.bytes() > 1
+    }
+
     pub fn len<Cx: ConstMethods<'tcx, Value = V>>(&self, cx: &Cx) -> V {
         if let FieldsShape::Array { count, .. } = self.layout.fields {
             if self.layout.is_unsized() {
@@ -143,7 +150,9 @@ impl<'a, 'tcx, V: CodegenObject> PlaceRef<'tcx, V> {
                 return simple();
             }
             _ if !field.is_unsized() => return simple(),
-            ty::Slice(..) | ty::Str | ty::Foreign(..) => return simple(),
+            _ if PointerMetadataKind::of_tail(field.ty).map_or(false, |k| k.has_static_align()) => {
+                return simple();
+            }
             ty::Adt(def, _) => {
                 if def.repr.packed() {
                     // FIXME(eddyb) generalize the adjustment when we
@@ -517,4 +526,25 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         let place_ty = mir::Place::ty_from(place_ref.local, place_ref.projection, self.mir, tcx);
         self.monomorphize(&place_ty.ty)
     }
+
+    /// Returns the alias scope of the memory `place_ref` is in, if it's behind
+    /// a local with an alias scope (see `analyze::alias_scopes`).
+    pub fn alias_scope(&self, place_ref: mir::PlaceRef<'tcx>) -> Option<usize> {
+        match place_ref.projection {
+            [mir::ProjectionElem::Deref, rest @ ..]
+                if !rest.contains(&mir::ProjectionElem::Deref) =>
+            {
+                self.alias_scopes.as_ref()?.scopes[place_ref.local]
+            }
+            _ => None,
+        }
+    }
+
+    /// Marks `access`, a load or store of memory in `scope`, as not aliasing
+    /// accesses of memory in any other scope of this function.
+    pub fn set_alias_scope(&self, bx: &mut Bx, access: Bx::Value, scope: usize) {
+        if let Some(scopes) = &self.alias_scopes {
+            bx.alias_scope_metadata(access, &scopes.nodes, scope);
+        }
+    }
 }
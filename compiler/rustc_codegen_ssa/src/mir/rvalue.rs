@@ -489,9 +489,16 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
 
             mir::Rvalue::Discriminant(ref place) => {
                 let discr_ty = rvalue.ty(self.mir, bx.tcx());
-                let discr = self
-                    .codegen_place(&mut bx, place.as_ref())
-                    .codegen_get_discr(&mut bx, discr_ty);
+                let discr = match self.discriminants.get(&(*place, discr_ty)) {
+                    Some(&discr) => discr,
+                    None => {
+                        let discr = self
+                            .codegen_place(&mut bx, place.as_ref())
+                            .codegen_get_discr(&mut bx, discr_ty);
+                        self.discriminants.insert((*place, discr_ty), discr);
+                        discr
+                    }
+                };
                 (
                     bx,
                     OperandRef {
@@ -735,7 +742,8 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     mir::BinOp::Mul => OverflowOp::Mul,
                     _ => unreachable!(),
                 };
-                bx.checked_binop(oop, input_ty, lhs, rhs)
+                let funclet = self.funclet_bb.and_then(|bb| self.funclets[bb].as_ref());
+                bx.checked_binop(oop, input_ty, lhs, rhs, funclet)
             }
             mir::BinOp::Shl | mir::BinOp::Shr => {
                 let lhs_llty = bx.cx().val_ty(lhs);
@@ -8,10 +8,49 @@ use crate::traits::BuilderMethods;
 use crate::traits::*;
 
 impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
+    /// Forgets the cached discriminants of every place based on `local` or
+    /// using it as an index, as they may no longer be the places they were.
+    fn invalidate_discriminants_of(&mut self, local: mir::Local) {
+        self.discriminants.retain(|&(cached, _), _| {
+            cached.local != local
+                && !cached.projection.iter().any(|elem| elem == mir::ProjectionElem::Index(local))
+        });
+    }
+
     pub fn codegen_statement(&mut self, mut bx: Bx, statement: &mir::Statement<'tcx>) -> Bx {
         debug!("codegen_statement(statement={:?})", statement);
 
         self.set_debug_loc(&mut bx, statement.source_info);
+
+        // Any statement that may write to memory can change the discriminant
+        // of a place, so cached discriminants can't be reused past it.
+        // Assignments to SSA locals don't touch memory, but still invalidate
+        // every discriminant read from, through or indexed by the local.
+        let may_write_memory = match statement.kind {
+            mir::StatementKind::Assign(box (ref place, _)) => match place.as_local() {
+                Some(index) => {
+                    self.invalidate_discriminants_of(index);
+                    !matches!(self.locals[index], LocalRef::Operand(_))
+                }
+                None => true,
+            },
+            mir::StatementKind::SetDiscriminant { .. } | mir::StatementKind::LlvmInlineAsm(..) => {
+                true
+            }
+            mir::StatementKind::StorageLive(local) | mir::StatementKind::StorageDead(local) => {
+                self.invalidate_discriminants_of(local);
+                false
+            }
+            mir::StatementKind::Coverage(..)
+            | mir::StatementKind::FakeRead(..)
+            | mir::StatementKind::Retag { .. }
+            | mir::StatementKind::AscribeUserType(..)
+            | mir::StatementKind::Nop => false,
+        };
+        if may_write_memory {
+            self.discriminants.clear();
+        }
+
         match statement.kind {
             mir::StatementKind::Assign(box (ref place, ref rvalue)) => {
                 if let Some(index) = place.as_local() {
@@ -42,12 +81,37 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     }
                 } else {
                     let cg_dest = self.codegen_place(&mut bx, place.as_ref());
-                    self.codegen_rvalue(bx, cg_dest, rvalue)
+                    match (self.alias_scope(place.as_ref()), rvalue) {
+                        (
+                            Some(scope),
+                            mir::Rvalue::Use(_)
+                            | mir::Rvalue::BinaryOp(..)
+                            | mir::Rvalue::UnaryOp(..),
+                        ) => {
+                            let (mut bx, operand) = self.codegen_rvalue_operand(bx, rvalue);
+                            operand.val.store_and_inspect(&mut bx, cg_dest, |bx, store| {
+                                self.set_alias_scope(bx, store, scope)
+                            });
+                            bx
+                        }
+                        (_, _) => self.codegen_rvalue(bx, cg_dest, rvalue),
+                    }
                 }
             }
             mir::StatementKind::SetDiscriminant { box ref place, variant_index } => {
-                self.codegen_place(&mut bx, place.as_ref())
-                    .codegen_set_discr(&mut bx, variant_index);
+                let dest = self.codegen_place(&mut bx, place.as_ref());
+                dest.codegen_set_discr(&mut bx, variant_index);
+
+                // The discriminant is now known, so reads of it later in the
+                // block fold to a constant instead of loading and decoding
+                // the tag again.
+                let enum_ty = dest.layout.ty;
+                if let Some(discr) = enum_ty.discriminant_for_variant(bx.tcx(), variant_index) {
+                    let discr_ty = enum_ty.discriminant_ty(bx.tcx());
+                    let discr_llty = bx.cx().immediate_backend_type(bx.layout_of(discr_ty));
+                    let discr = bx.cx().const_uint_big(discr_llty, discr.val);
+                    self.discriminants.insert((*place, discr_ty), discr);
+                }
                 bx
             }
             mir::StatementKind::StorageLive(local) => {
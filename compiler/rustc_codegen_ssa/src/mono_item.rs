@@ -1,4 +1,5 @@
 use crate::base;
+use crate::mir::naked_asm;
 use crate::traits::*;
 use rustc_hir as hir;
 use rustc_middle::mir::mono::{Linkage, Visibility};
@@ -39,7 +40,11 @@ impl<'a, 'tcx: 'a> MonoItemExt<'a, 'tcx> for MonoItem<'tcx> {
                 }
             }
             MonoItem::Fn(instance) => {
-                base::codegen_instance::<Bx>(&cx, instance);
+                // Naked functions emitted as module-level assembly were
+                // already defined when they were predefined.
+                if !naked_asm::is_module_asm(cx.tcx(), instance) {
+                    base::codegen_instance::<Bx>(&cx, instance);
+                }
             }
         }
 
@@ -72,6 +77,11 @@ impl<'a, 'tcx: 'a> MonoItemExt<'a, 'tcx> for MonoItem<'tcx> {
             MonoItem::Static(def_id) => {
                 cx.predefine_static(def_id, linkage, visibility, &symbol_name);
             }
+            MonoItem::Fn(instance) if naked_asm::is_module_asm(cx.tcx(), instance) => {
+                let asm =
+                    naked_asm::module_asm(cx.tcx(), instance, &symbol_name, linkage, visibility);
+                cx.codegen_module_asm(&asm, &[cx.tcx().def_span(instance.def_id())]);
+            }
             MonoItem::Fn(instance) => {
                 cx.predefine_fn(instance, linkage, visibility, &symbol_name);
             }
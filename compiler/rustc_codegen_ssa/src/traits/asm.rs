@@ -57,5 +57,10 @@ pub trait AsmBuilderMethods<'tcx>: BackendTypes {
 }
 
 pub trait AsmMethods {
-    fn codegen_global_asm(&self, ga: &GlobalAsm);
+    fn codegen_global_asm(&self, ga: &GlobalAsm<'_>);
+
+    /// Appends `asm` verbatim to the module-level assembly. Errors in its n-th
+    /// line are reported at `line_spans[n]`, or at the last span if there are
+    /// fewer spans than lines.
+    fn codegen_module_asm(&self, asm: &str, line_spans: &[Span]);
 }
@@ -48,6 +48,11 @@ impl<'tcx, T> Backend<'tcx> for T where
 {
 }
 
+/// The interface the driver uses to generate code for a crate.
+///
+/// Besides `rustc_interface`, it can be driven directly by tools embedding the
+/// compiler: query inputs go in through a `TyCtxt`, and a `CodegenResults`
+/// describing every emitted artifact comes out. See `codegen_and_join`.
 pub trait CodegenBackend {
     fn init(&self, _sess: &Session) {}
     fn print(&self, _req: PrintRequest, _sess: &Session) {}
@@ -66,6 +71,8 @@ pub trait CodegenBackend {
     fn metadata_loader(&self) -> Box<MetadataLoaderDyn>;
     fn provide(&self, _providers: &mut Providers);
     fn provide_extern(&self, _providers: &mut Providers);
+    /// Starts generating code for the local crate and returns an opaque handle
+    /// to the ongoing work, which may continue on background threads.
     fn codegen_crate<'tcx>(
         &self,
         tcx: TyCtxt<'tcx>,
@@ -95,6 +102,25 @@ pub trait CodegenBackend {
         codegen_results: CodegenResults,
         outputs: &OutputFilenames,
     ) -> Result<(), ErrorReported>;
+
+    /// Generates code for the local crate and waits until every module has
+    /// been optimized and emitted, without linking anything.
+    ///
+    /// This is meant for embedders such as alternative drivers, REPLs or IDE
+    /// features that want the backend's output without replicating the
+    /// driver. The returned `CodegenResults` lists the written artifacts in
+    /// `modules`, `allocator_module` and `metadata_module`, and can still be
+    /// passed to `link`. The work products are only needed to keep an
+    /// incremental session directory up to date.
+    fn codegen_and_join<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        metadata: EncodedMetadata,
+        need_metadata_module: bool,
+    ) -> Result<(CodegenResults, FxHashMap<WorkProductId, WorkProduct>), ErrorReported> {
+        let ongoing_codegen = self.codegen_crate(tcx, metadata, need_metadata_module);
+        self.join_codegen(ongoing_codegen, tcx.sess)
+    }
 }
 
 pub trait ExtraBackendMethods: CodegenBackend + WriteBackendMethods + Sized + Send + Sync {
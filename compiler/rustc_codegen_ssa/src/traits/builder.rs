@@ -7,7 +7,8 @@ use super::type_::ArgAbiMethods;
 use super::{HasCodegen, StaticBuilderMethods};
 
 use crate::common::{
-    AtomicOrdering, AtomicRmwBinOp, IntPredicate, RealPredicate, SynchronizationScope,
+    AtomicOrdering, AtomicRmwBinOp, CallSiteAttr, IntPredicate, RealPredicate,
+    SynchronizationScope, TailCallKind,
 };
 use crate::mir::operand::OperandRef;
 use crate::mir::place::PlaceRef;
@@ -16,7 +17,7 @@ use crate::MemFlags;
 use rustc_middle::ty::layout::{HasParamEnv, TyAndLayout};
 use rustc_middle::ty::Ty;
 use rustc_span::Span;
-use rustc_target::abi::{Abi, Align, Scalar, Size};
+use rustc_target::abi::{Abi, Align, Int, Pointer, Scalar, Size};
 use rustc_target::spec::HasTargetSpec;
 
 use std::ops::Range;
@@ -71,6 +72,17 @@ pub trait BuilderMethods<'a, 'tcx>:
         catch: Self::BasicBlock,
         funclet: Option<&Self::Funclet>,
     ) -> Self::Value;
+    /// Like `invoke`, but the call also carries an operand bundle named `tag`
+    /// without any inputs, next to the funclet's bundle if there is one.
+    fn invoke_with_operand_bundle(
+        &mut self,
+        llfn: Self::Value,
+        args: &[Self::Value],
+        then: Self::BasicBlock,
+        catch: Self::BasicBlock,
+        funclet: Option<&Self::Funclet>,
+        tag: &str,
+    ) -> Self::Value;
     fn unreachable(&mut self);
 
     fn add(&mut self, lhs: Self::Value, rhs: Self::Value) -> Self::Value;
@@ -107,13 +119,26 @@ pub trait BuilderMethods<'a, 'tcx>:
     fn neg(&mut self, v: Self::Value) -> Self::Value;
     fn fneg(&mut self, v: Self::Value) -> Self::Value;
     fn not(&mut self, v: Self::Value) -> Self::Value;
+    fn minnum(&mut self, lhs: Self::Value, rhs: Self::Value) -> Self::Value;
+    fn maxnum(&mut self, lhs: Self::Value, rhs: Self::Value) -> Self::Value;
+    fn bitreverse(&mut self, v: Self::Value) -> Self::Value;
+    /// Counts the leading zero bits of `v`. The result is undefined for zero
+    /// if `is_zero_undef` is set.
+    fn ctlz(&mut self, v: Self::Value, is_zero_undef: bool) -> Self::Value;
+    /// Counts the trailing zero bits of `v`. The result is undefined for zero
+    /// if `is_zero_undef` is set.
+    fn cttz(&mut self, v: Self::Value, is_zero_undef: bool) -> Self::Value;
 
+    /// Returns the result of the operation and whether it overflowed. Some
+    /// operations are lowered to library calls, which need `funclet` when the
+    /// operation is made inside a cleanup funclet.
     fn checked_binop(
         &mut self,
         oop: OverflowOp,
         ty: Ty<'_>,
         lhs: Self::Value,
         rhs: Self::Value,
+        funclet: Option<&Self::Funclet>,
     ) -> (Self::Value, Self::Value);
 
     fn from_immediate(&mut self, val: Self::Value) -> Self::Value;
@@ -131,8 +156,16 @@ pub trait BuilderMethods<'a, 'tcx>:
     fn array_alloca(&mut self, ty: Self::Type, len: Self::Value, align: Align) -> Self::Value;
 
     fn load(&mut self, ptr: Self::Value, align: Align) -> Self::Value;
-    fn volatile_load(&mut self, ptr: Self::Value) -> Self::Value;
-    fn atomic_load(&mut self, ptr: Self::Value, order: AtomicOrdering, size: Size) -> Self::Value;
+    /// Loads from `ptr` without assuming any alignment, as needed for the
+    /// fields of `#[repr(packed)]` types.
+    fn load_unaligned(&mut self, ptr: Self::Value) -> Self::Value {
+        self.load(ptr, Align::from_bytes(1).unwrap())
+    }
+    fn volatile_load(&mut self, ptr: Self::Value, align: Align) -> Self::Value;
+    /// Atomically loads from `ptr`. Backends may require `align` to be at
+    /// least the size of the loaded type.
+    fn atomic_load(&mut self, ptr: Self::Value, order: AtomicOrdering, align: Align)
+    -> Self::Value;
     fn load_operand(&mut self, place: PlaceRef<'tcx, Self::Value>)
     -> OperandRef<'tcx, Self::Value>;
 
@@ -147,7 +180,56 @@ pub trait BuilderMethods<'a, 'tcx>:
     fn range_metadata(&mut self, load: Self::Value, range: Range<u128>);
     fn nonnull_metadata(&mut self, load: Self::Value);
 
+    fn align_metadata(&mut self, load: Self::Value, align: Align);
+
+    /// Creates a new, anonymous alias scope domain with `num_scopes` scopes
+    /// and returns the scopes. `name` only serves to make the IR readable.
+    fn alias_scope_domain(&mut self, name: &str, num_scopes: usize) -> Vec<Self::Value>;
+
+    /// Attaches `!alias.scope` and `!noalias` metadata to `access`, a load or
+    /// store of memory in `scopes[scope]`, telling LLVM it doesn't alias
+    /// accesses in any other of `scopes`.
+    fn alias_scope_metadata(&mut self, access: Self::Value, scopes: &[Self::Value], scope: usize);
+
+    /// Attaches metadata to `load`, a load of `scalar` at `offset` into a
+    /// value of type `layout`, describing the values it can take: `!range`
+    /// for `bool`, `char` and niche discriminants, and `!nonnull` and
+    /// `!align` for references, boxes and the data pointer of fat pointers.
+    fn scalar_load_metadata(
+        &mut self,
+        load: Self::Value,
+        scalar: &Scalar,
+        layout: TyAndLayout<'tcx>,
+        offset: Size,
+    ) {
+        match scalar.value {
+            Int(..) => {
+                let range = scalar.valid_range_exclusive(self);
+                if range.start != range.end {
+                    self.range_metadata(load, range);
+                }
+            }
+            Pointer => {
+                let vr = &scalar.valid_range;
+                if vr.start() < vr.end() && !vr.contains(&0) {
+                    self.nonnull_metadata(load);
+                }
+                if let Some(pointee) = layout.pointee_info_at(self, offset) {
+                    if pointee.safe.is_some() && pointee.align.bytes() > 1 {
+                        self.align_metadata(load, pointee.align);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn store(&mut self, val: Self::Value, ptr: Self::Value, align: Align) -> Self::Value;
+    /// Stores to `ptr` without assuming any alignment, as needed for the
+    /// fields of `#[repr(packed)]` types.
+    fn store_unaligned(&mut self, val: Self::Value, ptr: Self::Value) -> Self::Value {
+        self.store_with_flags(val, ptr, Align::from_bytes(1).unwrap(), MemFlags::UNALIGNED)
+    }
     fn store_with_flags(
         &mut self,
         val: Self::Value,
@@ -155,12 +237,14 @@ pub trait BuilderMethods<'a, 'tcx>:
         align: Align,
         flags: MemFlags,
     ) -> Self::Value;
+    /// Atomically stores `val` to `ptr`. Backends may require `align` to be
+    /// at least the size of the stored type.
     fn atomic_store(
         &mut self,
         val: Self::Value,
         ptr: Self::Value,
         order: AtomicOrdering,
-        size: Size,
+        align: Align,
     );
 
     fn gep(&mut self, ptr: Self::Value, indices: &[Self::Value]) -> Self::Value;
@@ -213,6 +297,36 @@ pub trait BuilderMethods<'a, 'tcx>:
         align: Align,
         flags: MemFlags,
     );
+    /// Copies `size` bytes as a sequence of unordered atomic loads and stores of
+    /// `element_size` bytes each. `size` must be a multiple of `element_size`,
+    /// a power of two no wider than the target's atomics, and both pointers
+    /// must be aligned to at least `element_size`.
+    fn atomic_element_memcpy(
+        &mut self,
+        dst: Self::Value,
+        dst_align: Align,
+        src: Self::Value,
+        src_align: Align,
+        size: Self::Value,
+        element_size: Size,
+    );
+    /// Like `atomic_element_memcpy`, but stores `fill_byte` to every byte.
+    fn atomic_element_memset(
+        &mut self,
+        ptr: Self::Value,
+        fill_byte: Self::Value,
+        size: Self::Value,
+        align: Align,
+        element_size: Size,
+    );
+    /// Compares `size` bytes at `lhs` and `rhs` like C's `memcmp`, returning
+    /// an `i32`.
+    fn memcmp(&mut self, lhs: Self::Value, rhs: Self::Value, size: Self::Value) -> Self::Value;
+    /// Like `memcmp`, but only whether the result is zero is meaningful, which
+    /// allows calling `bcmp` on the targets that provide it.
+    fn bcmp(&mut self, lhs: Self::Value, rhs: Self::Value, size: Self::Value) -> Self::Value {
+        self.memcmp(lhs, rhs, size)
+    }
 
     fn select(
         &mut self,
@@ -224,6 +338,8 @@ pub trait BuilderMethods<'a, 'tcx>:
     fn va_arg(&mut self, list: Self::Value, ty: Self::Type) -> Self::Value;
     fn extract_element(&mut self, vec: Self::Value, idx: Self::Value) -> Self::Value;
     fn vector_splat(&mut self, num_elts: usize, elt: Self::Value) -> Self::Value;
+    /// Splats `elt` into a scalable vector of `min_num_elts * vscale` elements.
+    fn scalable_vector_splat(&mut self, min_num_elts: usize, elt: Self::Value) -> Self::Value;
     fn extract_value(&mut self, agg_val: Self::Value, idx: u64) -> Self::Value;
     fn insert_value(&mut self, agg_val: Self::Value, elt: Self::Value, idx: u64) -> Self::Value;
 
@@ -269,6 +385,15 @@ pub trait BuilderMethods<'a, 'tcx>:
     ) -> Self::Value;
     fn atomic_fence(&mut self, order: AtomicOrdering, scope: SynchronizationScope);
     fn set_invariant_load(&mut self, load: Self::Value);
+    /// Loads the pointer at `vtable_byte_offset` in `llvtable`, telling the
+    /// backend that the vtable is one of those registered under `typeid` by
+    /// `StaticMethods::add_vtable_type_metadata`.
+    fn type_checked_load(
+        &mut self,
+        llvtable: Self::Value,
+        vtable_byte_offset: u64,
+        typeid: &str,
+    ) -> Self::Value;
 
     /// Called for `StorageLive`
     fn lifetime_start(&mut self, ptr: Self::Value, size: Size);
@@ -290,8 +415,20 @@ pub trait BuilderMethods<'a, 'tcx>:
         args: &[Self::Value],
         funclet: Option<&Self::Funclet>,
     ) -> Self::Value;
+    /// Like `call`, but the call also carries an operand bundle named `tag`
+    /// without any inputs, next to the funclet's bundle if there is one.
+    fn call_with_operand_bundle(
+        &mut self,
+        llfn: Self::Value,
+        args: &[Self::Value],
+        funclet: Option<&Self::Funclet>,
+        tag: &str,
+    ) -> Self::Value;
     fn zext(&mut self, val: Self::Value, dest_ty: Self::Type) -> Self::Value;
 
     unsafe fn delete_basic_block(&mut self, bb: Self::BasicBlock);
-    fn do_not_inline(&mut self, llret: Self::Value);
+    /// Adds `attrs` to the call or invoke `llret`. They only guide optimization,
+    /// so backends without call-site attributes may ignore them.
+    fn apply_callsite_attrs(&mut self, _llret: Self::Value, _attrs: &[CallSiteAttr]) {}
+    fn set_tail_call_kind(&mut self, llret: Self::Value, kind: TailCallKind);
 }
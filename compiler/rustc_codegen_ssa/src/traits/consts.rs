@@ -25,6 +25,11 @@ pub trait ConstMethods<'tcx>: BackendTypes {
 
     fn const_to_opt_uint(&self, v: Self::Value) -> Option<u64>;
     fn const_to_opt_u128(&self, v: Self::Value, sign_ext: bool) -> Option<u128>;
+    /// Like `const_to_opt_u128` with sign extension, but returns the value as an `i128`.
+    fn const_to_opt_i128(&self, v: Self::Value) -> Option<i128>;
+    /// Returns the elements of a constant vector, array or struct, or `None` if `v` is
+    /// not such a constant.
+    fn const_vector_elements(&self, v: Self::Value) -> Option<Vec<Self::Value>>;
 
     fn scalar_to_backend(&self, cv: Scalar, layout: &abi::Scalar, llty: Self::Type) -> Self::Value;
     fn from_const_alloc(
@@ -3,12 +3,17 @@ use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::mir::mono::CodegenUnit;
 use rustc_middle::ty::{self, Instance, Ty};
 use rustc_session::Session;
+use rustc_span::{Span, Symbol};
+use rustc_target::abi::call::FnAbi;
 use std::cell::RefCell;
 
 pub trait MiscMethods<'tcx>: BackendTypes {
     fn vtables(
         &self,
     ) -> &RefCell<FxHashMap<(Ty<'tcx>, Option<ty::PolyExistentialTraitRef<'tcx>>), Self::Value>>;
+    /// Panic stubs already defined in this codegen unit, keyed by panic message
+    /// and the span of the reported location.
+    fn panic_stubs(&self) -> &RefCell<FxHashMap<(Symbol, Span), Self::Value>>;
     fn check_overflow(&self) -> bool;
     fn get_fn(&self, instance: Instance<'tcx>) -> Self::Function;
     fn get_fn_addr(&self, instance: Instance<'tcx>) -> Self::Value;
@@ -21,4 +26,13 @@ pub trait MiscMethods<'tcx>: BackendTypes {
     fn create_used_variable(&self);
     /// Declares the extern "C" main function for the entry point. Returns None if the symbol already exists.
     fn declare_c_main(&self, fn_type: Self::Type) -> Option<Self::Function>;
+    /// Defines a fresh internal, cold, never-inlined function for `fn_abi` whose body
+    /// only calls `panic_fn` with `args`. Returns the address of the new function.
+    fn define_panic_stub(
+        &self,
+        fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        panic_fn: Self::Value,
+        panic_fn_abi: &FnAbi<'tcx, Ty<'tcx>>,
+        args: &[Self::Value],
+    ) -> Self::Value;
 }
@@ -17,6 +17,12 @@ pub trait StaticMethods: BackendTypes {
     /// static FOO: u32 = 0;
     /// ```
     fn add_used_global(&self, global: Self::Value);
+
+    /// Registers `vtable` as a vtable of the trait identified by `typeid`, so
+    /// that the backend can devirtualize the `type_checked_load`s through it.
+    /// With `local_calls_only`, every call through the vtable is such a load,
+    /// and the backend may remove the entries that are never loaded.
+    fn add_vtable_type_metadata(&self, vtable: Self::Value, typeid: &str, local_calls_only: bool);
 }
 
 pub trait StaticBuilderMethods: BackendTypes {
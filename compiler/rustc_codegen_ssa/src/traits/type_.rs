@@ -3,7 +3,7 @@ use super::Backend;
 use super::HasCodegen;
 use crate::common::TypeKind;
 use crate::mir::place::PlaceRef;
-use rustc_middle::ty::layout::TyAndLayout;
+use rustc_middle::ty::layout::{PointerMetadataKind, TyAndLayout};
 use rustc_middle::ty::{self, Ty};
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::call::{ArgAbi, CastTarget, FnAbi, Reg};
@@ -31,6 +31,7 @@ pub trait BaseTypeMethods<'tcx>: Backend<'tcx> {
     fn element_type(&self, ty: Self::Type) -> Self::Type;
 
     /// Returns the number of elements in `self` if it is a LLVM vector type.
+    /// For scalable vectors this is the known minimum number of elements.
     fn vector_length(&self, ty: Self::Type) -> usize;
 
     fn float_width(&self, ty: Self::Type) -> usize;
@@ -83,16 +84,9 @@ pub trait DerivedTypeMethods<'tcx>: BaseTypeMethods<'tcx> + MiscMethods<'tcx> {
     }
 
     fn type_has_metadata(&self, ty: Ty<'tcx>) -> bool {
-        let param_env = ty::ParamEnv::reveal_all();
-        if ty.is_sized(self.tcx().at(DUMMY_SP), param_env) {
-            return false;
-        }
-
-        let tail = self.tcx().struct_tail_erasing_lifetimes(ty, param_env);
-        match tail.kind() {
-            ty::Foreign(..) => false,
-            ty::Str | ty::Slice(..) | ty::Dynamic(..) => true,
-            _ => bug!("unexpected unsized tail: {:?}", tail),
+        match PointerMetadataKind::of_pointee(self.tcx(), ty::ParamEnv::reveal_all(), ty) {
+            Some(kind) => !kind.is_thin(),
+            None => bug!("unexpected unsized tail of {:?}", ty),
         }
     }
 }
@@ -465,6 +465,11 @@ fn run_compiler(
             );
         }
 
+        if let Some(&exit_code) = sess.jit_exit_code.get() {
+            // `-Z jit-run` ran the crate instead of linking it, exit like it did.
+            process::exit(exit_code);
+        }
+
         Ok(())
     })
 }
@@ -610,6 +610,12 @@ declare_features! (
     /// Allows unsized fn parameters.
     (active, unsized_fn_params, "1.49.0", Some(48055), None),
 
+    /// Allows `extern "C-cmse-nonsecure-call" fn()`.
+    (active, abi_c_cmse_nonsecure_call, "1.49.0", Some(81391), None),
+
+    /// Allows `#[address_space = "..."]` on statics for NVPTX targets.
+    (active, nvptx_address_space, "1.49.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -342,6 +342,10 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
     gated!(ffi_returns_twice, AssumedUsed, template!(Word), experimental!(ffi_returns_twice)),
     gated!(ffi_pure, AssumedUsed, template!(Word), experimental!(ffi_pure)),
     gated!(ffi_const, AssumedUsed, template!(Word), experimental!(ffi_const)),
+    gated!(
+        address_space, AssumedUsed, template!(NameValueStr: "shared|constant|local"),
+        nvptx_address_space, experimental!(address_space),
+    ),
     gated!(
         register_attr, CrateLevel, template!(List: "attr1, attr2, ..."),
         experimental!(register_attr),
@@ -404,6 +408,10 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     rustc_attr!(rustc_allocator, AssumedUsed, template!(Word), IMPL_DETAIL),
     rustc_attr!(rustc_allocator_nounwind, AssumedUsed, template!(Word), IMPL_DETAIL),
+    rustc_attr!(rustc_must_tail, AssumedUsed, template!(Word), INTERNAL_UNSTABLE),
+    rustc_attr!(
+        rustc_operand_bundle, AssumedUsed, template!(NameValueStr: "tag"), INTERNAL_UNSTABLE
+    ),
     gated!(alloc_error_handler, Normal, template!(Word), experimental!(alloc_error_handler)),
     gated!(
         default_lib_allocator, AssumedUsed, template!(Word), allocator_internals,
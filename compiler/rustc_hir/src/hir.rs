@@ -2280,9 +2280,10 @@ pub struct ForeignMod<'hir> {
     pub items: &'hir [ForeignItem<'hir>],
 }
 
-#[derive(Encodable, Debug, HashStable_Generic)]
-pub struct GlobalAsm {
+#[derive(Debug, HashStable_Generic)]
+pub struct GlobalAsm<'hir> {
     pub asm: Symbol,
+    pub line_spans: &'hir [Span],
 }
 
 #[derive(Debug, HashStable_Generic)]
@@ -2534,7 +2535,7 @@ pub enum ItemKind<'hir> {
     /// An external module, e.g. `extern { .. }`.
     ForeignMod(ForeignMod<'hir>),
     /// Module-level inline assembly (from `global_asm!`).
-    GlobalAsm(&'hir GlobalAsm),
+    GlobalAsm(&'hir GlobalAsm<'hir>),
     /// A type alias, e.g., `type Foo = Bar<u8>`.
     TyAlias(&'hir Ty<'hir>, Generics<'hir>),
     /// An opaque `impl Trait` type alias, e.g., `type Foo = impl Bar;`.
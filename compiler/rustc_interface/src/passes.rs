@@ -922,6 +922,7 @@ fn analysis(tcx: TyCtxt<'_>, cnum: CrateNum) -> Result<()> {
 
                         tcx.ensure().check_mod_liveness(local_def_id);
                         tcx.ensure().check_mod_intrinsics(local_def_id);
+                        tcx.ensure().check_mod_naked_functions(local_def_id);
                     });
                 });
             }
@@ -1080,5 +1081,12 @@ pub fn start_codegen<'tcx>(
         }
     }
 
+    if tcx.sess.opts.debugging_opts.emit_abi_tests {
+        if let Err(e) = rustc_codegen_ssa::abi_tests::emit_abi_tests(tcx, outputs) {
+            tcx.sess.err(&format!("could not emit ABI tests: {}", e));
+            tcx.sess.abort_if_errors();
+        }
+    }
+
     codegen
 }
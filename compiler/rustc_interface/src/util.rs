@@ -29,7 +29,7 @@ use std::io::{self, Write};
 use std::lazy::SyncOnceCell;
 use std::mem;
 use std::ops::DerefMut;
-use std::path::{Path, PathBuf};
+use std::path::{self, Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, Once};
 #[cfg(not(parallel_compiler))]
@@ -216,6 +216,13 @@ pub fn setup_callbacks_and_run_in_thread_pool_with_globals<F: FnOnce() -> R + Se
 }
 
 fn load_backend_from_dylib(path: &Path) -> fn() -> Box<dyn CodegenBackend> {
+    // A bare file name is looked up on the dynamic loader's search path, so
+    // only an actual path can be checked for existence up front.
+    let has_dir = path.parent().map_or(false, |dir| dir != Path::new(""));
+    if has_dir && !path.exists() {
+        let err = format!("codegen backend {:?} does not exist", path);
+        early_error(ErrorOutputType::default(), &err);
+    }
     let lib = DynamicLibrary::open(path).unwrap_or_else(|err| {
         let err = format!("couldn't load codegen backend {:?}: {:?}", path, err);
         early_error(ErrorOutputType::default(), &err);
@@ -259,7 +266,11 @@ pub fn get_codegen_backend(sopts: &config::Options) -> Box<dyn CodegenBackend> {
             .unwrap_or(DEFAULT_CODEGEN_BACKEND);
 
         let backend = match codegen_name {
-            filename if filename.contains('.') => load_backend_from_dylib(filename.as_ref()),
+            // Anything that looks like a path rather than a bare backend name is
+            // loaded directly instead of being looked up in the sysroot.
+            filename if filename.contains('.') || filename.contains(path::is_separator) => {
+                load_backend_from_dylib(filename.as_ref())
+            }
             codegen_name => get_builtin_codegen_backend(codegen_name),
         };
 
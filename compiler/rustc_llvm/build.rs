@@ -98,6 +98,13 @@ fn main() {
         (8, 0)
     };
 
+    // LLD is only built as a library when it is enabled as an LLVM project, in
+    // which case its libraries are installed next to LLVM's own.
+    let llvm_libdir = output(Command::new(&llvm_config).arg("--libdir"));
+    let llvm_libdir = Path::new(llvm_libdir.trim());
+    let has_lld = !is_crossed
+        && (llvm_libdir.join("liblldELF.a").exists() || llvm_libdir.join("lldELF.lib").exists());
+
     let required_components = &[
         "ipo",
         "bitreader",
@@ -107,6 +114,7 @@ fn main() {
         "lto",
         "coverage",
         "instrumentation",
+        "orcjit",
     ];
 
     let components = output(Command::new(&llvm_config).arg("--components"));
@@ -127,6 +135,10 @@ fn main() {
         println!("cargo:rustc-cfg=llvm_has_msp430_asm_parser");
     }
 
+    if has_lld {
+        println!("cargo:rustc-cfg=llvm_has_lld");
+    }
+
     // Link in our own LLVM shims, compiled with the same flags as LLVM
     let mut cmd = Command::new(&llvm_config);
     cmd.arg("--cxxflags");
@@ -163,6 +175,10 @@ fn main() {
         cfg.define(&flag, None);
     }
 
+    if has_lld {
+        cfg.define("LLVM_RUSTC_HAS_LLD", None);
+    }
+
     if tracked_env_var_os("LLVM_RUSTLLVM").is_some() {
         cfg.define("LLVM_RUSTLLVM", None);
     }
@@ -178,12 +194,21 @@ fn main() {
         .file("llvm-wrapper/ArchiveWrapper.cpp")
         .file("llvm-wrapper/CoverageMappingWrapper.cpp")
         .file("llvm-wrapper/Linker.cpp")
+        .file("llvm-wrapper/JITWrapper.cpp")
+        .file("llvm-wrapper/LLDWrapper.cpp")
         .cpp(true)
         .cpp_link_stdlib(None) // we handle this below
         .compile("llvm-wrapper");
 
     let (llvm_kind, llvm_link_arg) = detect_llvm_link();
 
+    // LLD's libraries depend on LLVM's, so they have to come first.
+    if has_lld {
+        for lib in &["lldELF", "lldCOFF", "lldWasm", "lldCommon"] {
+            println!("cargo:rustc-link-lib=static={}", lib);
+        }
+    }
+
     // Link in all LLVM libraries, if we're using the "wrong" llvm-config then
     // we don't pick up system libs because unfortunately they're for the host
     // of llvm-config, not the target that we're attempting to link.
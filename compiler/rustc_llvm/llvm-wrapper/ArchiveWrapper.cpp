@@ -2,6 +2,7 @@
 
 #include "llvm/Object/Archive.h"
 #include "llvm/Object/ArchiveWriter.h"
+#include "llvm/Object/COFFImportFile.h"
 #include "llvm/Support/Path.h"
 
 using namespace llvm;
@@ -224,3 +225,52 @@ LLVMRustWriteArchive(char *Dst, size_t NumMembers,
 
   return LLVMRustResult::Failure;
 }
+
+// Mirrors `LLVMRustCOFFShortExport` on the Rust side.
+struct LLVMRustCOFFShortExport {
+  const char *name;
+  // Null if calls reference `name` itself.
+  const char *symbol_name;
+  bool ordinal_present;
+  // Only meaningful if `ordinal_present` is true.
+  uint16_t ordinal;
+};
+
+// Writes a COFF import library for the DLL `ImportName`, exporting `Exports`,
+// to `Path`. Used for `#[link(kind = "raw-dylib")]`.
+extern "C" LLVMRustResult
+LLVMRustWriteImportLibrary(const char *ImportName, const char *Path,
+                           const LLVMRustCOFFShortExport *Exports,
+                           size_t NumExports, uint16_t Machine, bool MinGW) {
+  std::vector<COFFShortExport> ConvertedExports;
+  ConvertedExports.reserve(NumExports);
+
+  for (size_t I = 0; I < NumExports; ++I) {
+    bool OrdinalPresent = Exports[I].ordinal_present;
+    uint16_t Ordinal = OrdinalPresent ? Exports[I].ordinal : 0;
+    // With a `SymbolName` differing from `Name`, the import is looked up in
+    // the DLL by its undecorated name.
+    const char *SymbolName =
+        Exports[I].symbol_name ? Exports[I].symbol_name : "";
+    ConvertedExports.push_back(COFFShortExport{
+        Exports[I].name, // Name
+        std::string{},   // ExtName
+        SymbolName,      // SymbolName
+        std::string{},   // AliasTarget
+        Ordinal,         // Ordinal
+        OrdinalPresent,  // Noname
+        false,           // Data
+        false,           // Private
+        false            // Constant
+    });
+  }
+
+  auto Error = writeImportLibrary(ImportName, Path, ConvertedExports,
+                                  static_cast<COFF::MachineTypes>(Machine),
+                                  MinGW);
+  if (Error) {
+    LLVMRustSetLastError(toString(std::move(Error)).c_str());
+    return LLVMRustResult::Failure;
+  }
+  return LLVMRustResult::Success;
+}
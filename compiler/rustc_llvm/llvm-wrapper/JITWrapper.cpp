@@ -0,0 +1,76 @@
+#include "LLVMWrapper.h"
+
+#if LLVM_VERSION_GE(11, 0)
+#include "llvm/ExecutionEngine/Orc/ExecutionUtils.h"
+#include "llvm/ExecutionEngine/Orc/LLJIT.h"
+#include "llvm/Support/MemoryBuffer.h"
+#endif
+
+using namespace llvm;
+
+// Loads the given object files into an ORC JIT for the host, resolving their
+// undefined symbols against the given archives, then the given shared
+// libraries, then the current process, and calls the C `main` they define.
+//
+// Returns false and sets the last error if anything fails before `main` runs.
+extern "C" bool
+LLVMRustRunJIT(const char **Objects, size_t NumObjects,
+               const char **Archives, size_t NumArchives,
+               const char **Dylibs, size_t NumDylibs,
+               const char *ProgramName, int *ExitCode) {
+#if LLVM_VERSION_GE(11, 0)
+  using namespace llvm::orc;
+
+  auto Fail = [](Error Err) {
+    LLVMRustSetLastError(toString(std::move(Err)).c_str());
+    return false;
+  };
+
+  auto JITOrErr = LLJITBuilder().create();
+  if (!JITOrErr)
+    return Fail(JITOrErr.takeError());
+  std::unique_ptr<LLJIT> J = std::move(*JITOrErr);
+  JITDylib &MainJD = J->getMainJITDylib();
+  char Prefix = J->getDataLayout().getGlobalPrefix();
+
+  // Generators are consulted in order, so the program's own dependencies take
+  // precedence over whatever the compiler process happens to have loaded.
+  for (size_t I = 0; I < NumArchives; I++) {
+    auto Gen = StaticLibraryDefinitionGenerator::Load(J->getObjLinkingLayer(),
+                                                      Archives[I]);
+    if (!Gen)
+      return Fail(Gen.takeError());
+    MainJD.addGenerator(std::move(*Gen));
+  }
+  for (size_t I = 0; I < NumDylibs; I++) {
+    auto Gen = DynamicLibrarySearchGenerator::Load(Dylibs[I], Prefix);
+    if (!Gen)
+      return Fail(Gen.takeError());
+    MainJD.addGenerator(std::move(*Gen));
+  }
+  auto ProcessGen = DynamicLibrarySearchGenerator::GetForCurrentProcess(Prefix);
+  if (!ProcessGen)
+    return Fail(ProcessGen.takeError());
+  MainJD.addGenerator(std::move(*ProcessGen));
+
+  for (size_t I = 0; I < NumObjects; I++) {
+    auto Buf = MemoryBuffer::getFile(Objects[I]);
+    if (!Buf)
+      return Fail(errorCodeToError(Buf.getError()));
+    if (Error Err = J->addObjectFile(std::move(*Buf)))
+      return Fail(std::move(Err));
+  }
+
+  auto MainSym = J->lookup("main");
+  if (!MainSym)
+    return Fail(MainSym.takeError());
+  auto *Main = (int (*)(int, char **))MainSym->getAddress();
+
+  char *Argv[] = {const_cast<char *>(ProgramName), nullptr};
+  *ExitCode = Main(1, Argv);
+  return true;
+#else
+  LLVMRustSetLastError("JIT execution requires LLVM 11 or later");
+  return false;
+#endif
+}
@@ -0,0 +1,60 @@
+#include "LLVMWrapper.h"
+
+#ifdef LLVM_RUSTC_HAS_LLD
+#include "lld/Common/Driver.h"
+#include "lld/Common/ErrorHandler.h"
+#endif
+
+using namespace llvm;
+
+enum class LLVMRustLLDFlavor {
+  Elf,
+  Coff,
+  Wasm,
+};
+
+// Runs the LLD driver for the given flavor inside this process, with `Args`
+// being the arguments that would otherwise be passed to `rust-lld` after its
+// `-flavor` argument.
+//
+// Everything LLD prints goes to `Out` and `Err` instead of the process's own
+// standard streams. Returns whether the link succeeded.
+extern "C" bool
+LLVMRustLinkWithLLD(LLVMRustLLDFlavor Flavor, const char **Args, size_t NumArgs,
+                    RustStringRef Out, RustStringRef Err) {
+  RawRustStringOstream OutOS(Out);
+  RawRustStringOstream ErrOS(Err);
+#ifdef LLVM_RUSTC_HAS_LLD
+  const char *Driver;
+  bool (*Link)(ArrayRef<const char *>, bool, raw_ostream &, raw_ostream &);
+  switch (Flavor) {
+  case LLVMRustLLDFlavor::Elf:
+    Driver = "ld.lld";
+    Link = lld::elf::link;
+    break;
+  case LLVMRustLLDFlavor::Coff:
+    Driver = "lld-link";
+    Link = lld::coff::link;
+    break;
+  case LLVMRustLLDFlavor::Wasm:
+    Driver = "wasm-ld";
+    Link = lld::wasm::link;
+    break;
+  default:
+    report_fatal_error("Bad LLDFlavor.");
+  }
+
+  std::vector<const char *> Argv;
+  Argv.push_back(Driver);
+  Argv.insert(Argv.end(), Args, Args + NumArgs);
+
+  // LLD keeps its error count across links, and the previous link of this
+  // session may have failed.
+  lld::errorHandler().errorCount = 0;
+  // Never let LLD call `exit` on the compiler process.
+  return Link(Argv, /* CanExitEarly */ false, OutOS, ErrOS);
+#else
+  ErrOS << "rustc: error: rustc was not built with LLD\n";
+  return false;
+#endif
+}
@@ -4,6 +4,7 @@
 #include <set>
 
 #include "LLVMWrapper.h"
+#include "llvm/ADT/Statistic.h"
 
 #include "llvm/Analysis/TargetLibraryInfo.h"
 #include "llvm/Analysis/TargetTransformInfo.h"
@@ -26,6 +27,9 @@
 #include "llvm/Transforms/IPO/PassManagerBuilder.h"
 #include "llvm/Transforms/IPO/AlwaysInliner.h"
 #include "llvm/Transforms/IPO/FunctionImport.h"
+#if LLVM_VERSION_GE(11, 0)
+#include "llvm/Transforms/IPO/MergeFunctions.h"
+#endif
 #include "llvm/Transforms/Utils/FunctionImportUtils.h"
 #include "llvm/LTO/LTO.h"
 #include "llvm-c/Transforms/PassManagerBuilder.h"
@@ -33,6 +37,7 @@
 #include "llvm/Transforms/Instrumentation.h"
 #if LLVM_VERSION_GE(9, 0)
 #include "llvm/Transforms/Instrumentation/AddressSanitizer.h"
+#include "llvm/Transforms/Instrumentation/GCOVProfiler.h"
 #include "llvm/Support/TimeProfiler.h"
 #endif
 #include "llvm/Transforms/Instrumentation/ThreadSanitizer.h"
@@ -89,6 +94,23 @@ extern "C" void LLVMTimeTraceProfilerFinish(const char* FileName) {
 #endif
 }
 
+// Makes LLVM collect its statistics counters without printing them on exit.
+extern "C" void LLVMRustEnableStatistics() {
+  EnableStatistics(/* DoPrintOnExit */ false);
+}
+
+extern "C" void LLVMRustResetStatistics() {
+  ResetStatistics();
+}
+
+// Prints the statistics counters collected since the last reset as a JSON
+// object, then resets them.
+extern "C" void LLVMRustTakeStatisticsJSON(RustStringRef Str) {
+  RawRustStringOstream OS(Str);
+  PrintStatisticsJSON(OS);
+  ResetStatistics();
+}
+
 enum class LLVMRustPassKind {
   Other,
   Function,
@@ -438,6 +460,25 @@ extern "C" void LLVMRustPrintTargetFeatures(LLVMTargetMachineRef TM) {
          "target-feature=+feature1,-feature2\n\n");
 }
 
+// Returns whether `Feature`, without a `+` or `-` sign, is in the feature
+// table of the target. This doesn't take a target machine, as creating one
+// with an unknown feature makes LLVM complain about it.
+extern "C" bool LLVMRustIsKnownFeature(const char *TripleStr,
+                                       const char *Feature) {
+  std::string Error;
+  Triple Trip(Triple::normalize(TripleStr));
+  const llvm::Target *TheTarget =
+      TargetRegistry::lookupTarget(Trip.getTriple(), Error);
+  if (TheTarget == nullptr)
+    return true;
+  std::unique_ptr<MCSubtargetInfo> MCInfo(
+      TheTarget->createMCSubtargetInfo(Trip.getTriple(), "", ""));
+  for (auto &Feat : MCInfo->getFeatureTable())
+    if (StringRef(Feat.Key) == Feature)
+      return true;
+  return false;
+}
+
 #else
 
 extern "C" void LLVMRustPrintTargetCPUs(LLVMTargetMachineRef) {
@@ -447,6 +488,11 @@ extern "C" void LLVMRustPrintTargetCPUs(LLVMTargetMachineRef) {
 extern "C" void LLVMRustPrintTargetFeatures(LLVMTargetMachineRef) {
   printf("Target features help is not supported by this LLVM version.\n\n");
 }
+
+// Without the feature table every feature has to be assumed to be known.
+extern "C" bool LLVMRustIsKnownFeature(const char *, const char *) {
+  return true;
+}
 #endif
 
 extern "C" const char* LLVMRustGetHostCPUName(size_t *len) {
@@ -465,6 +511,7 @@ extern "C" LLVMTargetMachineRef LLVMRustCreateTargetMachine(
     bool Singlethread,
     bool AsmComments,
     bool EmitStackSizeSection,
+    bool EmitAddrsig,
     bool RelaxELFRelocations,
     bool UseInitArray) {
 
@@ -508,6 +555,7 @@ extern "C" LLVMTargetMachineRef LLVMRustCreateTargetMachine(
   }
 
   Options.EmitStackSizeSection = EmitStackSizeSection;
+  Options.EmitAddrsig = EmitAddrsig;
 
   TargetMachine *TM = TheTarget->createTargetMachine(
       Trip.getTriple(), CPU, Feature, Options, RM, CM, OptLevel);
@@ -734,7 +782,7 @@ LLVMRustOptimizeWithNewPassManager(
     bool NoPrepopulatePasses, bool VerifyIR, bool UseThinLTOBuffers,
     bool MergeFunctions, bool UnrollLoops, bool SLPVectorize, bool LoopVectorize,
     bool DisableSimplifyLibCalls, bool EmitLifetimeMarkers,
-    LLVMRustSanitizerOptions *SanitizerOptions,
+    LLVMRustSanitizerOptions *SanitizerOptions, bool InsertGCOVProfiling,
     const char *PGOGenPath, const char *PGOUsePath,
     void* LlvmSelfProfiler,
     LLVMRustSelfProfileBeforePassCallback BeforePassCallback,
@@ -744,8 +792,11 @@ LLVMRustOptimizeWithNewPassManager(
   TargetMachine *TM = unwrap(TMRef);
   PassBuilder::OptimizationLevel OptLevel = fromRust(OptLevelRust);
 
-  // FIXME: MergeFunctions is not supported by NewPM yet.
+#if !LLVM_VERSION_GE(11, 0)
+  // FIXME: MergeFunctions is only supported with the module-level
+  // OptimizerLastEP callbacks of LLVM 11.
   (void) MergeFunctions;
+#endif
 
   PipelineTuningOptions PTO;
   PTO.LoopUnrolling = UnrollLoops;
@@ -810,6 +861,25 @@ LLVMRustOptimizeWithNewPassManager(
     });
   }
 
+  if (InsertGCOVProfiling) {
+    PipelineStartEPCallbacks.push_back([](ModulePassManager &MPM) {
+        MPM.addPass(GCOVProfilerPass(GCOVOptions::getDefault()));
+    });
+  }
+
+#if LLVM_VERSION_GE(11, 0)
+  // Merge functions before any sanitizer instruments them, like the legacy
+  // pass manager does. Whether it uses aliases or trampolines is decided by
+  // `-mergefunc-use-aliases`.
+  if (MergeFunctions) {
+    OptimizerLastEPCallbacks.push_back(
+      [](ModulePassManager &MPM, PassBuilder::OptimizationLevel Level) {
+        MPM.addPass(MergeFunctionsPass());
+      }
+    );
+  }
+#endif
+
   if (SanitizerOptions) {
     if (SanitizerOptions->SanitizeMemory) {
       MemorySanitizerOptions Options(
@@ -5,6 +5,7 @@
 #include "llvm/IR/GlobalVariable.h"
 #include "llvm/IR/Instructions.h"
 #include "llvm/IR/Intrinsics.h"
+#include "llvm/IR/MDBuilder.h"
 #include "llvm/Object/Archive.h"
 #include "llvm/Object/ObjectFile.h"
 #include "llvm/Bitcode/BitcodeWriterPass.h"
@@ -136,6 +137,23 @@ LLVMRustGetOrInsertGlobal(LLVMModuleRef M, const char *Name, size_t NameLen, LLV
   return wrap(unwrap(M)->getOrInsertGlobal(NameRef, unwrap(Ty)));
 }
 
+extern "C" LLVMValueRef
+LLVMRustGetOrInsertGlobalInAddrSpace(LLVMModuleRef M, const char *Name,
+                                     size_t NameLen, LLVMTypeRef Ty,
+                                     unsigned AddrSpace) {
+  Module *Mod = unwrap(M);
+  StringRef NameRef(Name, NameLen);
+  return wrap(Mod->getOrInsertGlobal(NameRef, unwrap(Ty), [=] {
+    return new GlobalVariable(*Mod, unwrap(Ty), false,
+                              GlobalValue::ExternalLinkage, nullptr, NameRef,
+                              nullptr, GlobalValue::NotThreadLocal, AddrSpace);
+  }));
+}
+
+extern "C" LLVMValueRef LLVMRustStripPointerCasts(LLVMValueRef V) {
+  return wrap(unwrap(V)->stripPointerCasts());
+}
+
 extern "C" LLVMValueRef
 LLVMRustInsertPrivateGlobal(LLVMModuleRef M, LLVMTypeRef Ty) {
   return wrap(new GlobalVariable(*unwrap(M),
@@ -218,6 +236,13 @@ extern "C" void LLVMRustAddCallSiteAttribute(LLVMValueRef Instr, unsigned Index,
   Call->addAttribute(Index, Attr);
 }
 
+extern "C" void LLVMRustAddCallSiteAttrString(LLVMValueRef Instr, unsigned Index,
+                                              const char *Name) {
+  CallBase *Call = unwrap<CallBase>(Instr);
+  Attribute Attr = Attribute::get(Call->getContext(), Name);
+  Call->addAttribute(Index, Attr);
+}
+
 extern "C" void LLVMRustAddAlignmentCallSiteAttr(LLVMValueRef Instr,
                                                  unsigned Index,
                                                  uint32_t Bytes) {
@@ -293,6 +318,14 @@ extern "C" void LLVMRustAddDereferenceableOrNullAttr(LLVMValueRef Fn,
   A->addAttributes(Index, B);
 }
 
+extern "C" void LLVMRustAddAllocSizeAttr(LLVMValueRef Fn,
+                                         unsigned ElemSizeArg) {
+  Function *A = unwrap<Function>(Fn);
+  AttrBuilder B;
+  B.addAllocSizeAttr(ElemSizeArg, None);
+  A->addAttributes(AttributeList::FunctionIndex, B);
+}
+
 extern "C" void LLVMRustAddByValAttr(LLVMValueRef Fn, unsigned Index,
                                      LLVMTypeRef Ty) {
   Function *F = unwrap<Function>(Fn);
@@ -1388,13 +1421,64 @@ extern "C" void LLVMRustFreeOperandBundleDef(OperandBundleDef *Bundle) {
 
 extern "C" LLVMValueRef LLVMRustBuildCall(LLVMBuilderRef B, LLVMValueRef Fn,
                                           LLVMValueRef *Args, unsigned NumArgs,
-                                          OperandBundleDef *Bundle) {
+                                          OperandBundleDef **Bundles,
+                                          unsigned NumBundles) {
   Value *Callee = unwrap(Fn);
   FunctionType *FTy = cast<FunctionType>(Callee->getType()->getPointerElementType());
-  unsigned Len = Bundle ? 1 : 0;
-  ArrayRef<OperandBundleDef> Bundles = makeArrayRef(Bundle, Len);
+  SmallVector<OperandBundleDef, 2> OpBundles;
+  for (unsigned I = 0; I < NumBundles; ++I)
+    OpBundles.push_back(*Bundles[I]);
   return wrap(unwrap(B)->CreateCall(
-      FTy, Callee, makeArrayRef(unwrap(Args), NumArgs), Bundles));
+      FTy, Callee, makeArrayRef(unwrap(Args), NumArgs), OpBundles));
+}
+
+// Alias scopes and their domains are self-referential, so that the inliner
+// gives each inlined copy of a function fresh scopes.
+extern "C" LLVMValueRef
+LLVMRustCreateAnonymousAliasScopeDomain(LLVMContextRef C, const char *Name,
+                                        size_t NameLen) {
+  MDBuilder MDB(*unwrap(C));
+  MDNode *Domain =
+      MDB.createAnonymousAliasScopeDomain(StringRef(Name, NameLen));
+  return wrap(MetadataAsValue::get(*unwrap(C), Domain));
+}
+
+extern "C" LLVMValueRef
+LLVMRustCreateAnonymousAliasScope(LLVMContextRef C, LLVMValueRef Domain,
+                                  const char *Name, size_t NameLen) {
+  MDBuilder MDB(*unwrap(C));
+  MDNode *DomainMD =
+      cast<MDNode>(unwrap<MetadataAsValue>(Domain)->getMetadata());
+  MDNode *Scope =
+      MDB.createAnonymousAliasScope(DomainMD, StringRef(Name, NameLen));
+  return wrap(MetadataAsValue::get(*unwrap(C), Scope));
+}
+
+enum class LLVMRustTailCallKind {
+  None,
+  Tail,
+  MustTail,
+  NoTail,
+};
+
+static CallInst::TailCallKind fromRust(LLVMRustTailCallKind Kind) {
+  switch (Kind) {
+  case LLVMRustTailCallKind::None:
+    return CallInst::TCK_None;
+  case LLVMRustTailCallKind::Tail:
+    return CallInst::TCK_Tail;
+  case LLVMRustTailCallKind::MustTail:
+    return CallInst::TCK_MustTail;
+  case LLVMRustTailCallKind::NoTail:
+    return CallInst::TCK_NoTail;
+  default:
+    report_fatal_error("bad TailCallKind.");
+  }
+}
+
+extern "C" void LLVMRustSetTailCallKind(LLVMValueRef Call,
+                                        LLVMRustTailCallKind Kind) {
+  unwrap<CallInst>(Call)->setTailCallKind(fromRust(Kind));
 }
 
 extern "C" LLVMValueRef LLVMRustGetInstrProfIncrementIntrinsic(LLVMModuleRef M) {
@@ -1449,18 +1533,51 @@ extern "C" LLVMValueRef LLVMRustBuildMemSet(LLVMBuilderRef B,
 #endif
 }
 
+extern "C" LLVMValueRef
+LLVMRustBuildElementUnorderedAtomicMemCpy(LLVMBuilderRef B,
+                                          LLVMValueRef Dst, unsigned DstAlign,
+                                          LLVMValueRef Src, unsigned SrcAlign,
+                                          LLVMValueRef Size, unsigned ElementSize) {
+#if LLVM_VERSION_GE(11, 0)
+  return wrap(unwrap(B)->CreateElementUnorderedAtomicMemCpy(
+      unwrap(Dst), Align(DstAlign),
+      unwrap(Src), Align(SrcAlign),
+      unwrap(Size), ElementSize));
+#else
+  return wrap(unwrap(B)->CreateElementUnorderedAtomicMemCpy(
+      unwrap(Dst), DstAlign,
+      unwrap(Src), SrcAlign,
+      unwrap(Size), ElementSize));
+#endif
+}
+
+extern "C" LLVMValueRef
+LLVMRustBuildElementUnorderedAtomicMemSet(LLVMBuilderRef B,
+                                          LLVMValueRef Dst, unsigned DstAlign,
+                                          LLVMValueRef Val,
+                                          LLVMValueRef Size, unsigned ElementSize) {
+#if LLVM_VERSION_GE(11, 0)
+  return wrap(unwrap(B)->CreateElementUnorderedAtomicMemSet(
+      unwrap(Dst), unwrap(Val), unwrap(Size), Align(DstAlign), ElementSize));
+#else
+  return wrap(unwrap(B)->CreateElementUnorderedAtomicMemSet(
+      unwrap(Dst), unwrap(Val), unwrap(Size), DstAlign, ElementSize));
+#endif
+}
+
 extern "C" LLVMValueRef
 LLVMRustBuildInvoke(LLVMBuilderRef B, LLVMValueRef Fn, LLVMValueRef *Args,
                     unsigned NumArgs, LLVMBasicBlockRef Then,
-                    LLVMBasicBlockRef Catch, OperandBundleDef *Bundle,
-                    const char *Name) {
+                    LLVMBasicBlockRef Catch, OperandBundleDef **Bundles,
+                    unsigned NumBundles, const char *Name) {
   Value *Callee = unwrap(Fn);
   FunctionType *FTy = cast<FunctionType>(Callee->getType()->getPointerElementType());
-  unsigned Len = Bundle ? 1 : 0;
-  ArrayRef<OperandBundleDef> Bundles = makeArrayRef(Bundle, Len);
+  SmallVector<OperandBundleDef, 2> OpBundles;
+  for (unsigned I = 0; I < NumBundles; ++I)
+    OpBundles.push_back(*Bundles[I]);
   return wrap(unwrap(B)->CreateInvoke(FTy, Callee, unwrap(Then), unwrap(Catch),
                                       makeArrayRef(unwrap(Args), NumArgs),
-                                      Bundles, Name));
+                                      OpBundles, Name));
 }
 
 extern "C" void LLVMRustPositionBuilderAtStart(LLVMBuilderRef B,
@@ -1484,6 +1601,28 @@ extern "C" void LLVMRustUnsetComdat(LLVMValueRef V) {
   GV->setComdat(nullptr);
 }
 
+// Unlike `LLVMGlobalSetMetadata`, keeps existing attachments of the same kind,
+// as a vtable may belong to several `!type`s.
+extern "C" void LLVMRustGlobalAddMetadata(LLVMValueRef V, unsigned KindID,
+                                          LLVMValueRef MD) {
+  MDNode *Node = cast<MDNode>(unwrap<MetadataAsValue>(MD)->getMetadata());
+  unwrap<GlobalObject>(V)->addMetadata(KindID, *Node);
+}
+
+// A vtable global may be shared by several trait objects, in which case it
+// keeps the widest of their `!vcall_visibility`s.
+extern "C" void LLVMRustGlobalSetVCallVisibility(LLVMValueRef V,
+                                                 unsigned Visibility) {
+#if LLVM_VERSION_GE(10, 0)
+  GlobalObject *GO = unwrap<GlobalObject>(V);
+  if (GO->getMetadata(LLVMContext::MD_vcall_visibility))
+    Visibility = std::min(Visibility, (unsigned)GO->getVCallVisibility());
+  GO->setVCallVisibilityMetadata((GlobalObject::VCallVisibility)Visibility);
+#else
+  report_fatal_error("virtual function elimination requires LLVM 10 or later");
+#endif
+}
+
 enum class LLVMRustLinkage {
   ExternalLinkage = 0,
   AvailableExternallyLinkage = 1,
@@ -1669,6 +1808,30 @@ LLVMRustModuleCost(LLVMModuleRef M) {
   return std::distance(std::begin(f), std::end(f));
 }
 
+// Returns the number of elements of a fixed-width vector type, or the known
+// minimum number of elements of a scalable one.
+extern "C" unsigned LLVMRustGetVectorMinNumElements(LLVMTypeRef Ty) {
+#if LLVM_VERSION_GE(12, 0)
+  return unwrap<VectorType>(Ty)->getElementCount().getKnownMinValue();
+#else
+  return unwrap<VectorType>(Ty)->getElementCount().Min;
+#endif
+}
+
+extern "C" LLVMValueRef
+LLVMRustBuildScalableVectorSplat(LLVMBuilderRef B, unsigned MinNumElts,
+                                 LLVMValueRef Elt) {
+#if LLVM_VERSION_GE(12, 0)
+  return wrap(unwrap(B)->CreateVectorSplat(
+      ElementCount::getScalable(MinNumElts), unwrap(Elt)));
+#elif LLVM_VERSION_GE(11, 0)
+  return wrap(unwrap(B)->CreateVectorSplat(
+      ElementCount(MinNumElts, /*Scalable=*/true), unwrap(Elt)));
+#else
+  report_fatal_error("scalable vector splats require LLVM 11 or later");
+#endif
+}
+
 // Vector reductions:
 extern "C" LLVMValueRef
 LLVMRustBuildVectorReduceFAdd(LLVMBuilderRef B, LLVMValueRef Acc, LLVMValueRef Src) {
@@ -19,6 +19,9 @@ impl RustString {
     }
 }
 
+/// Whether LLD is linked in, so that `LLVMRustLinkWithLLD` can run it in-process.
+pub const HAS_LLD: bool = cfg!(llvm_has_lld);
+
 /// Appending to a Rust string -- used by RawRustStringOstream.
 #[no_mangle]
 pub unsafe extern "C" fn LLVMRustStringWriteImpl(
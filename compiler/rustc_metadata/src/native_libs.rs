@@ -3,8 +3,9 @@ use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::struct_span_err;
 use rustc_hir as hir;
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
-use rustc_middle::middle::cstore::NativeLib;
-use rustc_middle::ty::TyCtxt;
+use rustc_hir::def_id::DefId;
+use rustc_middle::middle::cstore::{DllCallingConvention, DllImport, NativeLib};
+use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::parse::feature_err;
 use rustc_session::utils::NativeLibKind;
 use rustc_session::Session;
@@ -55,6 +56,7 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
                 cfg: None,
                 foreign_module: Some(self.tcx.hir().local_def_id(it.hir_id).to_def_id()),
                 wasm_import_module: None,
+                dll_imports: Vec::new(),
             };
             let mut kind_specified = false;
 
@@ -121,6 +123,11 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
                 .span_label(m.span, "missing `name` argument")
                 .emit();
             }
+
+            if lib.kind == NativeLibKind::RawDylib {
+                lib.dll_imports =
+                    fm.items.iter().filter_map(|item| self.build_dll_import(fm.abi, item)).collect();
+            }
             self.register_native_lib(Some(m.span), lib);
         }
     }
@@ -130,6 +137,64 @@ impl ItemLikeVisitor<'tcx> for Collector<'tcx> {
 }
 
 impl Collector<'tcx> {
+    fn build_dll_import(&self, abi: Abi, item: &hir::ForeignItem<'_>) -> Option<DllImport> {
+        match item.kind {
+            hir::ForeignItemKind::Fn(..) => {}
+            hir::ForeignItemKind::Static(..) => {
+                self.tcx.sess.span_err(item.span, "`raw-dylib` libraries can only import functions");
+                return None;
+            }
+            hir::ForeignItemKind::Type => return None,
+        }
+        let def_id = self.tcx.hir().local_def_id(item.hir_id).to_def_id();
+        let calling_convention = if self.tcx.sess.target.arch == "x86" {
+            match abi {
+                Abi::C | Abi::Cdecl => DllCallingConvention::C,
+                Abi::Stdcall | Abi::System => {
+                    DllCallingConvention::Stdcall(self.i686_arg_list_size(def_id, item.span)?)
+                }
+                Abi::Fastcall => {
+                    DllCallingConvention::Fastcall(self.i686_arg_list_size(def_id, item.span)?)
+                }
+                _ => {
+                    let msg = format!(
+                        "ABI `{}` is not supported by `raw-dylib` libraries on x86",
+                        abi.name()
+                    );
+                    self.tcx.sess.span_err(item.span, &msg);
+                    return None;
+                }
+            }
+        } else {
+            DllCallingConvention::C
+        };
+        let codegen_fn_attrs = self.tcx.codegen_fn_attrs(def_id);
+        Some(DllImport {
+            name: codegen_fn_attrs.link_name.unwrap_or(item.ident.name),
+            ordinal: codegen_fn_attrs.link_ordinal,
+            calling_convention,
+        })
+    }
+
+    /// Returns the size of the arguments of the foreign function `def_id`, as
+    /// encoded in the names of stdcall and fastcall functions on i686, where
+    /// each argument takes a multiple of 4 bytes. Returns `None` after reporting
+    /// an error if the layout of an argument can't be computed.
+    fn i686_arg_list_size(&self, def_id: DefId, span: Span) -> Option<usize> {
+        let sig = self.tcx.erase_late_bound_regions(&self.tcx.fn_sig(def_id));
+        let mut size = 0;
+        for &ty in sig.inputs() {
+            match self.tcx.layout_of(ty::ParamEnv::empty().and(ty)) {
+                Ok(layout) => size += (layout.size.bytes() as usize + 3) & !3,
+                Err(err) => {
+                    self.tcx.sess.span_err(span, &err.to_string());
+                    return None;
+                }
+            }
+        }
+        Some(size)
+    }
+
     fn register_native_lib(&mut self, span: Option<Span>, lib: NativeLib) {
         if lib.name.as_ref().map(|&s| s == kw::Invalid).unwrap_or(false) {
             match span {
@@ -257,6 +322,7 @@ impl Collector<'tcx> {
                     cfg: None,
                     foreign_module: None,
                     wasm_import_module: None,
+                    dll_imports: Vec::new(),
                 };
                 self.register_native_lib(None, lib);
             } else {
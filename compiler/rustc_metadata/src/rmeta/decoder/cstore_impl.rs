@@ -20,7 +20,7 @@ use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::utils::NativeLibKind;
 use rustc_session::{CrateDisambiguator, Session};
 use rustc_span::source_map::{Span, Spanned};
-use rustc_span::symbol::Symbol;
+use rustc_span::symbol::{sym, Symbol};
 
 use rustc_data_structures::sync::Lrc;
 use rustc_span::ExpnId;
@@ -156,6 +156,8 @@ provide! { <'tcx> tcx, def_id, other, cdata,
         r.map(|c| &*tcx.arena.alloc(c))
     }
     is_no_builtins => { cdata.root.no_builtins }
+    is_mir_only_rlib => { cdata.root.mir_only_rlib }
+    needs_atomic_libcalls => { cdata.root.needs_atomic_libcalls }
     symbol_mangling_version => { cdata.root.symbol_mangling_version }
     impl_defaultness => { cdata.get_impl_defaultness(def_id.index) }
     reachable_non_generics => {
@@ -288,6 +290,10 @@ pub fn provide(providers: &mut Providers) {
             assert_eq!(cnum, LOCAL_CRATE);
             Lrc::new(link_args::collect(tcx))
         },
+        is_no_builtins: |tcx, cnum| {
+            assert_eq!(cnum, LOCAL_CRATE);
+            tcx.sess.contains_name(&tcx.hir().krate().item.attrs, sym::no_builtins)
+        },
 
         // Returns a map from a sufficiently visible external item (i.e., an
         // external item that is visible from at least one local module) to a
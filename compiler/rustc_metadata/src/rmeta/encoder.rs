@@ -659,7 +659,10 @@ impl<'a, 'tcx> EncodeContext<'a, 'tcx> {
             compiler_builtins: tcx.sess.contains_name(&attrs, sym::compiler_builtins),
             needs_allocator: tcx.sess.contains_name(&attrs, sym::needs_allocator),
             needs_panic_runtime: tcx.sess.contains_name(&attrs, sym::needs_panic_runtime),
-            no_builtins: tcx.sess.contains_name(&attrs, sym::no_builtins),
+            no_builtins: tcx.is_no_builtins(LOCAL_CRATE),
+            mir_only_rlib: tcx.sess.is_mir_only_rlib(),
+            needs_atomic_libcalls: tcx.sess.opts.output_types.should_codegen()
+                && tcx.needs_atomic_libcalls(LOCAL_CRATE),
             panic_runtime: tcx.sess.contains_name(&attrs, sym::panic_runtime),
             profiler_runtime: tcx.sess.contains_name(&attrs, sym::profiler_runtime),
             symbol_mangling_version: tcx.sess.opts.debugging_opts.symbol_mangling_version,
@@ -1100,7 +1103,8 @@ impl EncodeContext<'a, 'tcx> {
                     || tcx.codegen_fn_attrs(def_id).requests_inline())
                     && !self.metadata_output_only();
                 let is_const_fn = sig.header.constness == hir::Constness::Const;
-                let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir;
+                let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir
+                    || self.tcx.sess.is_mir_only_rlib();
                 needs_inline || is_const_fn || always_encode_mir
             }
             hir::ImplItemKind::TyAlias(..) => false,
@@ -1416,7 +1420,8 @@ impl EncodeContext<'a, 'tcx> {
                 let needs_inline = (generics.requires_monomorphization(tcx)
                     || tcx.codegen_fn_attrs(def_id).requests_inline())
                     && !self.metadata_output_only();
-                let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir;
+                let always_encode_mir = self.tcx.sess.opts.debugging_opts.always_encode_mir
+                    || self.tcx.sess.is_mir_only_rlib();
                 needs_inline || sig.header.constness == hir::Constness::Const || always_encode_mir
             }
             _ => false,
@@ -234,6 +234,8 @@ crate struct CrateRoot<'tcx> {
     needs_allocator: bool,
     needs_panic_runtime: bool,
     no_builtins: bool,
+    mir_only_rlib: bool,
+    needs_atomic_libcalls: bool,
     panic_runtime: bool,
     profiler_runtime: bool,
     symbol_mangling_version: SymbolManglingVersion,
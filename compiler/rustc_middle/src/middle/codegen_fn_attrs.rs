@@ -22,7 +22,7 @@ pub struct CodegenFnAttrs {
     /// imported function has in the dynamic library. Note that this must not
     /// be set when `link_name` is set. This is for foreign items with the
     /// "raw-dylib" kind.
-    pub link_ordinal: Option<usize>,
+    pub link_ordinal: Option<u16>,
     /// The `#[target_feature(enable = "...")]` attribute and the enabled
     /// features (only enabled features are supported right now).
     pub target_features: Vec<Symbol>,
@@ -38,6 +38,26 @@ pub struct CodegenFnAttrs {
     /// be generated against a specific instruction set. Only usable on architectures which allow
     /// switching between multiple instruction sets.
     pub instruction_set: Option<InstructionSetAttr>,
+    /// The `#[address_space = "..."]` attribute. Indicates the GPU address
+    /// space a static is placed in instead of the global one.
+    pub address_space: Option<GpuAddressSpace>,
+    /// The `#[rustc_operand_bundle = "..."]` attribute. Names an operand bundle, such as
+    /// `deopt`, attached without inputs to every call the function makes.
+    pub operand_bundle: Option<Symbol>,
+}
+
+/// The address spaces `#[address_space = "..."]` can place a static in, named
+/// after their CUDA counterparts.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, HashStable)]
+pub enum GpuAddressSpace {
+    /// Memory shared by the threads of a block. It is uninitialized when the
+    /// kernel starts.
+    Shared,
+    /// Read-only memory, initialized by the host.
+    Constant,
+    /// Memory private to each thread. It is uninitialized when the kernel
+    /// starts.
+    Local,
 }
 
 bitflags! {
@@ -86,6 +106,9 @@ bitflags! {
         /// #[cmse_nonsecure_entry]: with a TrustZone-M extension, declare a
         /// function as an entry function from Non-Secure code.
         const CMSE_NONSECURE_ENTRY      = 1 << 14;
+        /// `#[rustc_must_tail]`: calls whose result the function returns right
+        /// away are guaranteed tail calls, so they don't grow the stack.
+        const MUST_TAIL                 = 1 << 15;
     }
 }
 
@@ -103,6 +126,8 @@ impl CodegenFnAttrs {
             link_section: None,
             no_sanitize: SanitizerSet::empty(),
             instruction_set: None,
+            address_space: None,
+            operand_bundle: None,
         }
     }
 
@@ -94,6 +94,26 @@ pub struct NativeLib {
     pub cfg: Option<ast::MetaItem>,
     pub foreign_module: Option<DefId>,
     pub wasm_import_module: Option<Symbol>,
+    /// The functions imported through a `kind = "raw-dylib"` library, from
+    /// which an import library is synthesized at link time.
+    pub dll_imports: Vec<DllImport>,
+}
+
+#[derive(Clone, Debug, Encodable, Decodable, HashStable)]
+pub struct DllImport {
+    pub name: Symbol,
+    pub ordinal: Option<u16>,
+    pub calling_convention: DllCallingConvention,
+}
+
+/// The calling convention of a function imported from a DLL, which on i686
+/// determines how its symbol name is decorated. The `usize` is the size of
+/// the function's arguments in bytes.
+#[derive(Clone, Copy, PartialEq, Debug, Encodable, Decodable, HashStable)]
+pub enum DllCallingConvention {
+    C,
+    Stdcall(usize),
+    Fastcall(usize),
 }
 
 #[derive(Clone, TyEncodable, TyDecodable, HashStable)]
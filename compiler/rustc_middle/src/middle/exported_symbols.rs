@@ -1,7 +1,9 @@
 use crate::ty::subst::SubstsRef;
 use crate::ty::{self, Ty, TyCtxt};
-use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
 use rustc_macros::HashStable;
+use std::hash::Hash;
 
 /// The SymbolExportLevel of a symbols specifies from which kinds of crates
 /// the symbol will be exported. `C` symbols will be exported from any
@@ -53,3 +55,46 @@ pub fn metadata_symbol_name(tcx: TyCtxt<'_>) -> String {
         tcx.crate_disambiguator(LOCAL_CRATE).to_fingerprint().to_hex()
     )
 }
+
+/// The symbol `-Z embed-abi-checksum` stores the result of `abi_checksum` in. Its name
+/// doesn't depend on the crate, so that code loading a binary can look it up with `dlsym`.
+pub const ABI_CHECKSUM_SYMBOL_NAME: &str = "__rustc_abi_checksum";
+
+/// The prefix of the per-crate symbols `-Z embed-abi-checksum` checks upstream crates with.
+pub const ABI_CHECKSUM_GUARD_PREFIX: &str = "__rustc_abi_checksum_guard_";
+
+/// The name of the ABI checksum guard symbol of crate `cnum`, as expected by the local
+/// crate. It encodes a hash of the local `abi_checksum`, so a dependency built for another
+/// ABI defines a differently named guard and referencing this one fails to link.
+pub fn abi_checksum_guard_name(tcx: TyCtxt<'_>, cnum: CrateNum) -> String {
+    let mut hasher = StableHasher::new();
+    abi_checksum(tcx).hash(&mut hasher);
+    let checksum_hash: u64 = hasher.finish();
+
+    format!(
+        "{}{}_{}_{:016x}",
+        ABI_CHECKSUM_GUARD_PREFIX,
+        tcx.original_crate_name(cnum),
+        tcx.crate_disambiguator(cnum).to_fingerprint().to_hex(),
+        checksum_hash
+    )
+}
+
+/// Describes the ABI the local crate is compiled for: the target triple, followed by
+/// hashes of the enabled target features and of the data layout. Two binaries built
+/// with the same checksum agree on calling conventions and type layouts. The target CPU
+/// is left out: crates built for different CPUs of the same target can be linked together.
+pub fn abi_checksum(tcx: TyCtxt<'_>) -> String {
+    let target = &tcx.sess.target;
+
+    let mut hasher = StableHasher::new();
+    target.options.features.hash(&mut hasher);
+    tcx.sess.opts.cg.target_feature.hash(&mut hasher);
+    let features_hash: u64 = hasher.finish();
+
+    let mut hasher = StableHasher::new();
+    target.data_layout.hash(&mut hasher);
+    let layout_hash: u64 = hasher.finish();
+
+    format!("rustc-abi-v1:{}:{:016x}:{:016x}", target.llvm_target, features_hash, layout_hash)
+}
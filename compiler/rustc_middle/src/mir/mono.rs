@@ -106,13 +106,15 @@ impl<'tcx> MonoItem<'tcx> {
                     return InstantiationMode::LocalCopy;
                 }
 
-                // Finally, if this is `#[inline(always)]` we're sure to respect
+                // Finally, if this is `#[inline(always)]`, or `#[inline]` within
+                // the `-Z inline-debug-threshold` budget, we're sure to respect
                 // that with an inline copy per CGU, but otherwise we'll be
                 // creating one copy of this `#[inline]` function which may
                 // conflict with upstream crates as it could be an exported
                 // symbol.
                 match tcx.codegen_fn_attrs(instance.def_id()).inline {
                     InlineAttr::Always => InstantiationMode::LocalCopy,
+                    _ if instance.def.inlines_in_debug(tcx) => InstantiationMode::LocalCopy,
                     _ => InstantiationMode::GloballyShared { may_conflict: true },
                 }
             }
@@ -433,7 +435,9 @@ impl CodegenUnitNameBuilder<'tcx> {
     {
         let cgu_name = self.build_cgu_name_no_mangle(cnum, components, special_suffix);
 
-        if self.tcx.sess.opts.debugging_opts.human_readable_cgu_names {
+        if self.tcx.sess.opts.debugging_opts.human_readable_cgu_names
+            || self.tcx.sess.opts.debugging_opts.deterministic_ir_names
+        {
             cgu_name
         } else {
             Symbol::intern(&CodegenUnit::mangle_name(&cgu_name.as_str()))
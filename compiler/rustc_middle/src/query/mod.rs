@@ -651,6 +651,10 @@ rustc_queries! {
             desc { |tcx| "checking liveness of variables in {}", describe_as_module(key, tcx) }
         }
 
+        query check_mod_naked_functions(key: LocalDefId) -> () {
+            desc { |tcx| "checking naked functions in {}", describe_as_module(key, tcx) }
+        }
+
         query check_mod_impl_wf(key: LocalDefId) -> () {
             desc { |tcx| "checking that impls are well-formed in {}", describe_as_module(key, tcx) }
         }
@@ -1043,6 +1047,10 @@ rustc_queries! {
             fatal_cycle
             desc { "test whether a crate has `#![no_builtins]`" }
         }
+        query is_mir_only_rlib(_: CrateNum) -> bool {
+            fatal_cycle
+            desc { "test whether a crate is a MIR-only rlib" }
+        }
         query symbol_mangling_version(_: CrateNum) -> SymbolManglingVersion {
             fatal_cycle
             desc { "query a crate's symbol mangling version" }
@@ -1397,6 +1405,18 @@ rustc_queries! {
             -> &'tcx [(ExportedSymbol<'tcx>, SymbolExportLevel)] {
             desc { "exported_symbols" }
         }
+
+        /// The upstream crates built with `-Z embed-abi-checksum`, whose ABI checksum
+        /// guard symbols the local crate references.
+        query upstream_abi_checksum_crates(_: CrateNum) -> &'tcx [CrateNum] {
+            desc { "collecting upstream crates with an ABI checksum" }
+        }
+
+        /// Whether the crate's code uses atomics wider than the target supports,
+        /// which LLVM expands to the `__atomic_*` libcalls.
+        query needs_atomic_libcalls(_: CrateNum) -> bool {
+            desc { "checking if the crate needs the atomic libcalls" }
+        }
     }
 
     Codegen {
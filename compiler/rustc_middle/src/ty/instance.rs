@@ -2,11 +2,13 @@ use crate::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use crate::ty::print::{FmtPrinter, Printer};
 use crate::ty::subst::InternalSubsts;
 use crate::ty::{self, SubstsRef, Ty, TyCtxt, TypeFoldable};
+use rustc_attr::InlineAttr;
 use rustc_errors::ErrorReported;
 use rustc_hir::def::Namespace;
 use rustc_hir::def_id::{CrateNum, DefId};
 use rustc_hir::lang_items::LangItem;
 use rustc_macros::HashStable;
+use rustc_session::config::OptLevel;
 
 use std::fmt;
 
@@ -224,6 +226,20 @@ impl<'tcx> InstanceDef<'tcx> {
         tcx.codegen_fn_attrs(self.def_id()).requests_inline()
     }
 
+    /// Returns `true` if this is an `#[inline]` function small enough to be
+    /// treated like an `#[inline(always)]` one at `-Copt-level=0`, where
+    /// nothing else is inlined. Its size budget, in MIR statements, is given
+    /// by `-Z inline-debug-threshold`.
+    pub fn inlines_in_debug(&self, tcx: TyCtxt<'tcx>) -> bool {
+        match (*self, tcx.sess.opts.debugging_opts.inline_debug_threshold) {
+            (InstanceDef::Item(def), Some(threshold)) if tcx.sess.opts.optimize == OptLevel::No => {
+                tcx.codegen_fn_attrs(def.did).inline == InlineAttr::Hint
+                    && tcx.instance_def_size_estimate(*self) <= threshold
+            }
+            _ => false,
+        }
+    }
+
     pub fn requires_caller_location(&self, tcx: TyCtxt<'_>) -> bool {
         match *self {
             InstanceDef::Item(def) => {
@@ -15,7 +15,7 @@ use rustc_session::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
 use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::call::{
-    ArgAbi, ArgAttribute, ArgAttributes, Conv, FnAbi, PassMode, Reg, RegKind,
+    ArgAbi, ArgAttribute, ArgAttributes, CastTarget, Conv, FnAbi, PassMode, Reg, RegKind,
 };
 use rustc_target::abi::*;
 use rustc_target::spec::{abi::Abi as SpecAbi, HasTargetSpec, PanicStrategy};
@@ -165,6 +165,81 @@ pub const FAT_PTR_ADDR: usize = 0;
 /// - For a slice, this is the length.
 pub const FAT_PTR_EXTRA: usize = 1;
 
+/// What a pointer carries next to the address of its pointee, based on the
+/// pointee's unsized tail. This is the only place that knows how each kind of
+/// unsized type is represented; a new kind of DST needs a new variant here.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointerMetadataKind {
+    /// Sized pointees and extern types: the pointer is thin.
+    Thin,
+    /// Slices and `str`: the number of elements.
+    Length,
+    /// Trait objects: a pointer to the vtable.
+    Vtable,
+}
+
+impl PointerMetadataKind {
+    /// Classifies pointers to `pointee`. Returns `None` if the unsized tail of
+    /// `pointee` isn't known yet, e.g. because it is a type parameter.
+    pub fn of_pointee<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        param_env: ty::ParamEnv<'tcx>,
+        pointee: Ty<'tcx>,
+    ) -> Option<Self> {
+        if pointee.is_sized(tcx.at(DUMMY_SP), param_env) {
+            return Some(PointerMetadataKind::Thin);
+        }
+        Self::of_tail(tcx.struct_tail_erasing_lifetimes(pointee, param_env))
+    }
+
+    /// Classifies pointers to types whose unsized tail is `tail`.
+    pub fn of_tail(tail: Ty<'_>) -> Option<Self> {
+        match tail.kind() {
+            ty::Foreign(..) => Some(PointerMetadataKind::Thin),
+            ty::Slice(_) | ty::Str => Some(PointerMetadataKind::Length),
+            ty::Dynamic(..) => Some(PointerMetadataKind::Vtable),
+            _ => None,
+        }
+    }
+
+    pub fn is_thin(self) -> bool {
+        self == PointerMetadataKind::Thin
+    }
+
+    /// Whether the alignment of an unsized tail of this kind is known without
+    /// looking at the metadata.
+    pub fn has_static_align(self) -> bool {
+        match self {
+            PointerMetadataKind::Thin | PointerMetadataKind::Length => true,
+            PointerMetadataKind::Vtable => false,
+        }
+    }
+
+    /// The type of the `FAT_PTR_EXTRA` field of a fat pointer.
+    pub fn metadata_ty<'tcx>(self, tcx: TyCtxt<'tcx>) -> Option<Ty<'tcx>> {
+        match self {
+            PointerMetadataKind::Thin => None,
+            PointerMetadataKind::Length => Some(tcx.types.usize),
+            PointerMetadataKind::Vtable => {
+                Some(tcx.mk_imm_ref(tcx.lifetimes.re_static, tcx.mk_array(tcx.types.usize, 3)))
+                /* FIXME: use actual fn pointers
+                Warning: naively computing the number of entries in the
+                vtable by counting the methods on the trait + methods on
+                all parent traits does not work, because some methods can
+                be not object safe and thus excluded from the vtable.
+                Increase this counter if you tried to implement this but
+                failed to do it without duplicating a lot of code from
+                other places in the compiler: 2
+                tcx.mk_tup(&[
+                    tcx.mk_array(tcx.types.usize, 3),
+                    tcx.mk_array(Option<fn()>),
+                ])
+                */
+            }
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, TyEncodable, TyDecodable)]
 pub enum LayoutError<'tcx> {
     Unknown(Ty<'tcx>),
@@ -522,17 +597,19 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
                 }
 
                 let unsized_part = tcx.struct_tail_erasing_lifetimes(pointee, param_env);
-                let metadata = match unsized_part.kind() {
-                    ty::Foreign(..) => {
+                let metadata = match PointerMetadataKind::of_tail(unsized_part) {
+                    Some(PointerMetadataKind::Thin) => {
                         return Ok(tcx.intern_layout(Layout::scalar(self, data_ptr)));
                     }
-                    ty::Slice(_) | ty::Str => scalar_unit(Int(dl.ptr_sized_integer(), false)),
-                    ty::Dynamic(..) => {
+                    Some(PointerMetadataKind::Length) => {
+                        scalar_unit(Int(dl.ptr_sized_integer(), false))
+                    }
+                    Some(PointerMetadataKind::Vtable) => {
                         let mut vtable = scalar_unit(Pointer);
                         vtable.valid_range = 1..=*vtable.valid_range.end();
                         vtable
                     }
-                    _ => return Err(LayoutError::Unknown(unsized_part)),
+                    None => return Err(LayoutError::Unknown(unsized_part)),
                 };
 
                 // Effectively a (ptr, meta) tuple.
@@ -2074,25 +2151,10 @@ where
                     ));
                 }
 
-                match tcx.struct_tail_erasing_lifetimes(pointee, cx.param_env()).kind() {
-                    ty::Slice(_) | ty::Str => tcx.types.usize,
-                    ty::Dynamic(_, _) => {
-                        tcx.mk_imm_ref(tcx.lifetimes.re_static, tcx.mk_array(tcx.types.usize, 3))
-                        /* FIXME: use actual fn pointers
-                        Warning: naively computing the number of entries in the
-                        vtable by counting the methods on the trait + methods on
-                        all parent traits does not work, because some methods can
-                        be not object safe and thus excluded from the vtable.
-                        Increase this counter if you tried to implement this but
-                        failed to do it without duplicating a lot of code from
-                        other places in the compiler: 2
-                        tcx.mk_tup(&[
-                            tcx.mk_array(tcx.types.usize, 3),
-                            tcx.mk_array(Option<fn()>),
-                        ])
-                        */
-                    }
-                    _ => bug!("TyAndLayout::field_type({:?}): not applicable", this),
+                let tail = tcx.struct_tail_erasing_lifetimes(pointee, cx.param_env());
+                match PointerMetadataKind::of_tail(tail).and_then(|kind| kind.metadata_ty(tcx)) {
+                    Some(metadata_ty) => metadata_ty,
+                    None => bug!("TyAndLayout::field_type({:?}): not applicable", this),
                 }
             }
 
@@ -2195,7 +2257,7 @@ where
                         //
                         // For now, do not enable mutable_noalias by default at all, while the
                         // issue is being figured out.
-                        if tcx.sess.opts.debugging_opts.mutable_noalias {
+                        if tcx.sess.opts.debugging_opts.mutable_noalias == Some(true) {
                             PointerKind::UniqueBorrowed
                         } else {
                             PointerKind::Shared
@@ -2570,6 +2632,7 @@ where
             AmdGpuKernel => Conv::AmdGpuKernel,
             AvrInterrupt => Conv::AvrInterrupt,
             AvrNonBlockingInterrupt => Conv::AvrNonBlockingInterrupt,
+            CCmseNonSecureCall => Conv::CCmseNonSecureCall,
 
             // These API constants ought to be more specific...
             Cdecl => Conv::C,
@@ -2635,6 +2698,10 @@ where
                 }
             }
 
+            // `Box` gets `noalias` by default, but `-Z mutable-noalias=no` turns it off
+            // along with `&mut` to rule out LLVM miscompiles around `noalias`.
+            let box_noalias = cx.tcx().sess.opts.debugging_opts.mutable_noalias != Some(false);
+
             if let Some(pointee) = layout.pointee_info_at(cx, offset) {
                 if let Some(kind) = pointee.safe {
                     attrs.pointee_align = Some(pointee.align);
@@ -2657,7 +2724,7 @@ where
                     // dependencies rather than pointer equality
                     let no_alias = match kind {
                         PointerKind::Shared => false,
-                        PointerKind::UniqueOwned => true,
+                        PointerKind::UniqueOwned => box_noalias,
                         PointerKind::Frozen | PointerKind::UniqueBorrowed => !is_return,
                     };
                     if no_alias {
@@ -2786,11 +2853,19 @@ where
 
                 // Return structures up to 2 pointers in size by value, matching `ScalarPair`. LLVM
                 // will usually return these in 2 registers, which is more efficient than by-ref.
-                let max_by_val_size = if is_ret { Pointer.size(cx) * 2 } else { Pointer.size(cx) };
+                let ptr_size = Pointer.size(cx);
+                let max_by_val_size = if is_ret { ptr_size * 2 } else { ptr_size };
                 let size = arg.layout.size;
 
                 if arg.layout.is_unsized() || size > max_by_val_size {
                     arg.make_indirect();
+                } else if size > ptr_size {
+                    // Split return values that don't fit in a single register into a pair
+                    // of integers, just like a `ScalarPair` immediate. A single integer twice
+                    // the size of a pointer is not returned in registers on every target.
+                    let lo = Reg { kind: RegKind::Integer, size: ptr_size };
+                    let hi = Reg { kind: RegKind::Integer, size: size - ptr_size };
+                    arg.cast_to(CastTarget::pair(lo, hi));
                 } else {
                     // We want to pass small aggregates as immediates, but using
                     // a LLVM aggregate type for this leads to bad optimizations,
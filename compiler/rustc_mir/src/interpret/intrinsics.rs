@@ -431,6 +431,26 @@ impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 );
                 self.copy_op(self.operand_index(args[0], index)?, dest)?;
             }
+            sym::raw_eq => {
+                let layout = self.layout_of(substs.type_at(0))?;
+                let lhs = self.read_scalar(args[0])?.check_init()?;
+                let rhs = self.read_scalar(args[1])?.check_init()?;
+                // Reading the bytes fails on uninitialized memory such as padding,
+                // which the intrinsic does not allow.
+                let lhs_bytes = self.memory.read_bytes(lhs, layout.size)?;
+                let rhs_bytes = self.memory.read_bytes(rhs, layout.size)?;
+                let result = Scalar::from_bool(lhs_bytes == rhs_bytes);
+                self.write_scalar(result, dest)?;
+            }
+            sym::raw_eq_bytes => {
+                let lhs = self.read_scalar(args[0])?.check_init()?;
+                let rhs = self.read_scalar(args[1])?.check_init()?;
+                let size = Size::from_bytes(self.read_scalar(args[2])?.to_machine_usize(self)?);
+                let lhs_bytes = self.memory.read_bytes(lhs, size)?;
+                let rhs_bytes = self.memory.read_bytes(rhs, size)?;
+                let result = Scalar::from_bool(lhs_bytes == rhs_bytes);
+                self.write_scalar(result, dest)?;
+            }
             sym::likely | sym::unlikely => {
                 // These just return their argument
                 self.copy_op(args[0], dest)?;
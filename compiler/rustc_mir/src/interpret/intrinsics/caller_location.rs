@@ -73,15 +73,21 @@ impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
     }
 
     /// Allocate a `const core::panic::Location` with the provided filename and line/column numbers.
+    /// Any part of the location excluded by `-Z location-detail` is replaced by `<redacted>` or 0.
     crate fn alloc_caller_location(
         &mut self,
         filename: Symbol,
         line: u32,
         col: u32,
     ) -> MPlaceTy<'tcx, M::PointerTag> {
-        let file = self.allocate_str(&filename.as_str(), MemoryKind::CallerLocation);
-        let line = Scalar::from_u32(line);
-        let col = Scalar::from_u32(col);
+        let loc_details = self.tcx.sess.opts.debugging_opts.location_detail;
+        let file = if loc_details.file {
+            self.allocate_str(&filename.as_str(), MemoryKind::CallerLocation)
+        } else {
+            self.allocate_str("<redacted>", MemoryKind::CallerLocation)
+        };
+        let line = Scalar::from_u32(if loc_details.line { line } else { 0 });
+        let col = Scalar::from_u32(if loc_details.column { col } else { 0 });
 
         // Allocate memory for `CallerLocation` struct.
         let loc_ty = self
@@ -180,17 +180,20 @@ use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::{par_iter, MTLock, MTRef, ParallelIterator};
 use rustc_errors::{ErrorReported, FatalError};
 use rustc_hir as hir;
-use rustc_hir::def_id::{DefId, DefIdMap, LocalDefId, LOCAL_CRATE};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, LocalDefId, LOCAL_CRATE};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::lang_items::LangItem;
 use rustc_index::bit_set::GrowableBitSet;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::middle::dependency_format::Linkage;
 use rustc_middle::mir::interpret::{AllocId, ConstValue};
 use rustc_middle::mir::interpret::{ErrorHandled, GlobalAlloc, Scalar};
 use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
 use rustc_middle::mir::visit::Visitor as MirVisitor;
 use rustc_middle::mir::{self, Local, Location};
 use rustc_middle::ty::adjustment::{CustomCoerceUnsized, PointerCast};
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts};
 use rustc_middle::ty::{self, GenericParamDefKind, Instance, Ty, TyCtxt, TypeFoldable};
 use rustc_session::config::EntryFnType;
@@ -327,6 +330,38 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionMode) -> Vec<MonoItem<
         visitor.push_extra_entry_roots();
     }
 
+    // MIR-only rlibs have no object code, so the artifact linking one codegens
+    // the items it exports, once, on behalf of every crate that uses them.
+    for cnum in statically_linked_mir_only_rlibs(tcx) {
+        let mut def_ids: Vec<_> = tcx.reachable_non_generics(cnum).keys().copied().collect();
+        def_ids.sort();
+        for def_id in def_ids {
+            if tcx.is_foreign_item(def_id) {
+                continue;
+            }
+            let item = if tcx.is_static(def_id) {
+                MonoItem::Static(def_id)
+            } else {
+                MonoItem::Fn(Instance::mono(tcx, def_id))
+            };
+            roots.push(dummy_spanned(item));
+        }
+    }
+
+    if let Some(ref path) = tcx.sess.opts.debugging_opts.codegen_only {
+        let codegen_only_root = codegen_only_root(tcx);
+        roots.retain(|root| match root.node {
+            MonoItem::Fn(instance) => Some(instance.def_id()) == codegen_only_root,
+            MonoItem::Static(_) | MonoItem::GlobalAsm(_) => false,
+        });
+        if roots.is_empty() {
+            tcx.sess.fatal(&format!(
+                "`-Z codegen-only={}` does not name a non-generic function of this crate",
+                path
+            ));
+        }
+    }
+
     // We can only codegen items that are instantiable - items all of
     // whose predicates hold. Luckily, items that aren't instantiable
     // can't actually be used, so we can just skip codegenning them.
@@ -336,6 +371,33 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionMode) -> Vec<MonoItem<
         .collect()
 }
 
+/// Returns the MIR-only rlibs whose contents end up in the artifact being
+/// built. Rlibs themselves link nothing, so this is empty for them.
+fn statically_linked_mir_only_rlibs(tcx: TyCtxt<'_>) -> Vec<CrateNum> {
+    let mut cnums: Vec<_> = tcx
+        .dependency_formats(LOCAL_CRATE)
+        .iter()
+        .flat_map(|(_, linkages)| linkages.iter().enumerate())
+        .filter(|&(_, &linkage)| linkage == Linkage::Static)
+        .map(|(i, _)| CrateNum::new(i + 1))
+        .filter(|&cnum| tcx.is_mir_only_rlib(cnum))
+        .collect();
+    cnums.sort();
+    cnums.dedup();
+    cnums
+}
+
+/// Resolves the function named by `-Z codegen-only` to its `DefId`, if any.
+pub fn codegen_only_root(tcx: TyCtxt<'_>) -> Option<DefId> {
+    let path = tcx.sess.opts.debugging_opts.codegen_only.as_ref()?;
+    with_no_trimmed_paths(|| {
+        tcx.body_owners()
+            .map(|def_id| def_id.to_def_id())
+            .filter(|&def_id| matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn))
+            .find(|&def_id| tcx.def_path_str(def_id) == *path)
+    })
+}
+
 // Collect all monomorphized items reachable from `starting_point`
 fn collect_items_rec<'tcx>(
     tcx: TyCtxt<'tcx>,
@@ -13,7 +13,7 @@ use rustc_middle::ty::{self, DefIdTree, InstanceDef, TyCtxt};
 use rustc_span::symbol::Symbol;
 
 use super::PartitioningCx;
-use crate::monomorphize::collector::InliningMap;
+use crate::monomorphize::collector::{self, InliningMap};
 use crate::monomorphize::partitioning::merging;
 use crate::monomorphize::partitioning::{
     MonoItemPlacement, Partitioner, PostInliningPartitioning, PreInliningPartitioning,
@@ -39,6 +39,8 @@ impl<'tcx> Partitioner<'tcx> for DefaultPartitioning {
         let export_generics =
             cx.tcx.sess.opts.share_generics() && cx.tcx.local_crate_exports_generics();
 
+        let codegen_only_root = collector::codegen_only_root(cx.tcx);
+
         let cgu_name_builder = &mut CodegenUnitNameBuilder::new(cx.tcx);
         let cgu_name_cache = &mut FxHashMap::default();
 
@@ -51,15 +53,16 @@ impl<'tcx> Partitioner<'tcx> for DefaultPartitioning {
             let characteristic_def_id = characteristic_def_id_of_mono_item(cx.tcx, mono_item);
             let is_volatile = is_incremental_build && mono_item.is_generic_fn();
 
-            let codegen_unit_name = match characteristic_def_id {
-                Some(def_id) => compute_codegen_unit_name(
+            let codegen_unit_name = match (large_fn(cx.tcx, mono_item), characteristic_def_id) {
+                (Some(def_id), _) => isolated_cgu_name(cx.tcx, cgu_name_builder, def_id),
+                (None, Some(def_id)) => compute_codegen_unit_name(
                     cx.tcx,
                     cgu_name_builder,
                     def_id,
                     is_volatile,
                     cgu_name_cache,
                 ),
-                None => fallback_cgu_name(cgu_name_builder),
+                (None, None) => fallback_cgu_name(cgu_name_builder),
             };
 
             let codegen_unit = codegen_units
@@ -72,6 +75,7 @@ impl<'tcx> Partitioner<'tcx> for DefaultPartitioning {
                 &mono_item,
                 &mut can_be_internalized,
                 export_generics,
+                codegen_only_root,
             );
             if visibility == Visibility::Hidden && can_be_internalized {
                 internalization_candidates.insert(mono_item);
@@ -371,16 +375,46 @@ fn fallback_cgu_name(name_builder: &mut CodegenUnitNameBuilder<'_>) -> Symbol {
     name_builder.build_cgu_name(LOCAL_CRATE, &["fallback"], Some("cgu"))
 }
 
+/// Returns the `DefId` of `mono_item` if it is a function large enough to be
+/// placed in a codegen unit of its own by `-Z isolate-large-fns`. Otherwise, a
+/// single huge function serializes the optimization of everything sharing its
+/// module.
+fn large_fn(tcx: TyCtxt<'tcx>, mono_item: MonoItem<'tcx>) -> Option<DefId> {
+    match (mono_item, tcx.sess.opts.debugging_opts.isolate_large_fns) {
+        (MonoItem::Fn(instance), Some(threshold)) if mono_item.size_estimate(tcx) > threshold => {
+            Some(instance.def_id())
+        }
+        _ => None,
+    }
+}
+
+fn isolated_cgu_name(
+    tcx: TyCtxt<'_>,
+    name_builder: &mut CodegenUnitNameBuilder<'_>,
+    def_id: DefId,
+) -> Symbol {
+    let def_path = tcx.def_path(def_id);
+    let components = def_path.data.iter().map(|part| part.to_string());
+    name_builder.build_cgu_name(def_path.krate, components, Some("isolated"))
+}
+
 fn mono_item_linkage_and_visibility(
     tcx: TyCtxt<'tcx>,
     mono_item: &MonoItem<'tcx>,
     can_be_internalized: &mut bool,
     export_generics: bool,
+    codegen_only_root: Option<DefId>,
 ) -> (Linkage, Visibility) {
     if let Some(explicit_linkage) = mono_item.explicit_linkage(tcx) {
         return (explicit_linkage, Visibility::Default);
     }
-    let vis = mono_item_visibility(tcx, mono_item, can_be_internalized, export_generics);
+    let vis = mono_item_visibility(
+        tcx,
+        mono_item,
+        can_be_internalized,
+        export_generics,
+        codegen_only_root,
+    );
     (Linkage::External, vis)
 }
 
@@ -391,6 +425,7 @@ fn mono_item_visibility(
     mono_item: &MonoItem<'tcx>,
     can_be_internalized: &mut bool,
     export_generics: bool,
+    codegen_only_root: Option<DefId>,
 ) -> Visibility {
     let instance = match mono_item {
         // This is pretty complicated; see below.
@@ -449,8 +484,22 @@ fn mono_item_visibility(
         return Visibility::Hidden;
     }
 
+    // The root requested with `-Z codegen-only` has no callers in the output,
+    // so keep it from being internalized and then optimized away.
+    if Some(def_id) == codegen_only_root {
+        *can_be_internalized = false;
+        return Visibility::Hidden;
+    }
+
     let is_generic = instance.substs.non_erasable_generics().next().is_some();
 
+    // The exported items of a MIR-only rlib are defined by the artifact linking
+    // it, and other crates' object code may refer to them.
+    if !def_id.is_local() && !is_generic && tcx.is_reachable_non_generic(def_id) {
+        *can_be_internalized = false;
+        return default_visibility(tcx, def_id, false);
+    }
+
     // Upstream `DefId` instances get different handling than local ones.
     if !def_id.is_local() {
         return if export_generics && is_generic {
@@ -542,8 +591,8 @@ fn default_visibility(tcx: TyCtxt<'_>, id: DefId, is_generic: bool) -> Visibilit
     }
 
     // Things with export level C don't get instantiated in
-    // downstream crates.
-    if !id.is_local() {
+    // downstream crates, except for those of MIR-only rlibs.
+    if !id.is_local() && !tcx.is_mir_only_rlib(id.krate) {
         return Visibility::Hidden;
     }
 
@@ -327,7 +327,9 @@ fn collect_and_partition_mono_items<'tcx>(
             }
         }
         None => {
-            if tcx.sess.link_dead_code() {
+            // Private functions are only roots in eager mode, and
+            // `-Z codegen-only` has to be able to start from any of them.
+            if tcx.sess.link_dead_code() || tcx.sess.opts.debugging_opts.codegen_only.is_some() {
                 MonoItemCollectionMode::Eager
             } else {
                 MonoItemCollectionMode::Lazy
@@ -204,7 +204,7 @@ struct MarkUsedGenericParams<'a, 'tcx> {
 
 impl<'a, 'tcx> MarkUsedGenericParams<'a, 'tcx> {
     /// Invoke `unused_generic_params` on a body contained within the current item (e.g.
-    /// a closure, generator or constant).
+    /// a closure, generator or constant), or on an upstream function it refers to.
     fn visit_child_body(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) {
         let unused = self.tcx.unused_generic_params(def_id);
         debug!(
@@ -301,6 +301,17 @@ impl<'a, 'tcx> TypeVisitor<'tcx> for MarkUsedGenericParams<'a, 'tcx> {
                 self.visit_child_body(def_id, substs);
                 false
             }
+            ty::FnDef(def_id, substs)
+                if !def_id.is_local() && self.tcx.trait_of_item(def_id).is_none() =>
+            {
+                debug!("visit_ty: upstream fn def_id={:?}", def_id);
+                // Parameters only passed to an upstream function in positions it doesn't use
+                // aren't needed to codegen the reference to it. Trait methods are excluded, as
+                // resolving them depends on all parameters, and so are local functions, where
+                // consulting the query could form a cycle through recursive calls.
+                self.visit_child_body(def_id, substs);
+                false
+            }
             ty::Param(param) => {
                 debug!("visit_ty: param={:?}", param);
                 self.unused_parameters.clear(param.index);
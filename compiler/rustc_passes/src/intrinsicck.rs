@@ -252,9 +252,13 @@ impl ExprVisitor<'tcx> {
         }
 
         // Check the type against the list of types supported by the selected
-        // register class.
+        // register class. Clobber-only register classes were already rejected
+        // for operands with a value when lowering.
         let asm_arch = self.tcx.sess.asm_arch.unwrap();
         let reg_class = reg.reg_class();
+        if reg_class.is_clobber_only() {
+            return Some(asm_ty);
+        }
         let supported_tys = reg_class.supported_types(asm_arch);
         let feature = match supported_tys.iter().find(|&&(t, _)| t == asm_ty) {
             Some((_, feature)) => feature,
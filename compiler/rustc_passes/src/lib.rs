@@ -32,6 +32,7 @@ pub mod layout_test;
 mod lib_features;
 mod liveness;
 pub mod loops;
+mod naked_functions;
 mod reachable;
 mod region;
 pub mod stability;
@@ -47,6 +48,7 @@ pub fn provide(providers: &mut Providers) {
     lib_features::provide(providers);
     loops::provide(providers);
     liveness::provide(providers);
+    naked_functions::provide(providers);
     intrinsicck::provide(providers);
     reachable::provide(providers);
     region::provide(providers);
@@ -0,0 +1,273 @@
+//! Checks validity of naked functions.
+//!
+//! A naked function has no prologue or epilogue, so its body may not contain
+//! anything that would require the compiler to insert code of its own: no
+//! destructuring of parameters, no uses of parameters (which live in
+//! registers or on the stack as dictated by the calling convention), no
+//! statements besides a single `asm!` block, and that block must not fall
+//! through to an implicit return.
+
+use rustc_ast::InlineAsmOptions;
+use rustc_hir as hir;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, FnKind, NestedVisitorMap, Visitor};
+use rustc_hir::{ExprKind, HirId, HirIdSet, InlineAsmOperand, PatKind, QPath, StmtKind};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::sym;
+use rustc_span::Span;
+
+fn check_mod_naked_functions(tcx: TyCtxt<'_>, module_def_id: LocalDefId) {
+    tcx.hir().visit_item_likes_in_module(
+        module_def_id,
+        &mut CheckNakedFunctions { tcx }.as_deep_visitor(),
+    );
+}
+
+pub fn provide(providers: &mut Providers) {
+    *providers = Providers { check_mod_naked_functions, ..*providers };
+}
+
+struct CheckNakedFunctions<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> Visitor<'tcx> for CheckNakedFunctions<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_fn(
+        &mut self,
+        fk: FnKind<'tcx>,
+        fd: &'tcx hir::FnDecl<'tcx>,
+        body_id: hir::BodyId,
+        span: Span,
+        hir_id: HirId,
+    ) {
+        let ident_span = match fk {
+            // Rejected during attribute check. Do not validate further.
+            FnKind::Closure(..) => return,
+            FnKind::ItemFn(ident, ..) | FnKind::Method(ident, ..) => ident.span,
+        };
+
+        let naked = fk.attrs().iter().any(|attr| self.tcx.sess.check_name(attr, sym::naked));
+        if naked {
+            let body = self.tcx.hir().body(body_id);
+            check_params(self.tcx, body);
+            check_body(self.tcx, body);
+            check_asm(self.tcx, body, ident_span);
+        }
+
+        intravisit::walk_fn(self, fk, fd, body_id, span, hir_id);
+    }
+}
+
+/// Checks that parameters are not destructured, which would require code to
+/// be inserted before the body of the function.
+fn check_params(tcx: TyCtxt<'_>, body: &hir::Body<'_>) {
+    for param in body.params {
+        match param.pat.kind {
+            PatKind::Wild | PatKind::Binding(_, _, _, None) => {}
+            _ => {
+                tcx.sess
+                    .struct_span_err(
+                        param.pat.span,
+                        "patterns not allowed in naked function parameters",
+                    )
+                    .emit();
+            }
+        }
+    }
+}
+
+/// Checks that function parameters aren't used in the function body.
+fn check_body<'tcx>(tcx: TyCtxt<'tcx>, body: &'tcx hir::Body<'tcx>) {
+    let mut params = HirIdSet::default();
+    for param in body.params {
+        param.pat.each_binding(|_binding_mode, hir_id, _span, _ident| {
+            params.insert(hir_id);
+        });
+    }
+    CheckParameters { tcx, params }.visit_body(body);
+}
+
+struct CheckParameters<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    params: HirIdSet,
+}
+
+impl<'tcx> Visitor<'tcx> for CheckParameters<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let ExprKind::Path(QPath::Resolved(
+            None,
+            hir::Path { res: Res::Local(var_hir_id), .. },
+        )) = expr.kind
+        {
+            if self.params.contains(var_hir_id) {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        expr.span,
+                        "use of parameters not allowed inside naked functions",
+                    )
+                    .help("follow the calling convention in the asm block to use parameters")
+                    .emit();
+                return;
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Checks that the function body contains a single `asm!` block that does
+/// not return, and that the block only uses operands which can be resolved
+/// without a register allocator.
+fn check_asm<'tcx>(tcx: TyCtxt<'tcx>, body: &'tcx hir::Body<'tcx>, fn_ident_span: Span) {
+    let mut this = CheckInlineAssembly { tcx, items: Vec::new() };
+    this.visit_body(body);
+    if let [(ItemKind::Asm, _)] = this.items[..] {
+        // Ok.
+    } else {
+        let mut diag = tcx
+            .sess
+            .struct_span_err(fn_ident_span, "naked functions must contain a single asm block");
+        let mut has_asm = false;
+        for &(kind, span) in &this.items {
+            match kind {
+                ItemKind::Asm if has_asm => {
+                    diag.span_label(span, "multiple asm blocks are unsupported in naked functions");
+                }
+                ItemKind::Asm => has_asm = true,
+                ItemKind::NonAsm => {
+                    diag.span_label(span, "non-asm is unsupported in naked functions");
+                }
+            }
+        }
+        diag.emit();
+    }
+}
+
+struct CheckInlineAssembly<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    items: Vec<(ItemKind, Span)>,
+}
+
+#[derive(Copy, Clone)]
+enum ItemKind {
+    Asm,
+    NonAsm,
+}
+
+impl<'tcx> CheckInlineAssembly<'tcx> {
+    fn check_expr(&mut self, expr: &'tcx hir::Expr<'tcx>, span: Span) {
+        match expr.kind {
+            ExprKind::InlineAsm(ref asm) => {
+                self.items.push((ItemKind::Asm, span));
+                self.check_inline_asm(asm, expr.span);
+            }
+            ExprKind::LlvmInlineAsm(..) => {
+                self.items.push((ItemKind::Asm, span));
+                self.tcx
+                    .sess
+                    .struct_span_err(expr.span, "`llvm_asm!` is unsupported in naked functions")
+                    .help("use `asm!` with the `noreturn` option instead")
+                    .emit();
+            }
+            ExprKind::DropTemps(..) | ExprKind::Block(..) | ExprKind::Err => {
+                intravisit::walk_expr(self, expr);
+            }
+            _ => {
+                self.items.push((ItemKind::NonAsm, span));
+            }
+        }
+    }
+
+    fn check_inline_asm(&self, asm: &'tcx hir::InlineAsm<'tcx>, span: Span) {
+        let has_register_operands = asm.operands.iter().any(|op| match op {
+            InlineAsmOperand::Const { .. } | InlineAsmOperand::Sym { .. } => false,
+            InlineAsmOperand::In { .. }
+            | InlineAsmOperand::Out { .. }
+            | InlineAsmOperand::InOut { .. }
+            | InlineAsmOperand::SplitInOut { .. } => true,
+        });
+        if has_register_operands {
+            self.tcx
+                .sess
+                .struct_span_err(
+                    span,
+                    "only `const` and `sym` operands are supported in naked functions",
+                )
+                .emit();
+        }
+
+        let unsupported_options: Vec<&'static str> = [
+            (InlineAsmOptions::NOMEM, "`nomem`"),
+            (InlineAsmOptions::NOSTACK, "`nostack`"),
+            (InlineAsmOptions::PRESERVES_FLAGS, "`preserves_flags`"),
+            (InlineAsmOptions::PURE, "`pure`"),
+            (InlineAsmOptions::READONLY, "`readonly`"),
+        ]
+        .iter()
+        .filter_map(|&(option, name)| if asm.options.contains(option) { Some(name) } else { None })
+        .collect();
+
+        if !unsupported_options.is_empty() {
+            self.tcx
+                .sess
+                .struct_span_err(
+                    span,
+                    &format!(
+                        "asm options unsupported in naked functions: {}",
+                        unsupported_options.join(", ")
+                    ),
+                )
+                .emit();
+        }
+
+        if !asm.options.contains(InlineAsmOptions::NORETURN) {
+            self.tcx
+                .sess
+                .struct_span_err(span, "asm in naked functions must use `noreturn` option")
+                .help(
+                    "the asm block is responsible for returning from the function, \
+                     specify `options(noreturn)` to reflect that",
+                )
+                .emit();
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for CheckInlineAssembly<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_stmt(&mut self, stmt: &'tcx hir::Stmt<'tcx>) {
+        match stmt.kind {
+            StmtKind::Item(..) => {}
+            StmtKind::Local(..) => {
+                self.items.push((ItemKind::NonAsm, stmt.span));
+            }
+            StmtKind::Expr(ref expr) | StmtKind::Semi(ref expr) => {
+                self.check_expr(expr, stmt.span);
+            }
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        self.check_expr(expr, expr.span);
+    }
+}
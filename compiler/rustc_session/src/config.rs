@@ -112,6 +112,16 @@ pub enum CFGuard {
     Checks,
 }
 
+/// The different settings that the `-Z overflow-trap` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum OverflowTrap {
+    /// Failed overflow checks call into the panic machinery.
+    Panic,
+
+    /// Failed overflow checks execute a trap instruction.
+    Abort,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Hash)]
 pub enum OptLevel {
     No,         // -O0
@@ -190,6 +200,44 @@ impl LinkerPluginLto {
     }
 }
 
+/// The parts of a source location that are embedded in `core::panic::Location`
+/// constants; omitted parts are replaced by `<redacted>` or zero.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub struct LocationDetail {
+    pub file: bool,
+    pub line: bool,
+    pub column: bool,
+}
+
+impl LocationDetail {
+    pub fn all() -> Self {
+        Self { file: true, line: true, column: true }
+    }
+}
+
+/// What the allocator shim's `__rust_alloc_error_handler` does once any alloc error hook
+/// has run.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum OomStrategy {
+    /// Raise a regular, unwindable panic.
+    Panic,
+    /// Abort the process.
+    Abort,
+}
+
+impl OomStrategy {
+    /// The `u8` static emitted in the allocator shim which tells the standard library's
+    /// default alloc error hook whether to panic.
+    pub const SYMBOL: &'static str = "__rust_alloc_error_handler_should_panic";
+
+    pub fn should_panic(self) -> u8 {
+        match self {
+            OomStrategy::Panic => 1,
+            OomStrategy::Abort => 0,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Hash)]
 pub enum SwitchWithOptPath {
     Enabled(Option<PathBuf>),
@@ -2084,9 +2132,9 @@ impl PpMode {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        CFGuard, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel,
-        OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm, SwitchWithOptPath,
-        SymbolManglingVersion, TrimmedDefPaths,
+        CFGuard, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LocationDetail, LtoCli,
+        OomStrategy, OptLevel, OutputTypes, OverflowTrap, Passes, SanitizerSet,
+        SourceFileHashAlgorithm, SwitchWithOptPath, SymbolManglingVersion, TrimmedDefPaths,
     };
     use crate::lint;
     use crate::utils::NativeLibKind;
@@ -2153,6 +2201,7 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(PanicStrategy);
     impl_dep_tracking_hash_via_hash!(RelroLevel);
     impl_dep_tracking_hash_via_hash!(Passes);
+    impl_dep_tracking_hash_via_hash!(OomStrategy);
     impl_dep_tracking_hash_via_hash!(OptLevel);
     impl_dep_tracking_hash_via_hash!(LtoCli);
     impl_dep_tracking_hash_via_hash!(DebugInfo);
@@ -2161,9 +2210,11 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(NativeLibKind);
     impl_dep_tracking_hash_via_hash!(SanitizerSet);
     impl_dep_tracking_hash_via_hash!(CFGuard);
+    impl_dep_tracking_hash_via_hash!(OverflowTrap);
     impl_dep_tracking_hash_via_hash!(TargetTriple);
     impl_dep_tracking_hash_via_hash!(Edition);
     impl_dep_tracking_hash_via_hash!(LinkerPluginLto);
+    impl_dep_tracking_hash_via_hash!(LocationDetail);
     impl_dep_tracking_hash_via_hash!(SwitchWithOptPath);
     impl_dep_tracking_hash_via_hash!(SymbolManglingVersion);
     impl_dep_tracking_hash_via_hash!(Option<SourceFileHashAlgorithm>);
@@ -2706,6 +2706,50 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    /// The `conflicting_link_section` lint detects `#[link_section]`
+    /// attributes that place an item in a section whose flags don't suit it.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (ELF-only)
+    /// #[link_section = ".rodata.counter"]
+    /// static mut COUNTER: u32 = 0;
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: mutable statics cannot be placed in the read-only section `.rodata.counter`
+    ///  --> src/main.rs:2:1
+    ///   |
+    /// 2 | static mut COUNTER: u32 = 0;
+    ///   | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = note: `#[warn(conflicting_link_section)]` on by default
+    ///   = warning: this was previously accepted by the compiler but is being phased out; it will become a hard error in a future release!
+    ///   = note: for more information, see <https://doc.rust-lang.org/reference/abi.html#the-link_section-attribute>
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// The linker merges a section such as `.rodata.counter` into `.rodata`,
+    /// so the item ends up mapped read-only, non-executable, or (for `.bss`)
+    /// without its initializer. On ELF, the `.tdata` and `.tbss` sections
+    /// also decide whether a symbol is thread-local. Functions in data
+    /// sections, mutable statics in read-only sections, non-zero statics in
+    /// zero-initialized sections, and thread-local statics outside the TLS
+    /// sections (or plain statics inside them) are therefore broken at run
+    /// time. This was accepted silently and is being phased out.
+    pub CONFLICTING_LINK_SECTION,
+    Warn,
+    "detects `#[link_section]`s whose flags conflict with the item placed in them",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "<https://doc.rust-lang.org/reference/abi.html#the-link_section-attribute>",
+        edition: None,
+    };
+}
+
 declare_tool_lint! {
     pub rustc::INEFFECTIVE_UNSTABLE_TRAIT_IMPL,
     Deny,
@@ -2793,6 +2837,7 @@ declare_lint_pass! {
         INEFFECTIVE_UNSTABLE_TRAIT_IMPL,
         UNINHABITED_STATIC,
         FUNCTION_ITEM_REFERENCES,
+        CONFLICTING_LINK_SECTION,
     ]
 }
 
@@ -253,6 +253,7 @@ macro_rules! options {
         pub const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
         pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `leak`, `memory` or `thread`";
         pub const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
+        pub const parse_overflow_trap: &str = "either `panic` or `abort`";
         pub const parse_cfguard: &str =
             "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
         pub const parse_strip: &str = "either `none`, `debuginfo`, or `symbols`";
@@ -268,6 +269,9 @@ macro_rules! options {
         pub const parse_switch_with_opt_path: &str =
             "an optional path to the profiling data output directory";
         pub const parse_merge_functions: &str = "one of: `disabled`, `trampolines`, or `aliases`";
+        pub const parse_location_detail: &str =
+            "comma separated list of `file`, `line` and `column`, or `none`";
+        pub const parse_oom_strategy: &str = "either `panic` or `abort`";
         pub const parse_symbol_mangling_version: &str = "either `legacy` or `v0` (RFC 2603)";
         pub const parse_src_file_hash: &str = "either `md5` or `sha1`";
         pub const parse_relocation_model: &str =
@@ -499,6 +503,15 @@ macro_rules! options {
             true
         }
 
+        fn parse_overflow_trap(slot: &mut OverflowTrap, v: Option<&str>) -> bool {
+            match v {
+                Some("panic") => *slot = OverflowTrap::Panic,
+                Some("abort") => *slot = OverflowTrap::Abort,
+                _ => return false,
+            }
+            true
+        }
+
         fn parse_cfguard(slot: &mut CFGuard, v: Option<&str>) -> bool {
             if v.is_some() {
                 let mut bool_arg = None;
@@ -675,6 +688,35 @@ macro_rules! options {
             true
         }
 
+        fn parse_location_detail(slot: &mut LocationDetail, v: Option<&str>) -> bool {
+            if let Some(v) = v {
+                *slot = LocationDetail { file: false, line: false, column: false };
+                if v == "none" {
+                    return true;
+                }
+                for s in v.split(',') {
+                    match s {
+                        "file" => slot.file = true,
+                        "line" => slot.line = true,
+                        "column" => slot.column = true,
+                        _ => return false,
+                    }
+                }
+                true
+            } else {
+                false
+            }
+        }
+
+        fn parse_oom_strategy(slot: &mut OomStrategy, v: Option<&str>) -> bool {
+            match v {
+                Some("panic") => *slot = OomStrategy::Panic,
+                Some("abort") => *slot = OomStrategy::Abort,
+                _ => return false,
+            }
+            true
+        }
+
         fn parse_symbol_mangling_version(
             slot: &mut SymbolManglingVersion,
             v: Option<&str>,
@@ -804,7 +846,8 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
     save_temps: bool = (false, parse_bool, [UNTRACKED],
         "save all temporary output files during compilation (default: no)"),
     soft_float: bool = (false, parse_bool, [TRACKED],
-        "use soft float ABI (*eabihf targets only) (default: no)"),
+        "generate code that does not use FPU or SIMD registers, with the soft float ABI \
+        (default: no, unless the target uses soft floats)"),
     target_cpu: Option<String> = (None, parse_opt_string, [TRACKED],
         "select target processor (`rustc --print target-cpus` for details)"),
     target_feature: String = (String::new(), parse_target_feature, [TRACKED],
@@ -849,11 +892,18 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     chalk: bool = (false, parse_bool, [TRACKED],
         "enable the experimental Chalk-based trait solving engine"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
-        "the backend to use"),
+        "the backend to use: the name of a builtin or sysroot backend, or a path to a dylib \
+        exporting `__rustc_codegen_backend`"),
+    codegen_only: Option<String> = (None, parse_opt_string, [TRACKED],
+        "only codegen the function at this path and the items it transitively needs; \
+        intended for inspecting `--emit=asm,llvm-ir,obj` output, which generally won't link"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
+    data_sections: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "whether each static should go in its own section (default: same as \
+        `-Z function-sections`)"),
     debug_macros: bool = (false, parse_bool, [TRACKED],
         "emit line numbers debug info inside macros (default: no)"),
     deduplicate_diagnostics: bool = (true, parse_bool, [UNTRACKED],
@@ -864,6 +914,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     dep_tasks: bool = (false, parse_bool, [UNTRACKED],
         "print tasks that execute and the color their dep node gets (requires debug build) \
         (default: no)"),
+    deterministic_ir_names: bool = (false, parse_bool, [TRACKED],
+        "only use names for LLVM values, local symbols and codegen units that depend on the \
+        input alone, so that `--emit=llvm-ir` and `--emit=asm` output can be diffed between \
+        builds (default: no)"),
     dont_buffer_diagnostics: bool = (false, parse_bool, [UNTRACKED],
         "emit diagnostics rather than buffering (breaks NLL error downgrading, sorting) \
         (default: no)"),
@@ -893,6 +947,15 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         all `statement`s (including terminators), only `terminator` spans, or \
         computed `block` spans (one span encompassing a block's terminator and \
         all statements)."),
+    embed_abi_checksum: bool = (false, parse_bool, [TRACKED],
+        "embed a checksum of the target, its enabled features and its data layout in the \
+        `__rustc_abi_checksum` symbol, to be checked by code loading the binary at runtime, and \
+        make linking fail if a dependency built with this option has another checksum \
+        (default: no)"),
+    emit_abi_tests: bool = (false, parse_bool, [UNTRACKED],
+        "emit a C header for the `extern \"C\"` functions of the crate and check the layout of \
+        their types by compiling assertions with `$CC`, which must target the same platform \
+        (default: no)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
     fewer_names: bool = (false, parse_bool, [TRACKED],
@@ -913,10 +976,16 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         environment variable `RUSTC_GRAPHVIZ_FONT` (default: `Courier, monospace`)"),
     hir_stats: bool = (false, parse_bool, [UNTRACKED],
         "print some statistics about AST and HIR (default: no)"),
+    hotpatch: bool = (false, parse_bool, [TRACKED],
+        "make functions hotpatchable by starting them with a two-byte instruction and, \
+        on MSVC, padding them apart at link time; x86 only (default: no)"),
     human_readable_cgu_names: bool = (false, parse_bool, [TRACKED],
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    in_process_lld: bool = (false, parse_bool, [UNTRACKED],
+        "run LLD inside the compiler instead of spawning it when the linker flavor is \
+        `ld.lld`, `lld-link` or `wasm-ld` (default: no)"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -924,6 +993,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         (default: no)"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
         "verify incr. comp. hashes of green query instances (default: no)"),
+    inline_debug_threshold: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "at `-C opt-level=0`, inline `#[inline]` functions whose MIR has at most this \
+        many statements, like `#[inline(always)]` ones (default: no inlining)"),
     inline_in_all_cgus: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "control whether `#[inline]` functions are in all CGUs"),
     input_stats: bool = (false, parse_bool, [UNTRACKED],
@@ -941,14 +1013,32 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         optimizations (default: no)"),
     instrument_mcount: bool = (false, parse_bool, [TRACKED],
         "insert function instrument code for mcount-based tracing (default: no)"),
+    instrument_mcount_fentry: bool = (false, parse_bool, [TRACKED],
+        "call `__fentry__` before the prologue of every function instead of calling the mcount \
+        function after it, as the function tracer of the Linux kernel expects; implies \
+        `-Z instrument-mcount` (x86 only) (default: no)"),
+    isolate_large_fns: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "place each function with more than this many MIR statements in its own \
+        codegen unit, so that it is optimized in parallel with the rest of the crate \
+        (the number of codegen units is still limited by `-C codegen-units`)"),
+    jit_run: bool = (false, parse_bool, [UNTRACKED],
+        "run a binary crate's `main` in LLVM's JIT instead of linking an executable \
+        (default: no)"),
     keep_hygiene_data: bool = (false, parse_bool, [UNTRACKED],
         "keep hygiene data after analysis (default: no)"),
     link_native_libraries: bool = (true, parse_bool, [UNTRACKED],
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    llvm_stats: bool = (false, parse_bool, [UNTRACKED],
+        "write the statistics counters of LLVM for the optimization, LTO and codegen stages of \
+        each codegen unit to `llvm_stats.json`; requires `-Z no-parallel-llvm` and an LLVM built with \
+        statistics enabled (default: no)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
         "generate JSON tracing data file from LLVM data (default: no)"),
+    location_detail: LocationDetail = (LocationDetail::all(), parse_location_detail, [TRACKED],
+        "which parts of a source location to embed in panic `Location` constants; omitted \
+        parts are replaced by `<redacted>` or zero (default: `file,line,column`)"),
     ls: bool = (false, parse_bool, [UNTRACKED],
         "list the symbols defined by a library crate (default: no)"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
@@ -961,14 +1051,22 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     mir_emit_retag: bool = (false, parse_bool, [TRACKED],
         "emit Retagging MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
+    mir_only_rlibs: bool = (false, parse_bool, [TRACKED],
+        "skip codegen for crates that are only emitted as rlibs and encode the MIR of all \
+        functions instead, so that the crates linking them instantiate what they use \
+        (default: no)"),
     mir_opt_level: usize = (1, parse_uint, [TRACKED],
         "MIR optimization level (0-3; default: 1)"),
-    mutable_noalias: bool = (false, parse_bool, [TRACKED],
-        "emit noalias metadata for mutable references (default: no)"),
+    mutable_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
+        "emit noalias metadata for mutable references, and for `Box` unless set to `no` \
+        (default: only for `Box`)"),
     new_llvm_pass_manager: bool = (false, parse_bool, [TRACKED],
         "use new LLVM pass manager (default: no)"),
     nll_facts: bool = (false, parse_bool, [UNTRACKED],
         "dump facts from NLL analysis into side files (default: no)"),
+    no_addrsig: bool = (false, parse_no_flag, [TRACKED],
+        "don't emit the address-significance tables that allow linkers to safely fold \
+        identical functions, for assemblers that don't support them"),
     no_analysis: bool = (false, parse_no_flag, [UNTRACKED],
         "parse and expand the source, but run no analysis"),
     no_codegen: bool = (false, parse_no_flag, [TRACKED],
@@ -985,8 +1083,17 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "run LLVM in non-parallel mode (while keeping codegen-units and ThinLTO)"),
     no_profiler_runtime: bool = (false, parse_no_flag, [TRACKED],
         "prevent automatic injection of the profiler_builtins crate"),
+    oom: OomStrategy = (OomStrategy::Abort, parse_oom_strategy, [TRACKED],
+        "what the default alloc error handler does on allocation failure: \
+        `panic` (unwindable) or `abort` (default: abort)"),
     osx_rpath_install_name: bool = (false, parse_bool, [TRACKED],
         "pass `-install_name @rpath/...` to the macOS linker (default: no)"),
+    outline_panic_stubs: bool = (false, parse_bool, [TRACKED],
+        "call a shared cold stub per panic message and location for failed \
+        MIR assertions instead of building the panic arguments inline (default: no)"),
+    overflow_trap: OverflowTrap = (OverflowTrap::Panic, parse_overflow_trap, [TRACKED],
+        "whether failed arithmetic overflow checks `panic` or `abort` with a trap \
+        instruction (default: panic)"),
     panic_abort_tests: bool = (false, parse_bool, [TRACKED],
         "support compiling tests with panic=abort (default: no)"),
     parse_only: bool = (false, parse_bool, [UNTRACKED],
@@ -1019,6 +1126,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print the result of the monomorphization collection pass"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
+    print_unused_monomorphizations: bool = (false, parse_bool, [UNTRACKED],
+        "print the functions of this crate that the linker removed as unused; needs a \
+        GNU-like linker and an executable output (default: no)"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
          "show backtraces for panics during proc-macro execution (default: no)"),
     profile: bool = (false, parse_bool, [TRACKED],
@@ -1130,6 +1240,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "in general, enable more debug printouts (default: no)"),
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
+    virtual_function_elimination: bool = (false, parse_bool, [TRACKED],
+        "emit type metadata for vtables and checked loads at virtual calls, so that fat LTO \
+        can devirtualize calls and remove unused vtable entries (default: no)"),
 
     // This list is in alphabetical order.
     //
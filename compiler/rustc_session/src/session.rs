@@ -26,7 +26,7 @@ use rustc_span::edition::Edition;
 use rustc_span::source_map::{FileLoader, MultiSpan, RealFileLoader, SourceMap, Span};
 use rustc_span::{sym, SourceFileHashAlgorithm, Symbol};
 use rustc_target::asm::InlineAsmArch;
-use rustc_target::spec::{CodeModel, PanicStrategy, RelocModel, RelroLevel};
+use rustc_target::spec::{CodeModel, EhStrategy, PanicStrategy, RelocModel, RelroLevel};
 use rustc_target::spec::{Target, TargetTriple, TlsModel};
 
 use std::cell::{self, RefCell};
@@ -141,6 +141,9 @@ pub struct Session {
     /// The maximum blocks a const expression can evaluate.
     pub const_eval_limit: OnceCell<Limit>,
 
+    /// The exit code of the crate run by `-Z jit-run`, which the driver exits with.
+    pub jit_exit_code: OnceCell<i32>,
+
     incr_comp_session: OneThread<RefCell<IncrCompSession>>,
     /// Used for incremental compilation tests. Will only be populated if
     /// `-Zquery-dep-graph` is specified.
@@ -311,6 +314,15 @@ impl Session {
         self.crate_types.set(crate_types).expect("`crate_types` was initialized twice")
     }
 
+    /// Returns `true` if no machine code is generated for this crate because it
+    /// is only emitted as an rlib with `-Z mir-only-rlibs`. The artifact that
+    /// eventually links such a crate codegens its exported items from the
+    /// encoded MIR, once, and every other crate refers to them as external.
+    pub fn is_mir_only_rlib(&self) -> bool {
+        self.opts.debugging_opts.mir_only_rlibs
+            && self.crate_types().iter().all(|&crate_type| crate_type == CrateType::Rlib)
+    }
+
     pub fn recursion_limit(&self) -> Limit {
         self.recursion_limit.get().copied().unwrap()
     }
@@ -573,6 +585,7 @@ impl Session {
     }
     pub fn instrument_mcount(&self) -> bool {
         self.opts.debugging_opts.instrument_mcount
+            || self.opts.debugging_opts.instrument_mcount_fentry
     }
     pub fn time_llvm_passes(&self) -> bool {
         self.opts.debugging_opts.time_llvm_passes
@@ -681,10 +694,31 @@ impl Session {
 
     /// Returns the panic strategy for this compile session. If the user explicitly selected one
     /// using '-C panic', use that, otherwise use the panic strategy defined by the target.
+    /// Targets that cannot unwind always abort.
     pub fn panic_strategy(&self) -> PanicStrategy {
+        if self.eh_strategy() == EhStrategy::None {
+            return PanicStrategy::Abort;
+        }
         self.opts.cg.panic.unwrap_or(self.target.options.panic_strategy)
     }
+
+    /// Returns how unwinding is lowered for this compile session. Wasm targets
+    /// that can't unwind otherwise use the exception-handling proposal once its
+    /// target feature is enabled.
+    pub fn eh_strategy(&self) -> EhStrategy {
+        let strategy = self.target.eh_strategy();
+        let wasm_eh = strategy == EhStrategy::None
+            && self.target.options.is_like_wasm
+            && self.opts.cg.target_feature.split(',').any(|f| f == "+exception-handling");
+        if wasm_eh { EhStrategy::Wasm } else { strategy }
+    }
     pub fn fewer_names(&self) -> bool {
+        // Whether names are kept must not depend on the requested outputs
+        // when builds are meant to be diffed against each other.
+        if self.opts.debugging_opts.deterministic_ir_names {
+            return false;
+        }
+
         let more_names = self.opts.output_types.contains_key(&OutputType::LlvmAssembly)
             || self.opts.output_types.contains_key(&OutputType::Bitcode)
             // AddressSanitizer and MemorySanitizer use alloca name when reporting an issue.
@@ -737,6 +771,10 @@ impl Session {
         self.opts.cg.code_model.or(self.target.options.code_model)
     }
 
+    pub fn soft_float(&self) -> bool {
+        self.opts.cg.soft_float || self.target.options.soft_float
+    }
+
     pub fn tls_model(&self) -> TlsModel {
         self.opts.debugging_opts.tls_model.unwrap_or(self.target.options.tls_model)
     }
@@ -770,13 +808,13 @@ impl Session {
         //
         // If a target requires unwind tables, then they must be emitted.
         // Otherwise, we can defer to the `-C force-unwind-tables=<yes/no>`
-        // value, if it is provided, or disable them, if not.
+        // value, if it is provided, or to the target's default, if not.
         if self.panic_strategy() == PanicStrategy::Unwind {
             true
         } else if self.target.options.requires_uwtable {
             true
         } else {
-            self.opts.cg.force_unwind_tables.unwrap_or(false)
+            self.opts.cg.force_unwind_tables.unwrap_or(self.target.options.default_uwtable)
         }
     }
 
@@ -1391,6 +1429,7 @@ pub fn build_session(
         recursion_limit: OnceCell::new(),
         type_length_limit: OnceCell::new(),
         const_eval_limit: OnceCell::new(),
+        jit_exit_code: OnceCell::new(),
         incr_comp_session: OneThread::new(RefCell::new(IncrCompSession::NotInitialized)),
         cgu_reuse_tracker,
         prof,
@@ -1445,6 +1484,13 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         );
     }
 
+    if sess.opts.cg.panic == Some(PanicStrategy::Unwind) && sess.eh_strategy() == EhStrategy::None {
+        sess.warn(&format!(
+            "the `{}` target cannot unwind, so `-C panic=unwind` is ignored and panics abort",
+            sess.opts.target_triple
+        ));
+    }
+
     // Make sure that any given profiling data actually exists so LLVM can't
     // decide to silently skip PGO.
     if let Some(ref path) = sess.opts.cg.profile_use {
@@ -1473,6 +1519,46 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // Code and relocation models are validated here rather than left to LLVM,
+    // which aborts on the ones a target lacks.
+    if let Some(code_model) = sess.opts.cg.code_model {
+        if !code_model.is_supported_on(&sess.target.arch) {
+            sess.err(&format!(
+                "`-C code-model={}` is not supported on the `{}` architecture",
+                code_model.desc(),
+                sess.target.arch
+            ));
+        } else if code_model == CodeModel::Tiny
+            && (sess.target.options.is_like_osx || sess.target.options.is_like_windows)
+        {
+            // LLVM only implements the tiny code model for ELF.
+            sess.err("`-C code-model=tiny` is only supported for ELF targets");
+        }
+    }
+    if let Some(relocation_model) = sess.opts.cg.relocation_model {
+        if !relocation_model.is_supported_on(&sess.target.arch) {
+            sess.err(&format!(
+                "`-C relocation-model={}` is not supported on the `{}` architecture",
+                relocation_model.desc(),
+                sess.target.arch
+            ));
+        }
+    }
+
+    // LLVM only emits `.stack_sizes` sections in ELF object files.
+    let target_options = &sess.target.options;
+    if sess.opts.debugging_opts.emit_stack_sizes
+        && (target_options.is_like_osx
+            || target_options.is_like_windows
+            || target_options.is_like_wasm)
+    {
+        sess.warn(&format!(
+            "`-Z emit-stack-sizes` is ignored on the `{}` target, \
+             which doesn't use ELF object files",
+            sess.opts.target_triple
+        ));
+    }
+
     // PGO does not work reliably with panic=unwind on Windows. Let's make it
     // an error to combine the two for now. It always runs into an assertions
     // if LLVM is built with assertions, but without assertions it sometimes
@@ -1492,6 +1578,18 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         );
     }
 
+    if sess.opts.debugging_opts.instrument_mcount_fentry
+        && sess.target.arch != "x86"
+        && sess.target.arch != "x86_64"
+    {
+        sess.err("`-Z instrument-mcount-fentry` is only supported on x86 targets");
+    }
+
+    // Vtable entries can only be dropped when every virtual call is visible.
+    if sess.opts.debugging_opts.virtual_function_elimination && sess.lto() != config::Lto::Fat {
+        sess.err("`-Z virtual-function-elimination` requires `-C lto`");
+    }
+
     const ASAN_SUPPORTED_TARGETS: &[&str] = &[
         "aarch64-fuchsia",
         "aarch64-unknown-linux-gnu",
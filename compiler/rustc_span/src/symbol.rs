@@ -218,6 +218,7 @@ symbols! {
         abi,
         abi_amdgpu_kernel,
         abi_avr_interrupt,
+        abi_c_cmse_nonsecure_call,
         abi_efiapi,
         abi_msp430_interrupt,
         abi_ptx,
@@ -232,6 +233,7 @@ symbols! {
         add_assign,
         add_with_overflow,
         address,
+        address_space,
         advanced_slice_patterns,
         adx_target_feature,
         alias,
@@ -335,6 +337,7 @@ symbols! {
         char,
         client,
         clippy,
+        clobber_abi,
         clone,
         clone_closures,
         clone_from,
@@ -566,6 +569,7 @@ symbols! {
         hash,
         hexagon_target_feature,
         hidden,
+        hit_counter,
         homogeneous_aggregate,
         html_favicon_url,
         html_logo_url,
@@ -643,6 +647,7 @@ symbols! {
         lint_reasons,
         literal,
         llvm_asm,
+        local,
         local_inner_macros,
         log10f32,
         log10f64,
@@ -751,6 +756,7 @@ symbols! {
         nostack,
         not,
         note,
+        nvptx_address_space,
         object_safe_for_dispatch,
         of,
         offset,
@@ -849,6 +855,8 @@ symbols! {
         quote,
         range_inclusive_new,
         raw_dylib,
+        raw_eq,
+        raw_eq_bytes,
         raw_identifiers,
         raw_ref_op,
         re_rebalance_coherence,
@@ -929,8 +937,10 @@ symbols! {
         rustc_layout_scalar_valid_range_start,
         rustc_macro_transparency,
         rustc_mir,
+        rustc_must_tail,
         rustc_nonnull_optimization_guaranteed,
         rustc_object_lifetime_default,
+        rustc_operand_bundle,
         rustc_on_unimplemented,
         rustc_outlives,
         rustc_paren_sugar,
@@ -970,6 +980,7 @@ symbols! {
         send_trait,
         shl,
         shl_assign,
+        shared,
         should_panic,
         shr,
         shr_assign,
@@ -1161,6 +1172,8 @@ symbols! {
         unix,
         unlikely,
         unmarked_api,
+        unordered_atomic_copy_memory,
+        unordered_atomic_set_memory,
         unpin,
         unreachable,
         unreachable_code,
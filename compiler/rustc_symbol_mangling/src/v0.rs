@@ -13,6 +13,9 @@ use rustc_target::spec::abi::Abi;
 use std::fmt::Write;
 use std::ops::Range;
 
+#[cfg(test)]
+mod tests;
+
 pub(super) fn mangle(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
@@ -132,19 +135,10 @@ impl SymbolMangler<'tcx> {
         let ident = if use_punycode {
             self.push("u");
 
-            // FIXME(eddyb) we should probably roll our own punycode implementation.
-            let mut punycode_bytes = match punycode::encode(ident) {
-                Ok(s) => s.into_bytes(),
-                Err(()) => bug!("symbol_names: punycode encoding failed for ident {:?}", ident),
+            punycode_string = match punycode_encode(ident) {
+                Some(s) => s,
+                None => bug!("symbol_names: punycode encoding failed for ident {:?}", ident),
             };
-
-            // Replace `-` with `_`.
-            if let Some(c) = punycode_bytes.iter_mut().rfind(|&&mut c| c == b'-') {
-                *c = b'_';
-            }
-
-            // FIXME(eddyb) avoid rechecking UTF-8 validity.
-            punycode_string = String::from_utf8(punycode_bytes).unwrap();
             &punycode_string
         } else {
             ident
@@ -677,3 +671,89 @@ impl Printer<'tcx> for SymbolMangler<'tcx> {
         Ok(self)
     }
 }
+
+/// Encodes `input` using the Punycode algorithm from RFC 3492, except that
+/// the delimiter between the basic and the encoded code points is `_`
+/// instead of `-`, so that the result is a valid identifier.
+///
+/// Returns `None` if the encoding would overflow, which can't happen for
+/// identifiers of any reasonable length.
+fn punycode_encode(input: &str) -> Option<String> {
+    const BASE: u32 = 36;
+    const T_MIN: u32 = 1;
+    const T_MAX: u32 = 26;
+    const SKEW: u32 = 38;
+    const DAMP: u32 = 700;
+    const INITIAL_BIAS: u32 = 72;
+    const INITIAL_N: u32 = 0x80;
+
+    fn adapt(mut delta: u32, num_points: u32, first_time: bool) -> u32 {
+        delta /= if first_time { DAMP } else { 2 };
+        delta += delta / num_points;
+        let mut k = 0;
+        while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+            delta /= BASE - T_MIN;
+            k += BASE;
+        }
+        k + (((BASE - T_MIN + 1) * delta) / (delta + SKEW))
+    }
+
+    fn encode_digit(d: u32) -> char {
+        match d {
+            0..=25 => (b'a' + d as u8) as char,
+            _ => (b'0' + (d - 26) as u8) as char,
+        }
+    }
+
+    // All the basic (ASCII) code points come first, in their original order.
+    let mut output: String = input.chars().filter(|c| c.is_ascii()).collect();
+    let basic_len = output.len() as u32;
+    if basic_len > 0 {
+        output.push('_');
+    }
+
+    // Then the remaining code points are encoded as a sequence of
+    // generalized variable-length integers describing insertion deltas.
+    let input_len = input.chars().count() as u32;
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut h = basic_len;
+    while h < input_len {
+        let m = input.chars().map(|c| c as u32).filter(|&c| c >= n).min()?;
+        delta = delta.checked_add((m - n).checked_mul(h + 1)?)?;
+        n = m;
+        for c in input.chars().map(|c| c as u32) {
+            if c < n {
+                delta = delta.checked_add(1)?;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = if k <= bias {
+                        T_MIN
+                    } else if k >= bias + T_MAX {
+                        T_MAX
+                    } else {
+                        k - bias
+                    };
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, h + 1, h == basic_len);
+                delta = 0;
+                h += 1;
+            }
+        }
+        delta = delta.checked_add(1)?;
+        n += 1;
+    }
+
+    Some(output)
+}
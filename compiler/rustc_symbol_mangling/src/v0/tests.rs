@@ -0,0 +1,15 @@
+use super::punycode_encode;
+
+#[test]
+fn test_punycode_encode() {
+    fn test(input: &str, expected: &str) {
+        assert_eq!(punycode_encode(input).as_deref(), Some(expected));
+    }
+
+    test("ü", "tda");
+    test("αβγ", "mxacd");
+    test("bücher", "bcher_kva");
+    test("München", "Mnchen_3ya");
+    test("ñandú", "and_6ma2c");
+    test("_ü", "__eha");
+}
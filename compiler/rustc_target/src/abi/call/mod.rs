@@ -432,9 +432,7 @@ impl<'a, Ty> ArgAbi<'a, Ty> {
         // program-invisible so can't possibly capture
         attrs.set(ArgAttribute::NoAlias).set(ArgAttribute::NoCapture).set(ArgAttribute::NonNull);
         attrs.pointee_size = self.layout.size;
-        // FIXME(eddyb) We should be doing this, but at least on
-        // i686-pc-windows-msvc, it results in wrong stack offsets.
-        // attrs.pointee_align = Some(self.layout.align.abi);
+        attrs.pointee_align = Some(self.layout.align.abi);
 
         let extra_attrs = self.layout.is_unsized().then_some(ArgAttributes::new());
 
@@ -446,6 +444,10 @@ impl<'a, Ty> ArgAbi<'a, Ty> {
         match self.mode {
             PassMode::Indirect(ref mut attrs, _) => {
                 attrs.set(ArgAttribute::ByVal);
+                // FIXME(eddyb) `byval` arguments should keep their alignment too,
+                // but at least on i686-pc-windows-msvc, it results in wrong stack
+                // offsets, since LLVM also uses it to align the argument's slot.
+                attrs.pointee_align = None;
             }
             _ => unreachable!(),
         }
@@ -511,6 +513,7 @@ pub enum Conv {
 
     // Target-specific calling conventions.
     ArmAapcs,
+    CCmseNonSecureCall,
 
     Msp430Intr,
 
@@ -599,7 +602,7 @@ impl<'a, Ty> FnAbi<'a, Ty> {
             "wasm32" if cx.target_spec().target_os != "emscripten" => {
                 wasm32_bindgen_compat::compute_abi_info(self)
             }
-            "wasm32" | "asmjs" => wasm32::compute_abi_info(cx, self),
+            "wasm32" | "wasm64" | "asmjs" => wasm32::compute_abi_info(cx, self),
             a => return Err(format!("unrecognized arch \"{}\" in target specification", a)),
         }
 
@@ -1,10 +1,10 @@
-use crate::abi::call::{ArgAbi, FnAbi, Reg};
-use crate::abi::Abi;
+use crate::abi::call::{ArgAbi, FnAbi, Reg, RegKind};
+use crate::abi::{Abi, Size};
 
 // Win64 ABI: https://docs.microsoft.com/en-us/cpp/build/parameter-passing
 
 pub fn compute_abi_info<Ty>(fn_abi: &mut FnAbi<'_, Ty>) {
-    let fixup = |a: &mut ArgAbi<'_, Ty>| {
+    let fixup = |a: &mut ArgAbi<'_, Ty>, is_ret: bool| {
         match a.layout.abi {
             Abi::Uninhabited => {}
             Abi::ScalarPair(..) | Abi::Aggregate { .. } => match a.layout.size.bits() {
@@ -19,7 +19,12 @@ pub fn compute_abi_info<Ty>(fn_abi: &mut FnAbi<'_, Ty>) {
                 // (probably what clang calls "illegal vectors").
             }
             Abi::Scalar(_) => {
-                if a.layout.size.bytes() > 8 {
+                if is_ret && a.layout.size.bits() == 128 {
+                    // Like clang, return `i128` and `u128` in XMM0 rather than
+                    // through a hidden pointer. Arguments are still passed
+                    // indirectly.
+                    a.cast_to(Reg { kind: RegKind::Vector, size: Size::from_bits(128) });
+                } else if a.layout.size.bytes() > 8 {
                     a.make_indirect();
                 } else {
                     a.extend_integer_width_to(32);
@@ -29,12 +34,12 @@ pub fn compute_abi_info<Ty>(fn_abi: &mut FnAbi<'_, Ty>) {
     };
 
     if !fn_abi.ret.is_ignore() {
-        fixup(&mut fn_abi.ret);
+        fixup(&mut fn_abi.ret, true);
     }
     for arg in &mut fn_abi.args {
         if arg.is_ignore() {
             continue;
         }
-        fixup(arg);
+        fixup(arg, false);
     }
 }
@@ -12,6 +12,7 @@ def_reg_class! {
         ymm_reg,
         zmm_reg,
         kreg,
+        kreg0,
     }
 }
 
@@ -34,7 +35,7 @@ impl X86InlineAsmRegClass {
             }
             Self::reg_byte => &[],
             Self::xmm_reg | Self::ymm_reg | Self::zmm_reg => &['x', 'y', 'z'],
-            Self::kreg => &[],
+            Self::kreg | Self::kreg0 => &[],
         }
     }
 
@@ -72,7 +73,7 @@ impl X86InlineAsmRegClass {
                 256 => Some(('y', "ymm0")),
                 _ => Some(('x', "xmm0")),
             },
-            Self::kreg => None,
+            Self::kreg | Self::kreg0 => None,
         }
     }
 
@@ -89,7 +90,7 @@ impl X86InlineAsmRegClass {
             Self::xmm_reg => Some(('x', "xmm0")),
             Self::ymm_reg => Some(('y', "ymm0")),
             Self::zmm_reg => Some(('z', "zmm0")),
-            Self::kreg => None,
+            Self::kreg | Self::kreg0 => None,
         }
     }
 
@@ -125,8 +126,19 @@ impl X86InlineAsmRegClass {
                 "avx512f": I8, I16;
                 "avx512bw": I32, I64;
             },
+            // `k0` holds the same values as the other mask registers, so it is
+            // gated on the same target features, but it can't be used as a
+            // write mask and is only ever clobbered (see `is_clobber_only`).
+            Self::kreg0 => types! {
+                "avx512f": I8, I16;
+                "avx512bw": I32, I64;
+            },
         }
     }
+
+    pub fn is_clobber_only(self) -> bool {
+        matches!(self, Self::kreg0)
+    }
 }
 
 fn x86_64_only(
@@ -255,6 +267,7 @@ def_regs! {
         zmm29: zmm_reg = ["zmm29", "xmm29", "ymm29"] % x86_64_only,
         zmm30: zmm_reg = ["zmm30", "xmm30", "ymm30"] % x86_64_only,
         zmm31: zmm_reg = ["zmm31", "xmm31", "ymm31"] % x86_64_only,
+        k0: kreg0 = ["k0"],
         k1: kreg = ["k1"],
         k2: kreg = ["k2"],
         k3: kreg = ["k3"],
@@ -272,8 +285,6 @@ def_regs! {
             "x87 registers are not currently supported as operands for inline asm",
         #error = ["mm0", "mm1", "mm2", "mm3", "mm4", "mm5", "mm6", "mm7"] =>
             "MMX registers are not currently supported as operands for inline asm",
-        #error = ["k0"] =>
-            "the k0 AVX mask register cannot be used as an operand for inline asm",
     }
 }
 
@@ -33,6 +33,8 @@ pub fn opts() -> TargetOptions {
         abi_return_struct_as_int: true,
         emit_debug_gdb_scripts: false,
         eh_frame_header: false,
+        // Like clang, so that profilers and backtraces work with panic=abort.
+        default_uwtable: true,
 
         // This environment variable is pretty magical but is intended for
         // producing deterministic builds. This was first discovered to be used
@@ -185,6 +185,50 @@ impl ToJson for PanicStrategy {
     }
 }
 
+/// How unwinding is lowered to LLVM IR for a target.
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable)]
+pub enum EhStrategy {
+    /// Itanium-style `invoke` and `landingpad`, backed by DWARF or SjLj tables.
+    LandingPads,
+    /// Funclets (`catchswitch`, `cleanuppad`) for the MSVC C++ runtime.
+    MsvcSeh,
+    /// Funclets for the WebAssembly exception-handling proposal.
+    Wasm,
+    /// The target cannot unwind: calls are never `invoke`s and panics abort.
+    None,
+}
+
+impl EhStrategy {
+    pub fn desc(&self) -> &str {
+        match *self {
+            EhStrategy::LandingPads => "landing-pads",
+            EhStrategy::MsvcSeh => "msvc-seh",
+            EhStrategy::Wasm => "wasm",
+            EhStrategy::None => "none",
+        }
+    }
+}
+
+impl FromStr for EhStrategy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<EhStrategy, ()> {
+        match s {
+            "landing-pads" => Ok(EhStrategy::LandingPads),
+            "msvc-seh" => Ok(EhStrategy::MsvcSeh),
+            "wasm" => Ok(EhStrategy::Wasm),
+            "none" => Ok(EhStrategy::None),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ToJson for EhStrategy {
+    fn to_json(&self) -> Json {
+        self.desc().to_json()
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable)]
 pub enum RelroLevel {
     Full,
@@ -295,6 +339,29 @@ impl FromStr for RelocModel {
     }
 }
 
+impl RelocModel {
+    pub fn desc(&self) -> &str {
+        match *self {
+            RelocModel::Static => "static",
+            RelocModel::Pic => "pic",
+            RelocModel::DynamicNoPic => "dynamic-no-pic",
+            RelocModel::Ropi => "ropi",
+            RelocModel::Rwpi => "rwpi",
+            RelocModel::RopiRwpi => "ropi-rwpi",
+        }
+    }
+
+    /// Whether code for `arch` can be generated with this relocation model.
+    /// The read-only and read-write position independent models only exist
+    /// for ARM.
+    pub fn is_supported_on(&self, arch: &str) -> bool {
+        match *self {
+            RelocModel::Ropi | RelocModel::Rwpi | RelocModel::RopiRwpi => arch == "arm",
+            RelocModel::Static | RelocModel::Pic | RelocModel::DynamicNoPic => true,
+        }
+    }
+}
+
 impl ToJson for RelocModel {
     fn to_json(&self) -> Json {
         match *self {
@@ -333,6 +400,30 @@ impl FromStr for CodeModel {
     }
 }
 
+impl CodeModel {
+    pub fn desc(&self) -> &str {
+        match *self {
+            CodeModel::Tiny => "tiny",
+            CodeModel::Small => "small",
+            CodeModel::Kernel => "kernel",
+            CodeModel::Medium => "medium",
+            CodeModel::Large => "large",
+        }
+    }
+
+    /// Whether code for `arch` can be generated with this code model. LLVM
+    /// aborts, rather than reporting an error, when it is asked for a code
+    /// model the backend of `arch` lacks.
+    pub fn is_supported_on(&self, arch: &str) -> bool {
+        match *self {
+            CodeModel::Tiny => arch == "aarch64",
+            CodeModel::Kernel => arch == "x86" || arch == "x86_64",
+            CodeModel::Medium => arch != "aarch64",
+            CodeModel::Small | CodeModel::Large => true,
+        }
+    }
+}
+
 impl ToJson for CodeModel {
     fn to_json(&self) -> Json {
         match *self {
@@ -603,6 +694,7 @@ supported_targets! {
     ("wasm32-unknown-emscripten", wasm32_unknown_emscripten),
     ("wasm32-unknown-unknown", wasm32_unknown_unknown),
     ("wasm32-wasi", wasm32_wasi),
+    ("wasm64-unknown-unknown", wasm64_unknown_unknown),
 
     ("thumbv6m-none-eabi", thumbv6m_none_eabi),
     ("thumbv7m-none-eabi", thumbv7m_none_eabi),
@@ -816,6 +908,9 @@ pub struct TargetOptions {
     pub is_like_emscripten: bool,
     /// Whether the target toolchain is like Fuchsia's.
     pub is_like_fuchsia: bool,
+    /// Whether the target is WebAssembly, of either pointer width, which has
+    /// its own custom sections, import modules and linker. Defaults to false.
+    pub is_like_wasm: bool,
     /// Version of DWARF to use if not using the default.
     /// Useful because some platforms (osx, bsd) only want up to DWARF2.
     pub dwarf_version: Option<u32>,
@@ -848,10 +943,9 @@ pub struct TargetOptions {
     /// resolve all symbols at startup and marks the GOT read-only before
     /// starting the program, preventing overwriting the GOT.
     pub relro_level: RelroLevel,
-    /// Format that archives should be emitted in. This affects whether we use
-    /// LLVM to assemble an archive or fall back to the system linker, and
-    /// currently only "gnu" is used to fall into LLVM. Unknown strings cause
-    /// the system linker to be used.
+    /// Format that archives should be emitted in. Archives are always written
+    /// by LLVM's archive writer, which understands "gnu", "bsd", "darwin" and
+    /// "coff". Unknown strings are a fatal error when building an archive.
     pub archive_format: String,
     /// Is asm!() allowed? Defaults to true.
     pub allow_asm: bool,
@@ -883,6 +977,11 @@ pub struct TargetOptions {
     /// Panic strategy: "unwind" or "abort"
     pub panic_strategy: PanicStrategy,
 
+    /// How unwinding is lowered: "landing-pads", "msvc-seh", "wasm" or "none".
+    /// Targets with "none" always use `panic=abort`.
+    /// Don't use this field; instead use the `.eh_strategy()` method.
+    pub eh_strategy: Option<EhStrategy>,
+
     /// A list of ABIs unsupported by the current target. Note that generic ABIs
     /// are considered to be supported on all platforms and cannot be marked
     /// unsupported.
@@ -915,6 +1014,11 @@ pub struct TargetOptions {
     /// This target has no support for threads.
     pub singlethread: bool,
 
+    /// Whether floating point operations are lowered to library calls rather
+    /// than using FPU or SIMD registers, which also implies the soft float ABI.
+    /// Kernels and early boot code use this to keep such registers untouched.
+    pub soft_float: bool,
+
     /// Whether library functions call lowering/optimization is disabled in LLVM
     /// for this target unconditionally.
     pub no_builtins: bool,
@@ -931,6 +1035,12 @@ pub struct TargetOptions {
     /// unwinders.
     pub requires_uwtable: bool,
 
+    /// Whether or not to emit `uwtable` attributes on functions when they are
+    /// neither required by the target nor by `-C panic=unwind`, and
+    /// `-C force-unwind-tables` isn't given, e.g. because profilers and
+    /// backtraces on the platform rely on them.
+    pub default_uwtable: bool,
+
     /// Whether or not SIMD types are passed by reference in the Rust ABI,
     /// typically required if a target can be compiled with a mixed set of
     /// target features. This is `true` by default, and `false` for targets like
@@ -1015,6 +1125,7 @@ impl Default for TargetOptions {
             is_like_emscripten: false,
             is_like_msvc: false,
             is_like_fuchsia: false,
+            is_like_wasm: false,
             dwarf_version: None,
             linker_is_gnu: false,
             allows_weak_linkage: true,
@@ -1045,6 +1156,7 @@ impl Default for TargetOptions {
             max_atomic_width: None,
             atomic_cas: true,
             panic_strategy: PanicStrategy::Unwind,
+            eh_strategy: None,
             unsupported_abis: vec![],
             crt_static_allows_dylibs: false,
             crt_static_default: false,
@@ -1055,10 +1167,12 @@ impl Default for TargetOptions {
             trap_unreachable: true,
             requires_lto: false,
             singlethread: false,
+            soft_float: false,
             no_builtins: false,
             default_hidden_visibility: false,
             emit_debug_gdb_scripts: true,
             requires_uwtable: false,
+            default_uwtable: false,
             simd_types_indirect: true,
             limit_rdylib_exports: true,
             override_export_symbols: None,
@@ -1114,8 +1228,27 @@ impl Target {
         self.options.max_atomic_width.unwrap_or_else(|| self.pointer_width.into())
     }
 
+    /// How unwinding is lowered for this target. Unless the target specifies
+    /// it, MSVC-like targets use funclets, and wasm targets other than
+    /// Emscripten (which unwinds through JavaScript) can't unwind at all.
+    pub fn eh_strategy(&self) -> EhStrategy {
+        self.options.eh_strategy.unwrap_or_else(|| {
+            if self.options.is_like_msvc {
+                EhStrategy::MsvcSeh
+            } else if self.options.is_like_wasm && !self.options.is_like_emscripten {
+                EhStrategy::None
+            } else {
+                EhStrategy::LandingPads
+            }
+        })
+    }
+
     pub fn is_abi_supported(&self, abi: Abi) -> bool {
-        abi.generic() || !self.options.unsupported_abis.contains(&abi)
+        match abi {
+            // Calling non-secure code requires the TrustZone-M extension.
+            Abi::CCmseNonSecureCall => self.llvm_target.contains("thumbv8m"),
+            _ => abi.generic() || !self.options.unsupported_abis.contains(&abi),
+        }
     }
 
     /// Loads a target descriptor from a JSON object.
@@ -1248,6 +1381,19 @@ impl Target {
                 Some(Ok(()))
             })).unwrap_or(Ok(()))
             } );
+            ($key_name:ident, EhStrategy) => ( {
+                let name = (stringify!($key_name)).replace("_", "-");
+                obj.find(&name[..]).and_then(|o| o.as_string().and_then(|s| {
+                    match s.parse::<EhStrategy>() {
+                        Ok(strategy) => base.options.$key_name = Some(strategy),
+                        _ => return Some(Err(format!("'{}' is not a valid value for \
+                                                      eh-strategy. Use 'landing-pads', \
+                                                      'msvc-seh', 'wasm' or 'none'.",
+                                                      s))),
+                    }
+                    Some(Ok(()))
+                })).unwrap_or(Ok(()))
+            } );
             ($key_name:ident, RelroLevel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
                 obj.find(&name[..]).and_then(|o| o.as_string().and_then(|s| {
@@ -1432,6 +1578,7 @@ impl Target {
         key!(is_like_emscripten, bool);
         key!(is_like_android, bool);
         key!(is_like_fuchsia, bool);
+        key!(is_like_wasm, bool);
         key!(dwarf_version, Option<u32>);
         key!(linker_is_gnu, bool);
         key!(allows_weak_linkage, bool);
@@ -1452,6 +1599,7 @@ impl Target {
         key!(min_atomic_width, Option<u64>);
         key!(atomic_cas, bool);
         key!(panic_strategy, PanicStrategy)?;
+        key!(eh_strategy, EhStrategy)?;
         key!(crt_static_allows_dylibs, bool);
         key!(crt_static_default, bool);
         key!(crt_static_respected, bool);
@@ -1461,10 +1609,12 @@ impl Target {
         key!(trap_unreachable, bool);
         key!(requires_lto, bool);
         key!(singlethread, bool);
+        key!(soft_float, bool);
         key!(no_builtins, bool);
         key!(default_hidden_visibility, bool);
         key!(emit_debug_gdb_scripts, bool);
         key!(requires_uwtable, bool);
+        key!(default_uwtable, bool);
         key!(simd_types_indirect, bool);
         key!(limit_rdylib_exports, bool);
         key!(override_export_symbols, opt_list);
@@ -1670,6 +1820,7 @@ impl ToJson for Target {
         target_option_val!(is_like_emscripten);
         target_option_val!(is_like_android);
         target_option_val!(is_like_fuchsia);
+        target_option_val!(is_like_wasm);
         target_option_val!(dwarf_version);
         target_option_val!(linker_is_gnu);
         target_option_val!(allows_weak_linkage);
@@ -1690,6 +1841,7 @@ impl ToJson for Target {
         target_option_val!(max_atomic_width);
         target_option_val!(atomic_cas);
         target_option_val!(panic_strategy);
+        target_option_val!(eh_strategy);
         target_option_val!(crt_static_allows_dylibs);
         target_option_val!(crt_static_default);
         target_option_val!(crt_static_respected);
@@ -1699,10 +1851,12 @@ impl ToJson for Target {
         target_option_val!(trap_unreachable);
         target_option_val!(requires_lto);
         target_option_val!(singlethread);
+        target_option_val!(soft_float);
         target_option_val!(no_builtins);
         target_option_val!(default_hidden_visibility);
         target_option_val!(emit_debug_gdb_scripts);
         target_option_val!(requires_uwtable);
+        target_option_val!(default_uwtable);
         target_option_val!(simd_types_indirect);
         target_option_val!(limit_rdylib_exports);
         target_option_val!(override_export_symbols);
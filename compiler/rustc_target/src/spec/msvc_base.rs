@@ -23,6 +23,7 @@ pub fn opts() -> TargetOptions {
         is_like_msvc: true,
         lld_flavor: LldFlavor::Link,
         pre_link_args,
+        archive_format: "coff".to_string(),
         abi_return_struct_as_int: true,
         emit_debug_gdb_scripts: false,
 
@@ -140,6 +140,8 @@ pub fn options() -> TargetOptions {
         // gdb scripts don't work on wasm blobs
         emit_debug_gdb_scripts: false,
 
+        is_like_wasm: true,
+
         ..Default::default()
     }
 }
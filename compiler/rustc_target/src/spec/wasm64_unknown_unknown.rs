@@ -0,0 +1,47 @@
+//! A "bare wasm" target like `wasm32-unknown-unknown`, but with 64-bit
+//! pointers into a memory using the memory64 proposal, for experimenting with
+//! heaps larger than 4GB.
+//!
+//! The only difference from `wasm32-unknown-unknown` is the pointer width, so
+//! see that target for details. Runtimes need to support memory64 to run
+//! modules built for this target.
+
+use super::wasm32_base;
+use super::{LinkerFlavor, LldFlavor, Target};
+
+pub fn target() -> Target {
+    let mut options = wasm32_base::options();
+    let clang_args = options.pre_link_args.get_mut(&LinkerFlavor::Gcc).unwrap();
+
+    // Make sure clang uses LLD as its linker and is configured appropriately
+    // otherwise
+    clang_args.push("--target=wasm64-unknown-unknown".to_string());
+
+    // For now this target just never has an entry symbol no matter the output
+    // type, so unconditionally pass this.
+    clang_args.push("-Wl,--no-entry".to_string());
+
+    // LLD defaults to 32-bit memories, and only enables memory64 in the linked
+    // module when asked to.
+    let lld_args = options.pre_link_args.get_mut(&LinkerFlavor::Lld(LldFlavor::Wasm)).unwrap();
+    lld_args.push("--no-entry".to_string());
+    lld_args.push("-mwasm64".to_string());
+
+    // Any code generated for this target accesses memory with 64-bit
+    // addresses, which is what the memory64 feature provides.
+    options.features = "+memory64".to_string();
+
+    Target {
+        llvm_target: "wasm64-unknown-unknown".to_string(),
+        target_endian: "little".to_string(),
+        pointer_width: 64,
+        target_c_int_width: "32".to_string(),
+        target_os: "unknown".to_string(),
+        target_env: String::new(),
+        target_vendor: "unknown".to_string(),
+        data_layout: "e-m:e-p:64:64-i64:64-n32:64-S128".to_string(),
+        arch: "wasm64".to_string(),
+        linker_flavor: LinkerFlavor::Lld(LldFlavor::Wasm),
+        options,
+    }
+}
@@ -40,6 +40,18 @@ pub(super) fn check_abi(tcx: TyCtxt<'_>, span: Span, abi: Abi) {
         )
         .emit()
     }
+
+    // Functions with this ABI are non-secure code, which can be called through
+    // a function pointer but not be defined or declared in Rust.
+    if abi == Abi::CCmseNonSecureCall {
+        struct_span_err!(
+            tcx.sess,
+            span,
+            E0780,
+            "the `\"C-cmse-nonsecure-call\"` ABI is only allowed on function pointers"
+        )
+        .emit()
+    }
 }
 
 /// Helper used for fns and closures. Does the grungy work of checking a function
@@ -648,10 +660,20 @@ pub fn check_item_type<'tcx>(tcx: TyCtxt<'tcx>, it: &'tcx hir::Item<'tcx>) {
         hir::ItemKind::Enum(ref enum_definition, _) => {
             check_enum(tcx, it.span, &enum_definition.variants, it.hir_id);
         }
-        hir::ItemKind::Fn(..) => {} // entirely within check_item_body
+        hir::ItemKind::Fn(..) => {
+            // the body is checked entirely within check_item_body
+            let def_id = tcx.hir().local_def_id(it.hir_id);
+            maybe_check_fn_with_link_section(tcx, def_id, it.span);
+        }
         hir::ItemKind::Impl { ref items, .. } => {
             debug!("ItemKind::Impl {} with id {}", it.ident, it.hir_id);
             let impl_def_id = tcx.hir().local_def_id(it.hir_id);
+            for item in items.iter() {
+                if let hir::AssocItemKind::Fn { .. } = item.kind {
+                    let def_id = tcx.hir().local_def_id(item.id.hir_id);
+                    maybe_check_fn_with_link_section(tcx, def_id, item.span);
+                }
+            }
             if let Some(impl_trait_ref) = tcx.impl_trait_ref(impl_def_id) {
                 check_impl_items_against_trait(tcx, it.span, impl_def_id, impl_trait_ref, items);
                 let trait_def_id = impl_trait_ref.def_id;
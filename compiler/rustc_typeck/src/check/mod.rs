@@ -112,6 +112,7 @@ use rustc_hir::{HirIdMap, Node};
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::Idx;
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::ty::fold::{TypeFoldable, TypeFolder};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::subst::GenericArgKind;
@@ -119,6 +120,7 @@ use rustc_middle::ty::subst::{InternalSubsts, Subst, SubstsRef};
 use rustc_middle::ty::WithConstness;
 use rustc_middle::ty::{self, RegionKind, Ty, TyCtxt, UserType};
 use rustc_session::config;
+use rustc_session::lint::builtin::CONFLICTING_LINK_SECTION;
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::source_map::DUMMY_SP;
@@ -745,15 +747,112 @@ fn fn_maybe_err(tcx: TyCtxt<'_>, sp: Span, abi: Abi) {
     }
 }
 
-fn maybe_check_static_with_link_section(tcx: TyCtxt<'_>, id: LocalDefId, span: Span) {
-    // Only restricted on wasm32 target for now
-    if !tcx.sess.opts.target_triple.triple().starts_with("wasm32") {
+/// Returns whether `section` is one of `sections`, or a subsection of one of
+/// them such as `.data.foo` for `.data`. COFF linkers also group `.rdata$foo`
+/// into `.rdata`, ordered by the suffix after the `$`.
+fn is_in_section(tcx: TyCtxt<'_>, section: &str, sections: &[&str]) -> bool {
+    let coff = tcx.sess.target.options.is_like_windows;
+    sections.iter().any(|&s| {
+        section.strip_prefix(s).map_or(false, |rest| {
+            rest.is_empty() || rest.starts_with('.') || (coff && rest.starts_with('$'))
+        })
+    })
+}
+
+fn lint_link_section_conflict(tcx: TyCtxt<'_>, id: LocalDefId, span: Span, msg: &str) {
+    let hir_id = tcx.hir().local_def_id_to_hir_id(id);
+    tcx.struct_span_lint_hir(CONFLICTING_LINK_SECTION, hir_id, span, |lint| {
+        lint.build(msg).emit();
+    });
+}
+
+fn maybe_check_fn_with_link_section(tcx: TyCtxt<'_>, id: LocalDefId, span: Span) {
+    // Mach-O sections are named `segment,section` and wasm custom sections
+    // are opaque, so the checks below only apply to ELF and COFF names.
+    let target = &tcx.sess.target.options;
+    if target.is_like_osx || target.is_like_wasm {
+        return;
+    }
+
+    let section = match tcx.codegen_fn_attrs(id).link_section {
+        Some(section) => section.as_str(),
+        None => return,
+    };
+
+    // Code placed into a data section would end up in memory that isn't
+    // executable.
+    if is_in_section(tcx, &section, &[".data", ".bss", ".rodata", ".rdata", ".tdata", ".tbss"]) {
+        let msg = format!("functions cannot be placed in the data section `{}`", section);
+        lint_link_section_conflict(tcx, id, span, &msg);
+    }
+}
+
+fn check_static_section_flags(tcx: TyCtxt<'_>, id: LocalDefId, span: Span, section: &str) {
+    let target = &tcx.sess.target.options;
+    if target.is_like_osx {
         return;
     }
 
+    // On ELF the TLS sections are what make a symbol thread-local, so a
+    // thread-local static anywhere else (or a plain static in them) would
+    // silently change meaning. COFF uses `.tls$` and has no such coupling.
+    if !target.is_like_windows {
+        let thread_local =
+            tcx.codegen_fn_attrs(id).flags.contains(CodegenFnAttrFlags::THREAD_LOCAL);
+        if thread_local && !is_in_section(tcx, section, &[".tdata", ".tbss"]) {
+            let msg = format!(
+                "thread-local statics must be placed in a `.tdata` or `.tbss` section, not `{}`",
+                section
+            );
+            lint_link_section_conflict(tcx, id, span, &msg);
+        } else if !thread_local && is_in_section(tcx, section, &[".tdata", ".tbss"]) {
+            let msg =
+                format!("only thread-local statics can be placed in the section `{}`", section);
+            lint_link_section_conflict(tcx, id, span, &msg);
+        }
+    }
+
+    if is_in_section(tcx, section, &[".text", ".rodata", ".rdata"]) {
+        let ty = tcx.type_of(id);
+        if tcx.is_mutable_static(id.to_def_id())
+            || !ty.is_freeze(tcx.at(span), ty::ParamEnv::reveal_all())
+        {
+            let msg =
+                format!("mutable statics cannot be placed in the read-only section `{}`", section);
+            lint_link_section_conflict(tcx, id, span, &msg);
+        }
+    }
+
+    // `.bss` and `.tbss` occupy no space in the object file, so anything but
+    // zeroes would be lost.
+    if is_in_section(tcx, section, &[".bss", ".tbss"]) {
+        if let Ok(alloc) = tcx.eval_static_initializer(id.to_def_id()) {
+            let zeroed = alloc.relocations().is_empty()
+                && alloc
+                    .inspect_with_uninit_and_ptr_outside_interpreter(0..alloc.len())
+                    .iter()
+                    .all(|&b| b == 0);
+            if !zeroed {
+                let msg = format!(
+                    "statics placed in the zero-initialized section `{}` must be all zeroes",
+                    section
+                );
+                lint_link_section_conflict(tcx, id, span, &msg);
+            }
+        }
+    }
+}
+
+fn maybe_check_static_with_link_section(tcx: TyCtxt<'_>, id: LocalDefId, span: Span) {
     // If `#[link_section]` is missing, then nothing to verify
     let attrs = tcx.codegen_fn_attrs(id);
-    if attrs.link_section.is_none() {
+    let section = match attrs.link_section {
+        Some(section) => section.as_str(),
+        None => return,
+    };
+
+    if !tcx.sess.target.options.is_like_wasm {
+        check_static_section_flags(tcx, id, span, &section);
         return;
     }
 
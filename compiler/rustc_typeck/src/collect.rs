@@ -34,7 +34,7 @@ use rustc_hir::weak_lang_items;
 use rustc_hir::{GenericParamKind, HirId, Node};
 use rustc_middle::hir::map::blocks::FnLikeNode;
 use rustc_middle::hir::map::Map;
-use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
+use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs, GpuAddressSpace};
 use rustc_middle::mir::mono::Linkage;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::subst::InternalSubsts;
@@ -2556,6 +2556,8 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, id: DefId) -> CodegenFnAttrs {
                     .emit();
             }
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::CMSE_NONSECURE_ENTRY;
+        } else if tcx.sess.check_name(attr, sym::rustc_must_tail) {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::MUST_TAIL;
         } else if tcx.sess.check_name(attr, sym::thread_local) {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::THREAD_LOCAL;
         } else if tcx.sess.check_name(attr, sym::track_caller) {
@@ -2618,6 +2620,43 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, id: DefId) -> CodegenFnAttrs {
                     codegen_fn_attrs.link_section = Some(val);
                 }
             }
+        } else if tcx.sess.check_name(attr, sym::rustc_operand_bundle) {
+            if let Some(val) = attr.value_str() {
+                if val.as_str().bytes().any(|b| b == 0) {
+                    let msg =
+                        format!("illegal null byte in rustc_operand_bundle value: `{}`", &val);
+                    tcx.sess.span_err(attr.span, &msg);
+                } else {
+                    codegen_fn_attrs.operand_bundle = Some(val);
+                }
+            }
+        } else if tcx.sess.check_name(attr, sym::address_space) {
+            if !tcx.is_static(id) {
+                tcx.sess.span_err(attr.span, "`#[address_space]` may only be used on statics");
+            } else if tcx.sess.target.arch != "nvptx64" {
+                tcx.sess.span_err(attr.span, "`#[address_space]` is only supported on NVPTX targets");
+            }
+            codegen_fn_attrs.address_space = match attr.value_str() {
+                Some(sym::shared) => Some(GpuAddressSpace::Shared),
+                Some(sym::constant) => {
+                    if tcx.static_mutability(id) == Some(hir::Mutability::Mut) {
+                        tcx.sess.span_err(
+                            attr.span,
+                            "statics in the `constant` address space cannot be mutable",
+                        );
+                    }
+                    Some(GpuAddressSpace::Constant)
+                }
+                Some(sym::local) => Some(GpuAddressSpace::Local),
+                Some(val) => {
+                    tcx.sess
+                        .struct_span_err(attr.span, &format!("unknown address space `{}`", val))
+                        .help("expected one of `shared`, `constant` or `local`")
+                        .emit();
+                    None
+                }
+                None => None,
+            };
         } else if tcx.sess.check_name(attr, sym::link_name) {
             codegen_fn_attrs.link_name = attr.value_str();
         } else if tcx.sess.check_name(attr, sym::link_ordinal) {
@@ -2880,7 +2919,7 @@ fn should_inherit_track_caller(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     false
 }
 
-fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<usize> {
+fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<u16> {
     use rustc_ast::{Lit, LitIntType, LitKind};
     let meta_item_list = attr.meta_item_list();
     let meta_item_list: Option<&[ast::NestedMetaItem]> = meta_item_list.as_ref().map(Vec::as_ref);
@@ -2889,13 +2928,13 @@ fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<usize> {
         _ => None,
     };
     if let Some(Lit { kind: LitKind::Int(ordinal, LitIntType::Unsuffixed), .. }) = sole_meta_list {
-        if *ordinal <= usize::MAX as u128 {
-            Some(*ordinal as usize)
+        if *ordinal <= u16::MAX as u128 {
+            Some(*ordinal as u16)
         } else {
             let msg = format!("ordinal value in `link_ordinal` is too large: `{}`", &ordinal);
             tcx.sess
                 .struct_span_err(attr.span, &msg)
-                .note("the value may not exceed `usize::MAX`")
+                .note("the value may not exceed `u16::MAX`")
                 .emit();
             None
         }
@@ -132,6 +132,7 @@
 #![feature(unsize)]
 #![cfg_attr(not(bootstrap), feature(unsized_fn_params))]
 #![cfg_attr(bootstrap, feature(unsized_locals))]
+#![feature(unwind_attributes)]
 #![feature(allocator_internals)]
 #![feature(slice_partition_dedup)]
 #![feature(maybe_uninit_extra, maybe_uninit_slice, maybe_uninit_uninit_array)]
@@ -12,6 +12,8 @@ use crate::convert::{Infallible, TryFrom};
 use crate::fmt;
 use crate::hash::{self, Hash};
 use crate::marker::Unsize;
+#[cfg(not(bootstrap))]
+use crate::slice::cmp::BytewiseEquality;
 use crate::slice::{Iter, IterMut};
 
 mod iter;
@@ -215,11 +217,41 @@ where
 {
     #[inline]
     fn eq(&self, other: &[B; N]) -> bool {
-        self[..] == other[..]
+        SpecArrayEq::spec_eq(self, other)
     }
     #[inline]
     fn ne(&self, other: &[B; N]) -> bool {
-        self[..] != other[..]
+        SpecArrayEq::spec_ne(self, other)
+    }
+}
+
+/// Specializes array equality for element types that compare bytewise, so
+/// that the comparison of a small array is a couple of loads rather than a
+/// `memcmp` call, even without optimizations.
+trait SpecArrayEq<Other, const N: usize>: Sized {
+    fn spec_eq(a: &[Self; N], b: &[Other; N]) -> bool;
+    fn spec_ne(a: &[Self; N], b: &[Other; N]) -> bool;
+}
+
+impl<T: PartialEq<Other>, Other, const N: usize> SpecArrayEq<Other, N> for T {
+    default fn spec_eq(a: &[Self; N], b: &[Other; N]) -> bool {
+        a[..] == b[..]
+    }
+    default fn spec_ne(a: &[Self; N], b: &[Other; N]) -> bool {
+        a[..] != b[..]
+    }
+}
+
+#[cfg(not(bootstrap))]
+impl<T: BytewiseEquality<U>, U, const N: usize> SpecArrayEq<U, N> for T {
+    fn spec_eq(a: &[T; N], b: &[U; N]) -> bool {
+        // SAFETY: `BytewiseEquality` is only implemented for `T == U`, for
+        // types without padding, so both arrays are fully initialized and
+        // have the same layout.
+        unsafe { crate::intrinsics::raw_eq(a, b as *const [U; N] as *const [T; N]) }
+    }
+    fn spec_ne(a: &[T; N], b: &[U; N]) -> bool {
+        !Self::spec_eq(a, b)
     }
 }
 
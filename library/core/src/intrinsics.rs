@@ -759,6 +759,20 @@ extern "rust-intrinsic" {
     /// This intrinsic does not have a stable counterpart.
     pub fn breakpoint();
 
+    /// Increments a counter that is unique to this call site.
+    ///
+    /// Every call site gets its own hidden `{ count: usize, location: &'static str }`
+    /// entry in the `rust_hit_counts` link section, which a runtime can walk to
+    /// report how often each call site was reached. Every instantiation and
+    /// inlined copy of the enclosing function shares the counter, although
+    /// each executable or dynamic library it is linked into has its own copy. The increment uses
+    /// [`Ordering::Relaxed`](crate::sync::atomic::Ordering::Relaxed) on targets
+    /// with atomic read-modify-writes of `usize`. Elsewhere it is a plain
+    /// increment, so concurrent hits may be lost.
+    ///
+    /// This intrinsic does not have a stable counterpart.
+    pub fn hit_counter();
+
     /// The size of a type in bytes.
     ///
     /// More specifically, this is the offset in bytes between successive
@@ -1149,6 +1163,24 @@ extern "rust-intrinsic" {
     ///
     /// This intrinsic does not have a stable counterpart.
     pub fn volatile_set_memory<T>(dst: *mut T, val: u8, count: usize);
+    /// Equivalent to the appropriate `llvm.memcpy.element.unordered.atomic.*`
+    /// intrinsic, with a size of `count` * `size_of::<T>()`. Every `T` is
+    /// copied with an unordered atomic load and store of `size_of::<T>()`
+    /// bytes, so the size of `T` must be a power of two no wider than the
+    /// target's atomics, and no larger than `min_align_of::<T>()`.
+    ///
+    /// The memory regions must not overlap.
+    ///
+    /// This intrinsic does not have a stable counterpart.
+    #[cfg(not(bootstrap))]
+    pub fn unordered_atomic_copy_memory<T>(dst: *mut T, src: *const T, count: usize);
+    /// Equivalent to the appropriate `llvm.memset.element.unordered.atomic.*`
+    /// intrinsic, with a size of `count` * `size_of::<T>()`. `T` has the same
+    /// requirements as for `unordered_atomic_copy_memory`.
+    ///
+    /// This intrinsic does not have a stable counterpart.
+    #[cfg(not(bootstrap))]
+    pub fn unordered_atomic_set_memory<T>(dst: *mut T, val: u8, count: usize);
 
     /// Performs a volatile load from the `src` pointer.
     ///
@@ -1732,6 +1764,31 @@ extern "rust-intrinsic" {
     /// See documentation of `<*const T>::guaranteed_ne` for details.
     #[rustc_const_unstable(feature = "const_raw_ptr_comparison", issue = "53020")]
     pub fn ptr_guaranteed_ne<T>(ptr: *const T, other: *const T) -> bool;
+
+    /// Determines whether the raw bytes of the two values are equal.
+    ///
+    /// Values that fit in a couple of registers are compared inline with
+    /// integer loads; larger ones with a call to `bcmp` or `memcmp`.
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be valid for reads of `size_of::<T>()` bytes and
+    /// all of those bytes must be initialized, which rules out any `T` with
+    /// padding.
+    #[cfg(not(bootstrap))]
+    pub fn raw_eq<T>(a: *const T, b: *const T) -> bool;
+
+    /// Determines whether the `len` bytes at `a` and `b` are equal.
+    ///
+    /// Unlike comparing the result of `memcmp` with zero, this lets the
+    /// backend call `bcmp` on the targets whose C library provides it.
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be valid for reads of `len` bytes and all of those
+    /// bytes must be initialized.
+    #[cfg(not(bootstrap))]
+    pub fn raw_eq_bytes(a: *const u8, b: *const u8, len: usize) -> bool;
 }
 
 // Some functions are defined here because they accidentally got made
@@ -114,6 +114,17 @@ where
         }
         // SAFETY: `self` and `other` are references and are thus guaranteed to be valid.
         // The two slices have been checked to have the same size above.
+        #[cfg(not(bootstrap))]
+        unsafe {
+            let size = mem::size_of_val(self);
+            crate::intrinsics::raw_eq_bytes(
+                self.as_ptr() as *const u8,
+                other.as_ptr() as *const u8,
+                size,
+            )
+        }
+        // SAFETY: as above.
+        #[cfg(bootstrap)]
         unsafe {
             let size = mem::size_of_val(self);
             memcmp(self.as_ptr() as *const u8, other.as_ptr() as *const u8, size) == 0
@@ -233,7 +244,7 @@ impl SliceOrd for u8 {
 
 // Hack to allow specializing on `Eq` even though `Eq` has a method.
 #[rustc_unsafe_specialization_marker]
-trait MarkerEq<T>: PartialEq<T> {}
+pub(crate) trait MarkerEq<T>: PartialEq<T> {}
 
 impl<T: Eq> MarkerEq<T> for T {}
 
@@ -241,7 +252,7 @@ impl<T: Eq> MarkerEq<T> for T {}
 /// Trait implemented for types that can be compared for equality using
 /// their bytewise representation
 #[rustc_specialization_trait]
-trait BytewiseEquality<T>: MarkerEq<T> + Copy {}
+pub(crate) trait BytewiseEquality<T>: MarkerEq<T> + Copy {}
 
 macro_rules! impl_marker_for {
     ($traitname:ident, $($ty:ty)*) => {
@@ -28,7 +28,7 @@ use crate::result::Result::{Err, Ok};
 pub mod memchr;
 
 mod ascii;
-mod cmp;
+pub(crate) mod cmp;
 pub(crate) mod index;
 mod iter;
 mod raw;
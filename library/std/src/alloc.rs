@@ -291,7 +291,8 @@ static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
 /// Registers a custom allocation error hook, replacing any that was previously registered.
 ///
 /// The allocation error hook is invoked when an infallible memory allocation fails, before
-/// the runtime aborts. The default hook prints a message to standard error,
+/// the runtime aborts. The default hook prints a message to standard error (or, when the
+/// final artifact was built with `-Z oom=panic`, raises a regular panic instead),
 /// but this behavior can be customized with the [`set_alloc_error_hook`] and
 /// [`take_alloc_error_hook`] functions.
 ///
@@ -316,6 +317,17 @@ pub fn take_alloc_error_hook() -> fn(Layout) {
 }
 
 fn default_alloc_error_hook(layout: Layout) {
+    #[cfg(not(bootstrap))]
+    extern "Rust" {
+        // Defined by rustc in the allocator shim; non-zero under `-Z oom=panic`.
+        static __rust_alloc_error_handler_should_panic: u8;
+    }
+
+    #[cfg(not(bootstrap))]
+    if unsafe { __rust_alloc_error_handler_should_panic != 0 } {
+        panic!("memory allocation of {} bytes failed", layout.size());
+    }
+
     dumb_print(format_args!("memory allocation of {} bytes failed", layout.size()));
 }
 
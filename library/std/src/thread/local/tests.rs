@@ -66,6 +66,42 @@ fn smoke_dtor() {
     rx.recv().unwrap();
 }
 
+#[test]
+fn smoke_no_dtor_const() {
+    thread_local!(static FOO: Cell<i32> = const { Cell::new(1) });
+
+    FOO.with(|f| {
+        assert_eq!(f.get(), 1);
+        f.set(2);
+    });
+    let (tx, rx) = channel();
+    let _t = thread::spawn(move || {
+        FOO.with(|f| {
+            assert_eq!(f.get(), 1);
+        });
+        tx.send(()).unwrap();
+    });
+    rx.recv().unwrap();
+
+    FOO.with(|f| {
+        assert_eq!(f.get(), 2);
+    });
+}
+
+#[test]
+fn smoke_dtor_const() {
+    thread_local!(static FOO: UnsafeCell<Option<Foo>> = const { UnsafeCell::new(None) });
+
+    let (tx, rx) = channel();
+    let _t = thread::spawn(move || unsafe {
+        let mut tx = Some(tx);
+        FOO.with(|f| {
+            *f.get() = Some(Foo(tx.take().unwrap()));
+        });
+    });
+    rx.recv().unwrap();
+}
+
 #[test]
 fn circular() {
     struct S1;
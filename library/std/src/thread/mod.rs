@@ -181,6 +181,10 @@ mod available_concurrency;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::local::{AccessError, LocalKey};
 
+#[unstable(feature = "thread_local_const_init", issue = "none")]
+#[doc(hidden)]
+pub use self::local::require_unstable_const_init_thread_local;
+
 #[unstable(feature = "available_concurrency", issue = "74479")]
 pub use available_concurrency::available_concurrency;
 
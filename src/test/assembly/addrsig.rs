@@ -0,0 +1,17 @@
+// revisions: ADDRSIG NO
+// assembly-output: emit-asm
+// compile-flags: --target x86_64-unknown-linux-gnu
+// needs-llvm-components: x86
+//[NO] compile-flags: -Z no-addrsig
+
+#![feature(no_core, lang_items)]
+#![crate_type = "rlib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+// ADDRSIG: .addrsig
+// NO-NOT: .addrsig
+#[no_mangle]
+pub fn foo() {}
@@ -1089,3 +1089,12 @@ check_reg!(k1_i64 i64 "k1" "kmovq");
 // CHECK: kmovq k1, k1
 // CHECK: #NO_APP
 check_reg!(k1_ptr ptr "k1" "kmovq");
+
+// CHECK-LABEL: k0_clobber:
+// CHECK: #APP
+// CHECK: kxorw k0, k0, k0
+// CHECK: #NO_APP
+#[no_mangle]
+pub unsafe fn k0_clobber() {
+    asm!("kxorw k0, k0, k0", out("k0") _);
+}
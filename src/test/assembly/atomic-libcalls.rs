@@ -0,0 +1,62 @@
+// Checks that atomic operations wider than the target supports are expanded
+// to the `__atomic_*` libcalls.
+//
+// assembly-output: emit-asm
+// compile-flags: -O --target=armv5te-unknown-linux-gnueabi
+// needs-llvm-components: arm
+
+#![feature(no_core, lang_items, intrinsics)]
+#![crate_type = "rlib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+impl Copy for u32 {}
+impl Copy for u64 {}
+
+extern "rust-intrinsic" {
+    fn atomic_load<T>(src: *const T) -> T;
+    fn atomic_store_rel<T>(dst: *mut T, val: T);
+    fn atomic_xadd_relaxed<T>(dst: *mut T, src: T) -> T;
+    fn atomic_umax<T>(dst: *mut T, src: T) -> T;
+}
+
+// CHECK-LABEL: load_u32:
+#[no_mangle]
+pub unsafe fn load_u32(src: *const u32) -> u32 {
+    // CHECK-NOT: __atomic_load
+    // CHECK: ldr
+    atomic_load(src)
+}
+
+// CHECK-LABEL: load_u64:
+#[no_mangle]
+pub unsafe fn load_u64(src: *const u64) -> u64 {
+    // CHECK: bl __atomic_load_8
+    atomic_load(src)
+}
+
+// CHECK-LABEL: store_u64:
+#[no_mangle]
+pub unsafe fn store_u64(dst: *mut u64, val: u64) {
+    // CHECK: bl __atomic_store_8
+    atomic_store_rel(dst, val)
+}
+
+// CHECK-LABEL: add_u64:
+#[no_mangle]
+pub unsafe fn add_u64(dst: *mut u64, src: u64) -> u64 {
+    // CHECK: bl __atomic_fetch_add_8
+    atomic_xadd_relaxed(dst, src)
+}
+
+// libatomic has no `max`, so LLVM expands it to a compare-exchange loop.
+// CHECK-LABEL: umax_u64:
+#[no_mangle]
+pub unsafe fn umax_u64(dst: *mut u64, src: u64) -> u64 {
+    // CHECK: bl __atomic_compare_exchange_8
+    atomic_umax(dst, src)
+}
@@ -0,0 +1,21 @@
+// revisions: DEFAULT NO
+// assembly-output: emit-asm
+// compile-flags: --target x86_64-unknown-linux-gnu -Z function-sections=yes
+// needs-llvm-components: x86
+//[NO] compile-flags: -Z data-sections=no
+
+#![feature(no_core, lang_items)]
+#![crate_type = "rlib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+// CHECK: .section .text.foo
+#[no_mangle]
+pub fn foo() {}
+
+// DEFAULT: .section .data.FOO
+// NO-NOT: .data.FOO
+#[no_mangle]
+pub static mut FOO: u32 = 1;
@@ -0,0 +1,48 @@
+// Checks that `-Z isolate-large-fns` places functions with more MIR statements than the
+// threshold in a codegen unit of their own.
+
+// We specify -C incremental here because we want to test the partitioning for
+// incremental compilation
+// compile-flags:-Zprint-mono-items=eager -Cincremental=tmp/partitioning-tests/isolate-large-fns
+// compile-flags:-Zisolate-large-fns=20
+
+#![allow(dead_code)]
+#![crate_type="lib"]
+
+//~ MONO_ITEM fn small @@ isolate_large_fns[Internal]
+fn small() {}
+
+//~ MONO_ITEM fn large @@ isolate_large_fns-large.isolated[Internal]
+fn large(x: u32) -> u32 {
+    let a = x ^ 1;
+    let b = a ^ 2;
+    let c = b ^ 3;
+    let d = c ^ 4;
+    let e = d ^ 5;
+    let f = e ^ 6;
+    let g = f ^ 7;
+    let h = g ^ 8;
+    let i = h ^ 9;
+    let j = i ^ 10;
+    j
+}
+
+mod module {
+    //~ MONO_ITEM fn module::small @@ isolate_large_fns-module[Internal]
+    fn small() {}
+
+    //~ MONO_ITEM fn module::large @@ isolate_large_fns-module-large.isolated[Internal]
+    fn large(x: u32) -> u32 {
+        let a = x ^ 1;
+        let b = a ^ 2;
+        let c = b ^ 3;
+        let d = c ^ 4;
+        let e = d ^ 5;
+        let f = e ^ 6;
+        let g = f ^ 7;
+        let h = g ^ 8;
+        let i = h ^ 9;
+        let j = i ^ 10;
+        j
+    }
+}
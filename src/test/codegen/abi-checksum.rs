@@ -0,0 +1,11 @@
+// compile-flags: -Z embed-abi-checksum
+// only-x86_64
+// ignore-tidy-linelength
+
+#![crate_type = "lib"]
+
+// CHECK: @__rustc_abi_checksum = linkonce_odr constant [{{[0-9]+}} x i8] c"rustc-abi-v1:x86_64-{{[a-z0-9_-]+}}:{{[0-9a-f]+}}:{{[0-9a-f]+}}\00", align 1
+// CHECK: @__rustc_abi_checksum_guard_abi_checksum_{{[0-9a-f]+}}_{{[0-9a-f]+}} = linkonce_odr constant [{{[0-9]+}} x i8] c"rustc-abi-v1:x86_64-{{[a-z0-9_-]+}}:{{[0-9a-f]+}}:{{[0-9a-f]+}}\00", align 1
+// CHECK: @llvm.used = appending global [2 x i8*] [{{.*}}@__rustc_abi_checksum{{.*}}@__rustc_abi_checksum_guard_abi_checksum_{{.*}}], section "llvm.metadata"
+
+pub fn foo() {}
@@ -0,0 +1,42 @@
+// compile-flags: -C opt-level=1 -C no-prepopulate-passes -Z mutable-noalias=yes -Zmir-opt-level=0
+
+#![crate_type = "lib"]
+
+// Loads and stores through distinct `noalias` reference arguments, or reborrows
+// of them, are put in distinct alias scopes.
+
+// CHECK-LABEL: @swap_values
+#[no_mangle]
+pub fn swap_values(a: &mut u32, b: &mut u32) {
+    // CHECK: load i32, i32* %a, align 4, !alias.scope ![[A:[0-9]+]], !noalias ![[B:[0-9]+]]
+    // CHECK: load i32, i32* %b, align 4, !alias.scope ![[B]], !noalias ![[A]]
+    // CHECK: store i32 %{{.*}}, i32* %a, align 4, !alias.scope ![[A]], !noalias ![[B]]
+    // CHECK: store i32 %{{.*}}, i32* %b, align 4, !alias.scope ![[B]], !noalias ![[A]]
+    let x = *a;
+    *a = *b;
+    *b = x;
+}
+
+// CHECK-LABEL: @copy_through_reborrow
+#[no_mangle]
+pub fn copy_through_reborrow(a: &mut [u32; 2], b: &[u32; 2]) {
+    // CHECK: load i32, i32* %{{.*}}, align 4, !alias.scope ![[Y:[0-9]+]], !noalias ![[X:[0-9]+]]
+    // CHECK: store i32 %{{.*}}, i32* %{{.*}}, align 4, !alias.scope ![[X]], !noalias ![[Y]]
+    let x = &mut *a;
+    x[0] = b[1];
+}
+
+// A single reference gets no scope.
+// CHECK-LABEL: @increment
+#[no_mangle]
+pub fn increment(a: &mut u32) {
+    // CHECK-NOT: !alias.scope
+    // CHECK: ret void
+    *a += 1;
+}
+
+// Scopes and domains are distinct, self-referential nodes, so that inlining
+// `swap_values` twice doesn't make the two copies share scopes.
+// CHECK: ![[A]] = !{![[SCOPE_A:[0-9]+]]}
+// CHECK: ![[SCOPE_A]] = distinct !{![[SCOPE_A]], ![[DOMAIN:[0-9]+]], !"swap_values: scope 0"}
+// CHECK: ![[DOMAIN]] = distinct !{![[DOMAIN]], !"swap_values"}
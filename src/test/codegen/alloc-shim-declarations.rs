@@ -0,0 +1,31 @@
+// compile-flags: -O
+
+#![crate_type = "lib"]
+
+use std::alloc::{self, Layout};
+
+// Callers see the global allocator shims through their declarations, so those
+// carry the same `noalias` and `allocsize` as the shim definitions.
+
+// CHECK-DAG: declare noalias i8* @__rust_alloc({{.*}}){{.*}} [[ALLOC:#[0-9]+]]
+// CHECK-DAG: declare noalias i8* @__rust_alloc_zeroed({{.*}}){{.*}} [[ALLOC_ZEROED:#[0-9]+]]
+// CHECK-DAG: declare noalias i8* @__rust_realloc({{.*}}){{.*}} [[REALLOC:#[0-9]+]]
+
+// CHECK-DAG: attributes [[ALLOC]] = { {{.*}}allocsize(0){{.*}} }
+// CHECK-DAG: attributes [[ALLOC_ZEROED]] = { {{.*}}allocsize(0){{.*}} }
+// CHECK-DAG: attributes [[REALLOC]] = { {{.*}}allocsize(3){{.*}} }
+
+#[no_mangle]
+pub unsafe fn do_alloc(layout: Layout) -> *mut u8 {
+    alloc::alloc(layout)
+}
+
+#[no_mangle]
+pub unsafe fn do_alloc_zeroed(layout: Layout) -> *mut u8 {
+    alloc::alloc_zeroed(layout)
+}
+
+#[no_mangle]
+pub unsafe fn do_realloc(ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    alloc::realloc(ptr, layout, new_size)
+}
@@ -0,0 +1,5 @@
+// no-prefer-dynamic
+#![crate_type = "rlib"]
+
+#[no_mangle]
+pub static RLIB_GLOBAL: i32 = 1;
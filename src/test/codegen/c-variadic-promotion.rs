@@ -0,0 +1,66 @@
+// compile-flags: -C no-prepopulate-passes
+// Arguments passed through the `...` of a C-variadic function undergo C's
+// default argument promotions, even when wrapped in a newtype or enum.
+
+#![crate_type = "lib"]
+
+#[repr(transparent)]
+pub struct Float(f32);
+
+#[repr(transparent)]
+pub struct Short(i16);
+
+#[repr(C)]
+pub struct CFloat(f32);
+
+#[repr(u8)]
+pub enum Flag {
+    A = 1,
+    B = 200,
+}
+
+extern "C" {
+    fn variadic(n: i32, ...);
+}
+
+// CHECK-LABEL: @promote_float
+#[no_mangle]
+pub unsafe fn promote_float(x: Float) {
+    // CHECK: [[EXT:%.*]] = fpext float %{{.*}} to double
+    // CHECK: call void (i32, ...) @variadic(i32{{.*}} 1, double{{.*}} [[EXT]])
+    variadic(1, x);
+}
+
+// CHECK-LABEL: @promote_short
+#[no_mangle]
+pub unsafe fn promote_short(x: Short) {
+    // CHECK: [[EXT:%.*]] = sext i16 %{{.*}} to i32
+    // CHECK: call void (i32, ...) @variadic(i32{{.*}} 1, i32{{.*}} [[EXT]])
+    variadic(1, x);
+}
+
+// C never promotes aggregates, even ones with a single scalar field.
+// CHECK-LABEL: @no_promotion_repr_c
+#[no_mangle]
+pub unsafe fn no_promotion_repr_c(x: CFloat) {
+    // CHECK-NOT: fpext
+    // CHECK: call void (i32, ...) @variadic(i32{{.*}} 1, {{.*}}float
+    variadic(1, x);
+}
+
+// CHECK-LABEL: @promote_enum
+#[no_mangle]
+pub unsafe fn promote_enum(x: Flag) {
+    // CHECK: [[EXT:%.*]] = zext i8 %{{.*}} to i32
+    // CHECK: call void (i32, ...) @variadic(i32{{.*}} 1, i32{{.*}} [[EXT]])
+    variadic(1, x);
+}
+
+// CHECK-LABEL: @no_promotion
+#[no_mangle]
+pub unsafe fn no_promotion(x: f64, y: u32) {
+    // CHECK-NOT: fpext
+    // CHECK-NOT: zext
+    // CHECK: call void (i32, ...) @variadic(i32{{.*}} 2, double{{.*}} %x, i32{{.*}} %y)
+    variadic(2, x, y);
+}
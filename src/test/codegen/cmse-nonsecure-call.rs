@@ -0,0 +1,21 @@
+// compile-flags: --target thumbv8m.main-none-eabi --crate-type=rlib
+// needs-llvm-components: arm
+
+#![feature(no_core, lang_items, abi_c_cmse_nonsecure_call)]
+#![no_core]
+
+#[lang = "sized"]
+pub trait Sized {}
+#[lang = "copy"]
+pub trait Copy {}
+
+impl Copy for u32 {}
+
+// CHECK-LABEL: @call_nonsecure
+#[no_mangle]
+pub fn call_nonsecure(f: extern "C-cmse-nonsecure-call" fn(u32) -> u32) -> u32 {
+    // CHECK: call i32 %f(i32 42) #[[ATTRS:[0-9]+]]
+    f(42)
+}
+
+// CHECK: attributes #[[ATTRS]] = { {{.*}}"cmse_nonsecure_call"{{.*}} }
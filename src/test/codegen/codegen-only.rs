@@ -0,0 +1,38 @@
+// Only the function named by `-Z codegen-only` and what it needs are codegened.
+// The root keeps a definition, hidden, even though nothing in the output calls it.
+
+// compile-flags: -O -C codegen-units=1 -Z codegen-only=hot::run
+
+#![crate_type = "lib"]
+
+// CHECK-NOT: define {{.*}}4cold
+// CHECK-NOT: define {{.*}}9cold_only
+pub fn cold(x: u32) -> u32 {
+    cold_only(x) + shared(x)
+}
+
+#[inline(never)]
+pub fn cold_only(x: u32) -> u32 {
+    x / 7
+}
+
+// CHECK: define {{.*}}@_ZN{{[0-9]+}}codegen_only6shared
+#[inline(never)]
+pub fn shared(x: u32) -> u32 {
+    x ^ 0x55
+}
+
+pub mod hot {
+    // CHECK: define internal {{.*}}@_ZN{{[0-9]+}}codegen_only3hot6helper
+    #[inline(never)]
+    fn helper(x: u32) -> u32 {
+        super::shared(x).wrapping_mul(3)
+    }
+
+    // CHECK: define hidden {{.*}}@_ZN{{[0-9]+}}codegen_only3hot3run
+    pub fn run(x: u32) -> u32 {
+        helper(x) + 1
+    }
+}
+
+// CHECK-NOT: define
@@ -0,0 +1,15 @@
+// compile-flags: --target x86_64-apple-darwin -C panic=abort -C no-prepopulate-passes
+// needs-llvm-components: x86
+
+// Apple targets emit unwind tables by default, even with panic=abort.
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+// CHECK: attributes #{{.*}} uwtable
+#[no_mangle]
+pub fn foo() {}
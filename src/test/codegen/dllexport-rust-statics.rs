@@ -0,0 +1,14 @@
+// only-windows
+// revisions: dylib rlib
+//[dylib] compile-flags: --crate-type=dylib
+//[rlib] compile-flags: --crate-type=rlib
+
+// Statics exported from a Rust dylib are `dllimport`ed by downstream crates,
+// so the dylib has to mark them `dllexport`. Rlibs end up in the same image as
+// their users and must not.
+
+// dylib: @EXPORTED = dllexport {{(local_unnamed_addr )?}}constant i32
+// rlib: @EXPORTED = {{(local_unnamed_addr )?}}constant i32
+// rlib-NOT: dllexport
+#[no_mangle]
+pub static EXPORTED: i32 = 1;
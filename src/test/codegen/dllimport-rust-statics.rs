@@ -0,0 +1,14 @@
+// only-windows
+// aux-build:static_rlib.rs
+
+// Statics of upstream Rust crates that are linked statically into every output
+// end up in the same image, so they must not be marked `dllimport`.
+
+extern crate static_rlib;
+
+// CHECK: @RLIB_GLOBAL = external {{(local_unnamed_addr )?}}global i32
+// CHECK-NOT: dllimport
+
+fn main() {
+    println!("{}", static_rlib::RLIB_GLOBAL);
+}
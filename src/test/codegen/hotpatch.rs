@@ -0,0 +1,10 @@
+// compile-flags: -Z hotpatch
+// only-x86_64
+
+#![crate_type = "lib"]
+
+// CHECK: define {{.*}}@patchable() {{.*}}#[[ATTRS:[0-9]+]]
+#[no_mangle]
+pub fn patchable() {}
+
+// CHECK: attributes #[[ATTRS]] = { {{.*}}"patchable-function"="prologue-short-redirect"{{.*}} }
@@ -0,0 +1,38 @@
+// ignore-tidy-linelength
+
+// Checks that 128-bit overflowing multiplication calls into compiler-builtins on 32-bit
+// targets, where LLVM would otherwise expand `llvm.*mul.with.overflow.i128` inline.
+
+// compile-flags: --target i686-unknown-linux-gnu -C no-prepopulate-passes
+// needs-llvm-components: x86
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, intrinsics)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+extern "rust-intrinsic" {
+    fn mul_with_overflow<T>(x: T, y: T) -> (T, bool);
+}
+
+// CHECK-LABEL: @signed
+#[no_mangle]
+pub unsafe fn signed(x: i128, y: i128) -> (i128, bool) {
+    // CHECK: [[PRODUCT:%.*]] = call i128 @__muloti4(i128 %{{.*}}, i128 %{{.*}}, i32* [[OVERFLOW:%.*]])
+    // CHECK: load i32, i32* [[OVERFLOW]]
+    // CHECK-NOT: llvm.smul.with.overflow
+    mul_with_overflow(x, y)
+}
+
+// CHECK-LABEL: @unsigned
+#[no_mangle]
+pub unsafe fn unsigned(x: u128, y: u128) -> (u128, bool) {
+    // CHECK: [[PRODUCT:%.*]] = call i128 @__rust_u128_mulo(i128 %{{.*}}, i128 %{{.*}}, i32* [[OVERFLOW:%.*]])
+    // CHECK: load i32, i32* [[OVERFLOW]]
+    // CHECK-NOT: llvm.umul.with.overflow
+    mul_with_overflow(x, y)
+}
@@ -0,0 +1,26 @@
+// Checks that, like clang, 128-bit integers are passed indirectly but returned in XMM0 on
+// x86_64 Windows.
+
+// compile-flags: --target x86_64-pc-windows-msvc -C no-prepopulate-passes
+// needs-llvm-components: x86
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+// CHECK: define <16 x i8> @identity(i128* {{.*}}%x)
+#[no_mangle]
+pub extern "C" fn identity(x: i128) -> i128 {
+    x
+}
+
+// CHECK: define <16 x i8> @unsigned(i64 %x)
+#[no_mangle]
+pub extern "C" fn unsigned(x: u64) -> u128 {
+    x as u128
+}
@@ -0,0 +1,34 @@
+// Checks that `-Z inline-debug-threshold` inlines small `#[inline]` functions at
+// `-C opt-level=0`, but not ones over the budget.
+
+// compile-flags: -C opt-level=0 -Z inline-debug-threshold=10
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn small(x: u32) -> u32 {
+    x
+}
+
+#[inline]
+pub fn large(x: u32) -> u32 {
+    let a = x ^ 1;
+    let b = a ^ 2;
+    let c = b ^ 3;
+    let d = c ^ 4;
+    let e = d ^ 5;
+    let f = e ^ 6;
+    let g = f ^ 7;
+    let h = g ^ 8;
+    let i = h ^ 9;
+    let j = i ^ 10;
+    j
+}
+
+// CHECK-LABEL: @caller
+#[no_mangle]
+pub fn caller(x: u32) -> u32 {
+    // CHECK-NOT: call {{.*}}5small
+    // CHECK: call {{.*}}5large
+    large(small(x))
+}
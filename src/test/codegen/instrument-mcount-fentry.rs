@@ -0,0 +1,8 @@
+// compile-flags: -Z instrument-mcount-fentry
+// only-x86_64
+
+#![crate_type = "lib"]
+
+// CHECK: attributes #{{.*}} "fentry-call"="true"
+// CHECK-NOT: "instrument-function-entry-inlined"
+pub fn foo() {}
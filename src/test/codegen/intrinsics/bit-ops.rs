@@ -0,0 +1,53 @@
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics;
+
+// CHECK-LABEL: @ctlz_u32
+#[no_mangle]
+pub unsafe fn ctlz_u32(a: u32) -> u32 {
+    // CHECK: call i32 @llvm.ctlz.i32(i32 %a, i1 false)
+    intrinsics::ctlz(a)
+}
+
+// CHECK-LABEL: @ctlz_nonzero_u32
+#[no_mangle]
+pub unsafe fn ctlz_nonzero_u32(a: u32) -> u32 {
+    // CHECK: call i32 @llvm.ctlz.i32(i32 %a, i1 true)
+    intrinsics::ctlz_nonzero(a)
+}
+
+// CHECK-LABEL: @cttz_u64
+#[no_mangle]
+pub unsafe fn cttz_u64(a: u64) -> u64 {
+    // CHECK: call i64 @llvm.cttz.i64(i64 %a, i1 false)
+    intrinsics::cttz(a)
+}
+
+// CHECK-LABEL: @cttz_nonzero_u64
+#[no_mangle]
+pub unsafe fn cttz_nonzero_u64(a: u64) -> u64 {
+    // CHECK: call i64 @llvm.cttz.i64(i64 %a, i1 true)
+    intrinsics::cttz_nonzero(a)
+}
+
+// CHECK-LABEL: @bitreverse_u16
+#[no_mangle]
+pub unsafe fn bitreverse_u16(a: u16) -> u16 {
+    // CHECK: call i16 @llvm.bitreverse.i16(i16 %a)
+    intrinsics::bitreverse(a)
+}
+
+// CHECK-LABEL: @minnumf32
+#[no_mangle]
+pub unsafe fn minnumf32(a: f32, b: f32) -> f32 {
+    // CHECK: llvm.minnum.f32
+    intrinsics::minnumf32(a, b)
+}
+
+// CHECK-LABEL: @maxnumf64
+#[no_mangle]
+pub unsafe fn maxnumf64(a: f64, b: f64) -> f64 {
+    // CHECK: llvm.maxnum.f64
+    intrinsics::maxnumf64(a, b)
+}
@@ -0,0 +1,29 @@
+// ignore-tidy-linelength
+// Targets without atomic read-modify-writes increment hit counters with a
+// plain load, add and store.
+//
+// compile-flags: -C no-prepopulate-passes --target=thumbv6m-none-eabi
+// needs-llvm-components: arm
+
+#![feature(no_core, lang_items, intrinsics)]
+#![crate_type = "lib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+extern "rust-intrinsic" {
+    fn hit_counter();
+}
+
+// CHECK: @[[COUNTER:hit_counter[.0-9]*]] = internal global { i32, {{.*}} } { i32 0, {{.*}} }, section "rust_hit_counts"
+
+// CHECK-LABEL: @count
+#[no_mangle]
+pub fn count() {
+    // CHECK-NOT: atomicrmw
+    // CHECK: [[COUNT:%.*]] = load i32, i32* bitcast ({{.*}}@[[COUNTER]] to i32*), align 4
+    // CHECK: [[NEW:%.*]] = add i32 [[COUNT]], 1
+    // CHECK: store i32 [[NEW]], i32* bitcast ({{.*}}@[[COUNTER]] to i32*), align 4
+    hit_counter();
+}
@@ -0,0 +1,40 @@
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes -C codegen-units=1
+// only-linux
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::hit_counter;
+
+// Each call site gets its own counter in the `rust_hit_counts` section, which is
+// shared by every instantiation of the function containing it.
+
+// CHECK: @[[FIRST:__rust_hit_counter_[0-9a-zA-Z]+]] = linkonce_odr hidden global { [[USIZE:i[0-9]+]], {{.*}} } { [[USIZE]] 0, {{.*}} }, section "rust_hit_counts", comdat
+// CHECK: @[[SECOND:__rust_hit_counter_[0-9a-zA-Z]+]] = linkonce_odr hidden global { [[USIZE]], {{.*}} } { [[USIZE]] 0, {{.*}} }, section "rust_hit_counts", comdat
+// CHECK: @[[GENERIC:__rust_hit_counter_[0-9a-zA-Z]+]] = linkonce_odr hidden global { [[USIZE]], {{.*}} } { [[USIZE]] 0, {{.*}} }, section "rust_hit_counts", comdat
+
+// CHECK-LABEL: @count_twice
+#[no_mangle]
+pub fn count_twice() {
+    // CHECK: atomicrmw add [[USIZE]]* bitcast ({{.*}}@[[FIRST]] to [[USIZE]]*), [[USIZE]] 1 monotonic
+    hit_counter();
+    // CHECK: atomicrmw add [[USIZE]]* bitcast ({{.*}}@[[SECOND]] to [[USIZE]]*), [[USIZE]] 1 monotonic
+    hit_counter();
+}
+
+#[inline(never)]
+fn count_generic<T>() {
+    hit_counter();
+}
+
+#[no_mangle]
+pub fn count_generic_twice() {
+    count_generic::<u8>();
+    count_generic::<u16>();
+}
+
+// CHECK: define internal void @{{.*}}count_generic{{.*}}()
+// CHECK: atomicrmw add [[USIZE]]* bitcast ({{.*}}@[[GENERIC]] to [[USIZE]]*), [[USIZE]] 1 monotonic
+// CHECK: define internal void @{{.*}}count_generic{{.*}}()
+// CHECK: atomicrmw add [[USIZE]]* bitcast ({{.*}}@[[GENERIC]] to [[USIZE]]*), [[USIZE]] 1 monotonic
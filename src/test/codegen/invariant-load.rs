@@ -0,0 +1,33 @@
+// compile-flags: -C no-prepopulate-passes -Zmir-opt-level=0
+// only-64bit
+
+#![crate_type = "lib"]
+
+pub trait Trait {
+    fn f(&self);
+}
+
+// CHECK-LABEL: @size_of_dyn
+#[no_mangle]
+pub fn size_of_dyn(x: &dyn Trait) -> usize {
+    // CHECK: load i64, i64* %{{.*}}, align 8, !range ![[SIZE_RANGE:[0-9]+]], !invariant.load
+    std::mem::size_of_val(x)
+}
+
+// CHECK-LABEL: @align_of_dyn
+#[no_mangle]
+pub fn align_of_dyn(x: &dyn Trait) -> usize {
+    // CHECK: load i64, i64* %{{.*}}, align 8, !range ![[ALIGN_RANGE:[0-9]+]], !invariant.load
+    std::mem::align_of_val(x)
+}
+
+// CHECK-LABEL: @promoted_pair
+#[no_mangle]
+pub fn promoted_pair() -> (u32, u64) {
+    // CHECK: load i32, i32* %{{.*}}, !invariant.load
+    // CHECK: load i64, i64* %{{.*}}, !invariant.load
+    *&(1, 2)
+}
+
+// CHECK: ![[SIZE_RANGE]] = !{i64 0, i64 -9223372036854775808}
+// CHECK: ![[ALIGN_RANGE]] = !{i64 1, i64 536870913}
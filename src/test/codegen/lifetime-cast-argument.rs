@@ -0,0 +1,26 @@
+// compile-flags: -O -C no-prepopulate-passes -Zmir-opt-level=0
+// only-x86_64
+// ignore-windows
+
+// The scratch slot used to pass an argument as a different LLVM type is only
+// live until it has been reloaded.
+
+#![crate_type = "lib"]
+
+#[repr(C)]
+pub struct Pair(u32, u32);
+
+extern "C" {
+    fn take(p: Pair);
+}
+
+// CHECK-LABEL: @pass_pair
+#[no_mangle]
+pub unsafe fn pass_pair(p: Pair) {
+    // CHECK: call void @llvm.lifetime.start{{.*}}(i{{[0-9 ]+}} 8, i8* %{{.*}})
+    // CHECK: [[ARG:%.*]] = load i64, i64* %{{.*}}
+    // CHECK-NEXT: {{%.*}} = bitcast
+    // CHECK-NEXT: call void @llvm.lifetime.end{{.*}}(i{{[0-9 ]+}} 8, i8* %{{.*}})
+    // CHECK: call void @take(i64 [[ARG]])
+    take(p);
+}
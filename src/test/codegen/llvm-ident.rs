@@ -0,0 +1,15 @@
+// Verifies that the `!llvm.ident` named metadata is emitted, on every target and
+// whether or not debuginfo is enabled, so `.comment` records the producer.
+//
+// revisions: NONE OPT DEBUG
+//
+// [OPT] compile-flags: -Copt-level=2
+// [DEBUG] compile-flags: -Cdebuginfo=2
+
+// The named metadata should contain a single metadata node.
+// CHECK: !llvm.ident = !{![[ID:[0-9]+]]}
+
+// In addition, check that the metadata node records the rustc and LLVM versions.
+// CHECK: ![[ID]] = !{!"rustc version 1.{{.*}} (LLVM {{[0-9]+}}.{{[0-9]+}})"}
+
+fn main() {}
@@ -0,0 +1,74 @@
+// compile-flags: -C no-prepopulate-passes -Zmir-opt-level=0
+
+#![crate_type = "lib"]
+
+use std::cmp::Ordering;
+
+// CHECK-LABEL: @load_bool
+#[no_mangle]
+pub fn load_bool(x: &bool) -> bool {
+// CHECK: load i8, i8* %x, align 1, !range ![[BOOL_RANGE:[0-9]+]]
+    *x
+}
+
+// CHECK-LABEL: @load_char
+#[no_mangle]
+pub fn load_char(x: &char) -> char {
+// CHECK: load i32, i32* %{{.*}}, align 4, !range ![[CHAR_RANGE:[0-9]+]]
+    *x
+}
+
+// CHECK-LABEL: @load_ordering
+#[no_mangle]
+pub fn load_ordering(x: &Ordering) -> Ordering {
+// CHECK: load i8, i8* %{{.*}}, align 1, !range ![[ORDERING_RANGE:[0-9]+]]
+    *x
+}
+
+extern "Rust" {
+    fn take_char(x: char);
+    fn take_ordering(x: Ordering);
+}
+
+// Scalars loaded from memory to be passed to a call.
+
+// CHECK-LABEL: @pass_char
+#[no_mangle]
+pub fn pass_char(x: &(char,)) {
+// CHECK: [[C:%.*]] = load i32, i32* %{{.*}}, align 4, !range ![[CHAR_RANGE]]
+// CHECK: call void @take_char(i32 {{.*}}[[C]])
+    unsafe { take_char(x.0) }
+}
+
+// CHECK-LABEL: @pass_ordering
+#[no_mangle]
+pub fn pass_ordering(x: &[Ordering; 2]) {
+// CHECK: [[O:%.*]] = load i8, i8* %{{.*}}, align 1, !range ![[ORDERING_RANGE]]
+// CHECK: call void @take_ordering(i8 {{.*}}[[O]])
+    unsafe { take_ordering(x[1]) }
+}
+
+// Scalars returned out of a return place that lives in memory, because it is
+// written through a field projection.
+
+// CHECK-LABEL: @return_bool
+#[no_mangle]
+pub fn return_bool(x: &bool) -> (bool,) {
+// CHECK: store i8
+// CHECK: load i8, i8* %{{.*}}, align 1, !range ![[BOOL_RANGE]]
+// CHECK: ret i1
+    (*x,)
+}
+
+// CHECK-LABEL: @return_char
+#[no_mangle]
+pub fn return_char(x: &char) -> (char,) {
+// CHECK: store i32
+// CHECK: [[R:%.*]] = load i32, i32* %{{.*}}, align 4, !range ![[CHAR_RANGE]]
+// CHECK: ret i32 [[R]]
+    (*x,)
+}
+
+// CHECK: ![[BOOL_RANGE]] = !{i8 0, i8 2}
+// CHECK: ![[CHAR_RANGE]] = !{i32 0, i32 1114112}
+// CHECK: ![[ORDERING_RANGE]] = !{i8 -1, i8 2}
@@ -1,3 +1,4 @@
+// ignore-tidy-linelength
 // compile-flags: -C no-prepopulate-passes -Zmir-opt-level=0
 
 #![crate_type = "lib"]
@@ -24,6 +25,22 @@ pub fn _box(x: Box<i32>) -> i32 {
     *x
 }
 
+// CHECK-LABEL: @load_ref
+#[no_mangle]
+pub fn load_ref<'a>(x: &&'a u32) -> &'a u32 {
+// CHECK: load {{(i32\*, )?}}i32** %x, align {{[0-9]+}}, !nonnull !{{[0-9]+}}, !align ![[ALIGN_4_META:[0-9]+]]
+    *x
+}
+
+// CHECK-LABEL: @load_slice_ptr
+#[no_mangle]
+pub fn load_slice_ptr<'a>(x: &&'a [u32]) -> &'a [u32] {
+// CHECK: load {{(\[0 x i32\]\*, )?}}[0 x i32]** %{{.*}}, align {{[0-9]+}}, !nonnull !{{[0-9]+}}, !align ![[ALIGN_4_META]]
+// CHECK-NOT: !align
+// CHECK: ret
+    *x
+}
+
 // CHECK-LABEL: small_array_alignment
 // The array is loaded as i32, but its alignment is lower, go with 1 byte to avoid target
 // dependent alignment
@@ -43,3 +60,5 @@ pub fn small_struct_alignment(x: Bytes) -> Bytes {
 // CHECK: ret i32 [[VAR]]
     x
 }
+
+// CHECK: ![[ALIGN_4_META]] = !{i64 4}
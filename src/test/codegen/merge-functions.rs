@@ -0,0 +1,27 @@
+// revisions: legacy newpm disabled
+// min-llvm-version: 11.0
+// only-x86_64
+//[legacy] compile-flags: -O -Z merge-functions=aliases
+//[newpm] compile-flags: -O -Z merge-functions=aliases -Z new-llvm-pass-manager
+//[disabled] compile-flags: -O -Z merge-functions=disabled
+
+#![crate_type = "lib"]
+
+// Both functions have the same LLVM IR, so one becomes an alias of the other
+// with either pass manager, unless merging is disabled.
+
+// legacy: @{{mix_[iu]32}} = {{.*}}alias
+// newpm: @{{mix_[iu]32}} = {{.*}}alias
+// disabled-NOT: alias
+// disabled: define {{.*}}@mix_u32
+// disabled: define {{.*}}@mix_i32
+
+#[no_mangle]
+pub fn mix_u32(a: u32, b: u32) -> u32 {
+    a.wrapping_mul(3) ^ b.rotate_left(7)
+}
+
+#[no_mangle]
+pub fn mix_i32(a: i32, b: i32) -> i32 {
+    a.wrapping_mul(3) ^ b.rotate_left(7)
+}
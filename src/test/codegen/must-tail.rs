@@ -0,0 +1,44 @@
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(rustc_attrs)]
+
+#[no_mangle]
+pub fn step(state: u32, acc: u64) -> u64 {
+    acc + state as u64
+}
+
+#[no_mangle]
+pub fn fill(seed: u64) -> [u64; 4] {
+    [seed; 4]
+}
+
+// CHECK-LABEL: @run
+#[no_mangle]
+#[rustc_must_tail]
+pub fn run(state: u32, acc: u64) -> u64 {
+    // CHECK: [[RET:%.*]] = musttail call i64 @step(i32 {{.*}}, i64 {{.*}})
+    // CHECK-NEXT: ret i64 [[RET]]
+    step(state, acc)
+}
+
+// The `sret` pointer of the caller is handed on to the callee.
+// CHECK-LABEL: @refill
+// CHECK-SAME: [[SRET:%[0-9]+]]
+#[no_mangle]
+#[rustc_must_tail]
+pub fn refill(seed: u64) -> [u64; 4] {
+    // CHECK: musttail call void @fill({{.*}} [[SRET]], i64 {{.*}})
+    // CHECK-NEXT: ret void
+    fill(seed)
+}
+
+// Calls that aren't in tail position are left alone.
+// CHECK-LABEL: @not_tail
+#[no_mangle]
+#[rustc_must_tail]
+pub fn not_tail(state: u32, acc: u64) -> u64 {
+    // CHECK-NOT: musttail
+    // CHECK: call i64 @step
+    step(state, acc) + 1
+}
@@ -0,0 +1,31 @@
+// revisions: default yes no
+// compile-flags: -C no-prepopulate-passes
+//[yes] compile-flags: -Z mutable-noalias=yes
+//[no] compile-flags: -Z mutable-noalias=no
+
+#![crate_type = "lib"]
+
+// default: @mut_ref(i32* align 4 dereferenceable(4) %x)
+// yes: @mut_ref(i32* noalias align 4 dereferenceable(4) %x)
+// no: @mut_ref(i32* align 4 dereferenceable(4) %x)
+#[no_mangle]
+pub fn mut_ref(x: &mut i32) {
+    *x += 1;
+}
+
+// default: @boxed(i32* noalias nonnull align 4 %x)
+// yes: @boxed(i32* noalias nonnull align 4 %x)
+// no: @boxed(i32* nonnull align 4 %x)
+#[no_mangle]
+pub fn boxed(x: Box<i32>) -> i32 {
+    *x
+}
+
+// default: noalias nonnull align 4 i32* @make_box
+// yes: noalias nonnull align 4 i32* @make_box
+// no-NOT: noalias {{.*}} @make_box
+// no: nonnull align 4 i32* @make_box
+#[no_mangle]
+pub fn make_box(x: i32) -> Box<i32> {
+    Box::new(x)
+}
@@ -1,86 +1,71 @@
-// compile-flags: -C no-prepopulate-passes -Zmir-opt-level=0
+// compile-flags: -C no-prepopulate-passes
+// only-x86_64
+// ignore-windows
+// ignore-macos
 
 #![crate_type = "lib"]
+#![feature(asm)]
 #![feature(naked_functions)]
 
-// CHECK: Function Attrs: naked
-// CHECK-NEXT: define void @naked_empty()
-#[no_mangle]
-#[naked]
-pub fn naked_empty() {
-    // CHECK-NEXT: {{.+}}:
-    // CHECK-NEXT: ret void
-}
+// Naked functions whose asm block only has `const` operands are emitted as
+// module-level assembly, byte for byte.
 
-// CHECK: Function Attrs: naked
+// CHECK: module asm ".pushsection \22.text.naked_empty\22,\22ax\22,@progbits"
+// CHECK-NEXT: module asm ".globl \22naked_empty\22"
+// CHECK-NEXT: module asm ".type \22naked_empty\22,@function"
+// CHECK-NEXT: module asm "\22naked_empty\22:"
+// CHECK-NEXT: module asm ".intel_syntax noprefix"
+// CHECK-NEXT: module asm "ret"
+// CHECK-NEXT: module asm ".att_syntax"
+// CHECK-NEXT: module asm ".size \22naked_empty\22, . - \22naked_empty\22"
+// CHECK-NEXT: module asm ".popsection"
 #[no_mangle]
 #[naked]
-// CHECK-NEXT: define void @naked_with_args(i{{[0-9]+( %0)?}})
-pub fn naked_with_args(a: isize) {
-    // CHECK-NEXT: {{.+}}:
-    // CHECK-NEXT: %a = alloca i{{[0-9]+}}
-    &a; // keep variable in an alloca
-    // CHECK: ret void
+pub unsafe extern "C" fn naked_empty() {
+    asm!("ret", options(noreturn));
 }
 
-// CHECK: Function Attrs: naked
-// CHECK-NEXT: define i{{[0-9]+}} @naked_with_return()
+// CHECK: module asm "\22naked_exit\22:"
+// CHECK-NEXT: module asm ".intel_syntax noprefix"
+// CHECK-NEXT: module asm "mov eax, 60"
+// CHECK-NEXT: module asm "syscall"
+// CHECK-NEXT: module asm ".att_syntax"
 #[no_mangle]
 #[naked]
-pub fn naked_with_return() -> isize {
-    // CHECK-NEXT: {{.+}}:
-    // CHECK-NEXT: ret i{{[0-9]+}} 0
-    0
+pub unsafe extern "C" fn naked_exit() {
+    asm!("mov eax, {}", "syscall", const 60, options(noreturn));
 }
 
+// CHECK-NOT: define {{.*}}@naked_empty
+// CHECK-NOT: define {{.*}}@naked_exit
+
+// Anything LLVM needs to know about, such as the symbol referenced by a `sym`
+// operand, is still left to LLVM as a function without prologue.
+
 // CHECK: Function Attrs: naked
-// CHECK-NEXT: define i{{[0-9]+}} @naked_with_args_and_return(i{{[0-9]+( %0)?}})
+// CHECK-NEXT: define void @naked_tail_call()
 #[no_mangle]
 #[naked]
-pub fn naked_with_args_and_return(a: isize) -> isize {
+pub unsafe extern "C" fn naked_tail_call() {
     // CHECK-NEXT: {{.+}}:
-    // CHECK-NEXT: %a = alloca i{{[0-9]+}}
-    &a; // keep variable in an alloca
-    // CHECK: ret i{{[0-9]+}} %{{[0-9]+}}
-    a
+    // CHECK-NEXT: call void asm sideeffect
+    // CHECK-NEXT: unreachable
+    asm!("jmp {}", sym naked_empty, options(noreturn));
 }
 
+#[repr(C)]
+pub struct Pair(u32, u32);
+
+// Arguments are left where the calling convention put them, even those that
+// would otherwise be copied into a stack slot.
+
 // CHECK: Function Attrs: naked
-// CHECK-NEXT: define void @naked_recursive()
+// CHECK-NEXT: define void @naked_with_args(i64 %{{.+}}, i32 %{{.+}})
 #[no_mangle]
 #[naked]
-pub fn naked_recursive() {
+pub unsafe extern "C" fn naked_with_args(_a: Pair, _b: u32) {
     // CHECK-NEXT: {{.+}}:
-    // CHECK-NEXT: call void @naked_empty()
-
-    // FIXME(#39685) Avoid one block per call.
-    // CHECK-NEXT: br label %bb1
-    // CHECK: bb1:
-
-    naked_empty();
-
-    // CHECK-NEXT: %_4 = call i{{[0-9]+}} @naked_with_return()
-
-    // FIXME(#39685) Avoid one block per call.
-    // CHECK-NEXT: br label %bb2
-    // CHECK: bb2:
-
-    // CHECK-NEXT: %_3 = call i{{[0-9]+}} @naked_with_args_and_return(i{{[0-9]+}} %_4)
-
-    // FIXME(#39685) Avoid one block per call.
-    // CHECK-NEXT: br label %bb3
-    // CHECK: bb3:
-
-    // CHECK-NEXT: call void @naked_with_args(i{{[0-9]+}} %_3)
-
-    // FIXME(#39685) Avoid one block per call.
-    // CHECK-NEXT: br label %bb4
-    // CHECK: bb4:
-
-    naked_with_args(
-        naked_with_args_and_return(
-            naked_with_return()
-        )
-    );
-    // CHECK-NEXT: ret void
+    // CHECK-NEXT: call void asm sideeffect
+    // CHECK-NEXT: unreachable
+    asm!("jmp {}", sym naked_empty, options(noreturn));
 }
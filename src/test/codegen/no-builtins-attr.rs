@@ -0,0 +1,21 @@
+// compile-flags: -C opt-level=3
+
+#![crate_type = "lib"]
+#![no_builtins]
+
+// A `#![no_builtins]` crate may be the one providing `memset`, so its functions
+// must not be turned back into calls to it.
+
+// CHECK-LABEL: @zero_bytes
+// CHECK-NOT: call {{.*}}memset
+// CHECK: ret void
+#[no_mangle]
+pub unsafe fn zero_bytes(dst: *mut u8, n: usize) {
+    let mut i = 0;
+    while i < n {
+        *dst.add(i) = 0;
+        i += 1;
+    }
+}
+
+// CHECK: attributes #{{[0-9]+}} = { {{.*}}"no-builtins"="true"{{.*}} }
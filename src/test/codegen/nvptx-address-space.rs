@@ -0,0 +1,42 @@
+// Checks that `#[address_space]` statics are placed in the NVPTX address space and are
+// referred to through a cast to the generic address space.
+
+// compile-flags: --target nvptx64-nvidia-cuda -C no-prepopulate-passes
+// needs-llvm-components: nvptx
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, nvptx_address_space)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+#[lang = "freeze"]
+trait Freeze {}
+impl<T: ?Sized> Freeze for T {}
+#[lang = "sync"]
+trait Sync {}
+impl Sync for [u32; 4] {}
+
+// CHECK: @SHARED = addrspace(3) global {{.*}} undef, align 4
+#[no_mangle]
+#[address_space = "shared"]
+pub static mut SHARED: [u32; 64] = [0; 64];
+
+// CHECK: @TABLE = addrspace(4) constant {{.*}} c"\01\00\00\00\02\00\00\00\03\00\00\00\04\00\00\00"
+#[no_mangle]
+#[address_space = "constant"]
+pub static TABLE: [u32; 4] = [1, 2, 3, 4];
+
+// CHECK: @SCRATCH = addrspace(5) global {{.*}} undef, align 4
+#[no_mangle]
+#[address_space = "local"]
+pub static mut SCRATCH: [u32; 16] = [0; 16];
+
+// CHECK-LABEL: @shared_address
+// CHECK: ret [64 x i32]* addrspacecast ({{.*}}@SHARED{{.*}} to [64 x i32]*)
+#[no_mangle]
+pub unsafe fn shared_address() -> *mut [u32; 64] {
+    &mut SHARED
+}
@@ -0,0 +1,46 @@
+// compile-flags: -C no-prepopulate-passes
+// ignore-wasm32-bare compiled with panic=abort by default
+
+#![crate_type = "lib"]
+#![feature(rustc_attrs)]
+
+extern "C" {
+    fn callee();
+}
+
+#[inline(never)]
+#[no_mangle]
+pub fn may_unwind() {}
+
+pub struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+// CHECK-LABEL: @with_bundle
+#[no_mangle]
+#[rustc_operand_bundle = "deopt"]
+pub unsafe fn with_bundle() {
+    // CHECK: call void @callee() {{.*}}[ "deopt"() ]
+    callee();
+}
+
+// CHECK-LABEL: @invoke_with_bundle
+#[no_mangle]
+#[rustc_operand_bundle = "deopt"]
+pub fn invoke_with_bundle() {
+    let _guard = Guard;
+    // CHECK: invoke void @may_unwind() {{.*}}[ "deopt"() ]
+    may_unwind();
+}
+
+// CHECK-LABEL: @without_bundle
+#[no_mangle]
+pub unsafe fn without_bundle() {
+    // CHECK-NOT: "deopt"
+    // CHECK: call void @callee()
+    callee();
+    // CHECK-NOT: "deopt"
+    // CHECK: ret void
+}
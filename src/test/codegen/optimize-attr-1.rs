@@ -1,6 +1,7 @@
-// revisions: NO-OPT SIZE-OPT SPEED-OPT
+// revisions: NO-OPT SIZE-OPT SIZEMIN-OPT SPEED-OPT
 //[NO-OPT] compile-flags: -Copt-level=0 -Ccodegen-units=1
 //[SIZE-OPT] compile-flags: -Copt-level=s -Ccodegen-units=1
+//[SIZEMIN-OPT] compile-flags: -Copt-level=z -Ccodegen-units=1
 //[SPEED-OPT] compile-flags: -Copt-level=3 -Ccodegen-units=1
 
 #![feature(optimize_attribute)]
@@ -10,7 +11,8 @@
 // CHECK-SAME: [[NOTHING_ATTRS:#[0-9]+]]
 // NO-OPT: ret i32 4
 // SIZE-OPT: ret i32 4
-// SPEEC-OPT: ret i32 4
+// SIZEMIN-OPT: ret i32 4
+// SPEED-OPT: ret i32 4
 #[no_mangle]
 pub fn nothing() -> i32 {
     2 + 2
@@ -20,6 +22,7 @@ pub fn nothing() -> i32 {
 // CHECK-SAME: [[SIZE_ATTRS:#[0-9]+]]
 // NO-OPT: ret i32 6
 // SIZE-OPT: ret i32 6
+// SIZEMIN-OPT: ret i32 6
 // SPEED-OPT: ret i32 6
 #[optimize(size)]
 #[no_mangle]
@@ -31,8 +34,10 @@ pub fn size() -> i32 {
 // NO-OPT-SAME: [[NOTHING_ATTRS]]
 // SPEED-OPT-SAME: [[NOTHING_ATTRS]]
 // SIZE-OPT-SAME: [[SPEED_ATTRS:#[0-9]+]]
+// SIZEMIN-OPT-SAME: [[SPEED_ATTRS:#[0-9]+]]
 // NO-OPT: ret i32 8
 // SIZE-OPT: ret i32 8
+// SIZEMIN-OPT: ret i32 8
 // SPEED-OPT: ret i32 8
 #[optimize(speed)]
 #[no_mangle]
@@ -44,7 +49,12 @@ pub fn speed() -> i32 {
 // SPEED-OPT-DAG: attributes [[SIZE_ATTRS]] = {{.*}}minsize{{.*}}optsize{{.*}}
 // SIZE-OPT-DAG: attributes [[NOTHING_ATTRS]] = {{.*}}optsize{{.*}}
 // SIZE-OPT-DAG: attributes [[SIZE_ATTRS]] = {{.*}}minsize{{.*}}optsize{{.*}}
+// SIZEMIN-OPT-DAG: attributes [[NOTHING_ATTRS]] = {{.*}}minsize{{.*}}optsize{{.*}}
 
 // SIZE-OPT: attributes [[SPEED_ATTRS]]
 // SIZE-OPT-NOT: minsize
 // SIZE-OPT-NOT: optsize
+
+// SIZEMIN-OPT: attributes [[SPEED_ATTRS]]
+// SIZEMIN-OPT-NOT: minsize
+// SIZEMIN-OPT-NOT: optsize
@@ -0,0 +1,31 @@
+// Checks that `-Z outline-panic-stubs` moves the panic call of a failed
+// overflow check into a cold stub, leaving only a call to it in the function.
+//
+// compile-flags: -C no-prepopulate-passes -C overflow-checks=on -Z outline-panic-stubs
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @add
+#[no_mangle]
+pub fn add(a: u32, b: u32) -> u32 {
+    // CHECK-NOT: call {{.*}}core9panicking5panic
+    // CHECK: call void @panic_stub.{{[0-9a-zA-Z]+}}()
+    // CHECK-NOT: call {{.*}}core9panicking5panic
+    // CHECK: ret i32
+    a + b
+}
+
+// Bounds checks pass the index and length at run time and keep calling the
+// panic entry point directly.
+// CHECK-LABEL: @index
+#[no_mangle]
+pub fn index(s: &[u8], i: usize) -> u8 {
+    // CHECK: call {{.*}}panic_bounds_check
+    s[i]
+}
+
+// CHECK: define internal void @panic_stub.{{[0-9a-zA-Z]+}}() unnamed_addr [[STUB_ATTRS:#[0-9]+]]
+// CHECK: call {{.*}}core9panicking5panic
+// CHECK-NEXT: unreachable
+
+// CHECK: attributes [[STUB_ATTRS]] = { {{.*}}cold{{.*}}noinline
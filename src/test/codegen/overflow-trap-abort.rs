@@ -0,0 +1,20 @@
+// compile-flags: -C no-prepopulate-passes -C overflow-checks=on -Z overflow-trap=abort
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @add
+#[no_mangle]
+pub fn add(a: u32, b: u32) -> u32 {
+    // CHECK-NOT: panicking
+    // CHECK: call void @llvm.trap()
+    // CHECK-NEXT: unreachable
+    a + b
+}
+
+// Other assertions still panic.
+// CHECK-LABEL: @div
+#[no_mangle]
+pub fn div(a: u32, b: u32) -> u32 {
+    // CHECK: call {{.*}}core9panicking5panic
+    a / b
+}
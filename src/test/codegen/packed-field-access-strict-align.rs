@@ -0,0 +1,75 @@
+// Checks that fields of packed structs are accessed with byte alignment on
+// targets that trap on misaligned loads and stores.
+
+// revisions: sparc64 thumbv7m
+// needs-llvm-components: arm sparc
+
+//[sparc64] compile-flags: --target=sparc64-unknown-linux-gnu
+//[thumbv7m] compile-flags: --target=thumbv7m-none-eabi
+// compile-flags: -O
+
+#![feature(no_core, lang_items)]
+#![crate_type = "lib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+impl Copy for u32 {}
+
+#[repr(packed)]
+pub struct Packed {
+    a: u8,
+    b: u32,
+}
+
+#[repr(packed)]
+pub struct PackedPair {
+    a: u8,
+    b: (u32, u32),
+}
+
+#[repr(packed(2))]
+pub struct Packed2 {
+    a: u8,
+    b: u32,
+}
+
+// CHECK-LABEL: @read_packed
+#[no_mangle]
+pub fn read_packed(p: &Packed) -> u32 {
+    // CHECK: load i32, i32* %{{.*}}, align 1
+    p.b
+}
+
+// CHECK-LABEL: @write_packed
+#[no_mangle]
+pub fn write_packed(p: &mut Packed, v: u32) {
+    // CHECK: store i32 %v, i32* %{{.*}}, align 1
+    p.b = v;
+}
+
+// CHECK-LABEL: @read_packed_pair
+#[no_mangle]
+pub fn read_packed_pair(p: &PackedPair) -> (u32, u32) {
+    // CHECK: load i32, i32* %{{.*}}, align 1
+    // CHECK: load i32, i32* %{{.*}}, align 1
+    p.b
+}
+
+// CHECK-LABEL: @write_packed_pair
+#[no_mangle]
+pub fn write_packed_pair(p: &mut PackedPair, v: (u32, u32)) {
+    // CHECK: store i32 %{{.*}}, i32* %{{.*}}, align 1
+    // CHECK: store i32 %{{.*}}, i32* %{{.*}}, align 1
+    p.b = v;
+}
+
+// CHECK-LABEL: @read_packed2
+#[no_mangle]
+pub fn read_packed2(p: &Packed2) -> u32 {
+    // CHECK: load i32, i32* %{{.*}}, align 2
+    p.b
+}
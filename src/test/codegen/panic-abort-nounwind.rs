@@ -0,0 +1,24 @@
+// compile-flags: -C panic=abort -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+// With `-C panic=abort` nothing unwinds, so even calls through function
+// pointers are `nounwind` and no personality is attached.
+
+pub struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+// CHECK-LABEL: define void @call_fn_ptr(
+// CHECK-NOT: personality
+#[no_mangle]
+pub fn call_fn_ptr(f: fn()) {
+    let _guard = Guard;
+    // CHECK: call void %f() [[NOUNWIND:#[0-9]+]]
+    // CHECK-NOT: invoke
+    f();
+}
+
+// CHECK: attributes [[NOUNWIND]] = { nounwind }
@@ -0,0 +1,22 @@
+// Checks that calls to the panic entry points are marked `cold` and `noreturn`
+// at the call site.
+//
+// compile-flags: -C no-prepopulate-passes -C overflow-checks=on
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @add
+#[no_mangle]
+pub fn add(a: u32, b: u32) -> u32 {
+    // CHECK: call {{.*}}core9panicking5panic{{.*}} [[PANIC_CALL:#[0-9]+]]
+    a + b
+}
+
+// CHECK-LABEL: @index
+#[no_mangle]
+pub fn index(s: &[u8], i: usize) -> u8 {
+    // CHECK: call {{.*}}panic_bounds_check{{.*}} [[PANIC_CALL]]
+    s[i]
+}
+
+// CHECK: attributes [[PANIC_CALL]] = { cold noreturn }
@@ -0,0 +1,32 @@
+// Checks that byte equality is lowered to `bcmp` where the C library provides it, and to
+// `memcmp` elsewhere.
+
+// revisions: gnu musl msvc
+//[gnu] compile-flags: --target x86_64-unknown-linux-gnu
+//[musl] compile-flags: --target x86_64-unknown-linux-musl
+//[msvc] compile-flags: --target x86_64-pc-windows-msvc
+// compile-flags: -C no-prepopulate-passes
+// needs-llvm-components: x86
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, intrinsics)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+extern "rust-intrinsic" {
+    fn raw_eq_bytes(a: *const u8, b: *const u8, len: usize) -> bool;
+}
+
+// CHECK-LABEL: @bytes_eq
+#[no_mangle]
+pub unsafe fn bytes_eq(a: *const u8, b: *const u8, len: usize) -> bool {
+    // gnu: [[CMP:%.*]] = call i32 @bcmp(i8* %a, i8* %b, i64 %len)
+    // musl: [[CMP:%.*]] = call i32 @bcmp(i8* %a, i8* %b, i64 %len)
+    // msvc: [[CMP:%.*]] = call i32 @memcmp(i8* %a, i8* %b, i64 %len)
+    // CHECK: icmp eq i32 [[CMP]], 0
+    raw_eq_bytes(a, b, len)
+}
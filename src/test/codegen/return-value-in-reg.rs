@@ -1,4 +1,5 @@
-//! This test checks that types of up to 128 bits are returned by-value instead of via out-pointer.
+//! This test checks that types of up to 128 bits are returned by-value instead of via out-pointer,
+//! as a pair of integers when they don't fit in a single register.
 
 // compile-flags: -C no-prepopulate-passes -O
 // only-x86_64
@@ -11,12 +12,24 @@ pub struct S {
     c: u32,
 }
 
-// CHECK: define i128 @modify(%S* noalias nocapture dereferenceable(16) %s)
+// CHECK: define { i64, i64 } @modify(%S* noalias nocapture align 8 dereferenceable(16) %s)
 #[no_mangle]
 pub fn modify(s: S) -> S {
     S { a: s.a + s.a, b: s.b + s.b, c: s.c + s.c }
 }
 
+pub struct Small {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+// CHECK: define { i64, i32 } @m_small(%Small* noalias nocapture align 4 dereferenceable(12) %s)
+#[no_mangle]
+pub fn m_small(s: Small) -> Small {
+    Small { a: s.a + s.a, b: s.b + s.b, c: s.c + s.c }
+}
+
 #[repr(packed)]
 pub struct TooBig {
     a: u64,
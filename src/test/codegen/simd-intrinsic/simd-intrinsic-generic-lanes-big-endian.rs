@@ -0,0 +1,60 @@
+// Lane indices of shuffles, insertions and extractions are element indices and
+// must reach LLVM unchanged on big-endian targets.
+
+// revisions: powerpc64 s390x
+// needs-llvm-components: powerpc systemz
+
+//[powerpc64] compile-flags: --target powerpc64-unknown-linux-gnu
+//[s390x] compile-flags: --target s390x-unknown-linux-gnu
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, repr_simd, platform_intrinsics)]
+#![no_core]
+#![allow(non_camel_case_types)]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+impl Copy for u32 {}
+
+#[repr(simd)]
+pub struct u32x4(u32, u32, u32, u32);
+
+impl Copy for u32x4 {}
+
+extern "platform-intrinsic" {
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+    fn simd_insert<T, E>(x: T, idx: u32, val: E) -> T;
+    fn simd_extract<T, E>(x: T, idx: u32) -> E;
+}
+
+// CHECK-LABEL: @interleave_low
+#[no_mangle]
+pub unsafe fn interleave_low(a: u32x4, b: u32x4) -> u32x4 {
+    // CHECK: shufflevector <4 x i32> %{{.*}}, <4 x i32> %{{.*}}, <4 x i32> <i32 0, i32 4, i32 1, i32 5>
+    simd_shuffle4(a, b, [0, 4, 1, 5])
+}
+
+// CHECK-LABEL: @reverse
+#[no_mangle]
+pub unsafe fn reverse(a: u32x4) -> u32x4 {
+    // CHECK: shufflevector <4 x i32> %{{.*}}, <4 x i32> %{{.*}}, <4 x i32> <i32 3, i32 2, i32 1, i32 0>
+    simd_shuffle4(a, a, [3, 2, 1, 0])
+}
+
+// CHECK-LABEL: @insert_second
+#[no_mangle]
+pub unsafe fn insert_second(a: u32x4, x: u32) -> u32x4 {
+    // CHECK: insertelement <4 x i32> %{{.*}}, i32 %x, i32 1
+    simd_insert(a, 1, x)
+}
+
+// CHECK-LABEL: @extract_last
+#[no_mangle]
+pub unsafe fn extract_last(a: u32x4) -> u32 {
+    // CHECK: extractelement <4 x i32> %{{.*}}, i32 3
+    simd_extract(a, 3)
+}
@@ -0,0 +1,51 @@
+// ignore-tidy-linelength
+
+// Shuffle indices are read element by element from the constant index array,
+// including when they come from a named constant.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i32x2(i32, i32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i32x8(i32, i32, i32, i32, i32, i32, i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_shuffle2<T, U>(x: T, y: T, idx: [u32; 2]) -> U;
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+    fn simd_shuffle8<T, U>(x: T, y: T, idx: [u32; 8]) -> U;
+}
+
+const ODD: [u32; 4] = [1, 3, 5, 7];
+
+// CHECK-LABEL: @last_lanes
+#[no_mangle]
+pub unsafe fn last_lanes(a: i32x4, b: i32x4) -> i32x2 {
+    // CHECK: shufflevector <4 x i32> %{{.*}}, <4 x i32> %{{.*}}, <2 x i32> <i32 3, i32 7>
+    simd_shuffle2(a, b, [3, 7])
+}
+
+// CHECK-LABEL: @odd_lanes
+#[no_mangle]
+pub unsafe fn odd_lanes(a: i32x4, b: i32x4) -> i32x4 {
+    // CHECK: shufflevector <4 x i32> %{{.*}}, <4 x i32> %{{.*}}, <4 x i32> <i32 1, i32 3, i32 5, i32 7>
+    simd_shuffle4(a, b, ODD)
+}
+
+// CHECK-LABEL: @concat
+#[no_mangle]
+pub unsafe fn concat(a: i32x4, b: i32x4) -> i32x8 {
+    // CHECK: shufflevector <4 x i32> %{{.*}}, <4 x i32> %{{.*}}, <8 x i32> <i32 0, i32 1, i32 2, i32 3, i32 4, i32 5, i32 6, i32 7>
+    simd_shuffle8(a, b, [0, 1, 2, 3, 4, 5, 6, 7])
+}
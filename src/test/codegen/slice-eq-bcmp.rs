@@ -0,0 +1,16 @@
+// Checks that bytewise slice equality only asks whether the bytes are equal, rather than
+// comparing the result of `memcmp` with zero.
+
+// compile-flags: -O -C no-prepopulate-passes
+// only-linux
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @slice_eq
+#[no_mangle]
+pub fn slice_eq(a: &[u8], b: &[u8]) -> bool {
+    a == b
+}
+
+// CHECK: call i32 @bcmp(
+// CHECK-NOT: @memcmp
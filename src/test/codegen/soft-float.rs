@@ -0,0 +1,14 @@
+// Checks that `-C soft-float` marks functions as using soft floats.
+
+// compile-flags: -C no-prepopulate-passes -C soft-float
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @add
+// CHECK-SAME: #[[ATTRS:[0-9]+]]
+#[no_mangle]
+pub fn add(a: f32, b: f32) -> f32 {
+    a + b
+}
+
+// CHECK: attributes #[[ATTRS]] = {{.*}} "use-soft-float"="true"
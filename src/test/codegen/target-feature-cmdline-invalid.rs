@@ -0,0 +1,15 @@
+// Entries of `-C target-feature` that LLVM doesn't know about are dropped from the features
+// passed to it.
+
+// only-x86_64
+// compile-flags: -C target-feature=+sse2,-not-a-feature,avx
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @foo
+#[no_mangle]
+#[target_feature(enable = "avx2")]
+pub unsafe fn foo() {}
+
+// CHECK-NOT: not-a-feature
+// CHECK: attributes #{{[0-9]+}} = {{.*}}"target-features"="+sse2,+avx2"
@@ -0,0 +1,35 @@
+// Checks that `-Z tls-model` selects the TLS model of both local and extern
+// `#[thread_local]` statics.
+
+// revisions: GD LD IE LE
+//[GD] compile-flags: -Z tls-model=global-dynamic
+//[LD] compile-flags: -Z tls-model=local-dynamic
+//[IE] compile-flags: -Z tls-model=initial-exec
+//[LE] compile-flags: -Z tls-model=local-exec
+// only-x86_64
+// ignore-windows
+
+#![crate_type = "lib"]
+#![feature(thread_local)]
+
+// GD: @LOCAL = thread_local global i32 0
+// LD: @LOCAL = thread_local(localdynamic) global i32 0
+// IE: @LOCAL = thread_local(initialexec) global i32 0
+// LE: @LOCAL = thread_local(localexec) global i32 0
+#[no_mangle]
+#[thread_local]
+pub static mut LOCAL: i32 = 0;
+
+// GD: @EXTERN = external thread_local global i32
+// LD: @EXTERN = external thread_local(localdynamic) global i32
+// IE: @EXTERN = external thread_local(initialexec) global i32
+// LE: @EXTERN = external thread_local(localexec) global i32
+extern "C" {
+    #[thread_local]
+    static mut EXTERN: i32;
+}
+
+#[no_mangle]
+pub unsafe fn bump() {
+    LOCAL += EXTERN;
+}
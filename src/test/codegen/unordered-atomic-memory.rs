@@ -0,0 +1,24 @@
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes
+// only-64bit
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{unordered_atomic_copy_memory, unordered_atomic_set_memory};
+
+// CHECK-LABEL: @copy_u32
+#[no_mangle]
+pub unsafe fn copy_u32(dst: *mut u32, src: *const u32, count: usize) {
+    // CHECK: [[SIZE:%.*]] = mul i64 4, %count
+    // CHECK: call void @llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i64(i8* align 4 %{{.*}}, i8* align 4 %{{.*}}, i64 [[SIZE]], i32 4)
+    unordered_atomic_copy_memory(dst, src, count);
+}
+
+// CHECK-LABEL: @set_u64
+#[no_mangle]
+pub unsafe fn set_u64(dst: *mut u64, count: usize) {
+    // CHECK: [[SIZE:%.*]] = mul i64 8, %count
+    // CHECK: call void @llvm.memset.element.unordered.atomic.p0i8.i64(i8* align 8 %{{.*}}, i8 0, i64 [[SIZE]], i32 8)
+    unordered_atomic_set_memory(dst, 0, count);
+}
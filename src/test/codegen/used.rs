@@ -0,0 +1,17 @@
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+// `#[used]` statics are kept alive through `@llvm.used`, whether or not they are
+// reachable from outside the crate.
+
+// CHECK: @{{.*}}INTERRUPT_VECTORS{{.*}} = internal constant
+#[used]
+static INTERRUPT_VECTORS: [u32; 2] = [0x1000, 0x2000];
+
+// CHECK: @PLUGIN_REGISTRATION = constant
+#[used]
+#[no_mangle]
+pub static PLUGIN_REGISTRATION: u32 = 42;
+
+// CHECK: @llvm.used = appending global [2 x i8*] {{.*}}, section "llvm.metadata"
@@ -0,0 +1,52 @@
+// compile-flags: -C lto -O -Z virtual-function-elimination
+// no-prefer-dynamic
+// ignore-tidy-linelength
+
+// Checks that fat LTO removes the vtable entries of a local trait that are
+// never called.
+
+trait Shape {
+    fn area(&self) -> u32;
+    fn unused(&self) -> u32;
+}
+
+struct Square(u32);
+struct Rect(u32, u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+    fn unused(&self) -> u32 {
+        self.0 + 1
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> u32 {
+        self.0 * self.1
+    }
+    fn unused(&self) -> u32 {
+        self.0 + self.1
+    }
+}
+
+// The last entry of each `Shape` vtable is `unused`.
+// CHECK: @vtable.{{[0-9]+}} = {{.*}} null }, align {{[0-9]+}}, !type ![[TYPE:[0-9]+]], !vcall_visibility ![[LINKAGE_UNIT:[0-9]+]]
+// CHECK: @vtable.{{[0-9]+}} = {{.*}} null }, align {{[0-9]+}}, !type ![[TYPE]], !vcall_visibility ![[LINKAGE_UNIT]]
+
+// CHECK-NOT: define {{.*}}unused
+
+#[inline(never)]
+fn area(shape: &dyn Shape) -> u32 {
+    shape.area()
+}
+
+fn main() {
+    let square = Square(3);
+    let rect = Rect(2, 5);
+    let shape: &dyn Shape = if std::env::args().count() > 1 { &square } else { &rect };
+    println!("{}", area(shape));
+}
+
+// CHECK: ![[LINKAGE_UNIT]] = !{i64 1}
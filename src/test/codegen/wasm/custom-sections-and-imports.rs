@@ -0,0 +1,44 @@
+// compile-flags: --target=wasm32-unknown-unknown --crate-type=rlib
+// needs-llvm-components: webassembly
+
+// Checks that statics with a `#[link_section]` become wasm custom sections and
+// that functions imported from a wasm module carry their module and name.
+
+#![feature(no_core, lang_items)]
+#![crate_type = "lib"]
+#![no_core]
+
+#[lang = "sized"]
+pub trait Sized {}
+
+#[link_section = "my-custom-section"]
+#[no_mangle]
+pub static CUSTOM: [u8; 3] = [1, 2, 3];
+
+#[link(wasm_import_module = "env")]
+extern "C" {
+    fn plain();
+    #[link_name = "renamed"]
+    fn local_name();
+}
+
+// CHECK-LABEL: @call_imports
+#[no_mangle]
+pub fn call_imports() {
+    unsafe {
+        // CHECK: call void @plain()
+        plain();
+        // CHECK: call void @renamed()
+        local_name();
+    }
+}
+
+// CHECK: declare void @plain() unnamed_addr [[PLAIN:#[0-9]+]]
+// CHECK: declare void @renamed() unnamed_addr [[RENAMED:#[0-9]+]]
+
+// CHECK: !wasm.custom_sections = !{[[SECTION:![0-9]+]]}
+
+// CHECK: attributes [[PLAIN]] = { {{.*}}"wasm-import-module"="env" "wasm-import-name"="plain"{{.*}} }
+// CHECK: attributes [[RENAMED]] = { {{.*}}"wasm-import-module"="env" "wasm-import-name"="renamed"{{.*}} }
+
+// CHECK: [[SECTION]] = !{!"my-custom-section", !"\01\02\03"}
@@ -0,0 +1,24 @@
+// Checks that wasm64 uses 64-bit pointers and `usize`, and enables memory64.
+
+// compile-flags: --target wasm64-unknown-unknown -C no-prepopulate-passes
+// needs-llvm-components: webassembly
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "copy"]
+trait Copy {}
+
+// CHECK: target datalayout = "e-m:e-p:64:64-i64:64-n32:64-S128"
+// CHECK: target triple = "wasm64-unknown-unknown"
+
+// CHECK: define i64 @address(i8* {{.*}}%x)
+#[no_mangle]
+pub fn address(x: *const u8) -> usize {
+    x as usize
+}
+
+// CHECK: attributes #{{[0-9]+}} = { {{.*}}"target-features"="+memory64"{{.*}} }
@@ -0,0 +1,37 @@
+// revisions: rpass1 rpass2
+// compile-flags: -Zquery-dep-graph
+// only-x86_64
+// ignore-windows
+// ignore-macos
+
+#![feature(global_asm, rustc_attrs)]
+#![rustc_partition_codegened(module = "global_asm-changed", cfg = "rpass2")]
+#![rustc_partition_reused(module = "global_asm-unchanged", cfg = "rpass2")]
+
+// This test case makes sure that a change to the assembly of a `global_asm!`
+// item only causes the codegen unit containing it to be codegened again.
+
+mod changed {
+    #[cfg(rpass1)]
+    global_asm!(".globl changed_fn\nchanged_fn:\n    movl $1, %eax\n    ret");
+
+    #[cfg(rpass2)]
+    global_asm!(".globl changed_fn\nchanged_fn:\n    movl $2, %eax\n    ret");
+}
+
+mod unchanged {
+    global_asm!(".globl unchanged_fn\nunchanged_fn:\n    movl $3, %eax\n    ret");
+}
+
+extern "C" {
+    fn changed_fn() -> u32;
+    fn unchanged_fn() -> u32;
+}
+
+fn main() {
+    #[cfg(rpass1)]
+    assert_eq!(unsafe { changed_fn() }, 1);
+    #[cfg(rpass2)]
+    assert_eq!(unsafe { changed_fn() }, 2);
+    assert_eq!(unsafe { unchanged_fn() }, 3);
+}
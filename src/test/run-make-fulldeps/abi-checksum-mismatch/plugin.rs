@@ -0,0 +1,4 @@
+#[no_mangle]
+pub extern "C" fn plugin_entry() -> u32 {
+    42
+}
@@ -0,0 +1,35 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(u8)]
+pub enum Shape {
+    Square,
+    Circle,
+}
+
+#[no_mangle]
+pub extern "C" fn translate(p: Point, by: i32) -> Point {
+    Point { x: p.x + by, y: p.y + by }
+}
+
+impl Point {
+    #[no_mangle]
+    pub extern "C" fn point_scale(self, by: i32) -> Point {
+        Point { x: self.x * by, y: self.y * by }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn area(shape: Shape, size: f64) -> f64 {
+    match shape {
+        Shape::Square => size * size,
+        Shape::Circle => 3.0 * size * size,
+    }
+}
+
+extern "C" {
+    pub fn abort_with(message: &str) -> !;
+}
@@ -0,0 +1,9 @@
+#![feature(no_core, lang_items)]
+#![crate_type = "cdylib"]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[no_mangle]
+pub extern "C" fn foo() {}
@@ -0,0 +1,8 @@
+fn answer(v: &[u32]) -> u32 {
+    v.iter().sum()
+}
+
+fn main() {
+    let v: Vec<u32> = (1..=3).map(|x| x * 7).collect();
+    println!("hello from the JIT: {}", answer(&v));
+}
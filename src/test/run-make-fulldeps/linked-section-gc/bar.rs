@@ -0,0 +1,33 @@
+#![feature(core_intrinsics)]
+
+extern crate foo;
+
+use std::intrinsics::hit_counter;
+use std::slice;
+
+#[repr(C)]
+struct Entry {
+    count: usize,
+    location: *const u8,
+    location_len: usize,
+}
+
+extern "C" {
+    static __start_rust_hit_counts: Entry;
+    static __stop_rust_hit_counts: Entry;
+}
+
+fn main() {
+    foo::used();
+    hit_counter();
+
+    let entries = unsafe {
+        let start = &__start_rust_hit_counts as *const Entry;
+        let stop = &__stop_rust_hit_counts as *const Entry;
+        slice::from_raw_parts(start, stop.offset_from(start) as usize)
+    };
+
+    // One entry for `used`, two for `unused` and one for `main`.
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries.iter().map(|entry| entry.count).sum::<usize>(), 2);
+}
@@ -0,0 +1,13 @@
+#![crate_type = "rlib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::hit_counter;
+
+pub fn used() {
+    hit_counter();
+}
+
+pub fn unused() {
+    hit_counter();
+    hit_counter();
+}
@@ -0,0 +1,3 @@
+fn main() {
+    println!("{}", (1..10u32).map(|x| x * x).sum::<u32>());
+}
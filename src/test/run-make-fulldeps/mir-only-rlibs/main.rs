@@ -0,0 +1,19 @@
+extern crate middle_a;
+extern crate middle_b;
+extern crate upstream;
+
+extern "C" {
+    fn upstream_no_mangle() -> u32;
+}
+
+fn main() {
+    assert_eq!(middle_a::quadruple(3), 12);
+    assert_eq!(middle_b::sextuple(3), 18);
+
+    // Every crate refers to the one copy of the upstream static.
+    assert_eq!(middle_a::shared_addr(), upstream::shared_addr());
+    assert_eq!(middle_b::shared_addr(), upstream::shared_addr());
+    assert_eq!(unsafe { *upstream::shared_addr() }, 7);
+
+    assert_eq!(unsafe { upstream_no_mangle() }, 42);
+}
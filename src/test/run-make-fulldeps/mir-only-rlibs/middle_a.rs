@@ -0,0 +1,11 @@
+#![crate_type = "rlib"]
+
+extern crate upstream;
+
+pub fn quadruple(x: u32) -> u32 {
+    upstream::double(upstream::double(x))
+}
+
+pub fn shared_addr() -> *const u32 {
+    &upstream::SHARED
+}
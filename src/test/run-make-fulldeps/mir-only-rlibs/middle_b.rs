@@ -0,0 +1,11 @@
+#![crate_type = "rlib"]
+
+extern crate upstream;
+
+pub fn sextuple(x: u32) -> u32 {
+    upstream::double(x) * 3
+}
+
+pub fn shared_addr() -> *const u32 {
+    &upstream::SHARED
+}
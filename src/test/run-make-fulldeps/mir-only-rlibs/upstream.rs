@@ -0,0 +1,19 @@
+#![crate_type = "rlib"]
+
+pub static SHARED: u32 = 7;
+
+#[used]
+static UPSTREAM_USED: [u8; 4] = *b"used";
+
+pub fn double(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn shared_addr() -> *const u32 {
+    &SHARED
+}
+
+#[no_mangle]
+pub extern "C" fn upstream_no_mangle() -> u32 {
+    42
+}
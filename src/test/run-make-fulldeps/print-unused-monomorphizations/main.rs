@@ -0,0 +1,14 @@
+#[inline(never)]
+fn always_called() -> u32 {
+    42
+}
+
+// Not exported from an executable, so nothing keeps it alive at link time.
+#[no_mangle]
+pub extern "C" fn never_called() -> u32 {
+    7
+}
+
+fn main() {
+    assert_eq!(always_called(), 42);
+}
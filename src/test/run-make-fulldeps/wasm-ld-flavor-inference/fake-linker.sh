@@ -0,0 +1,3 @@
+#!/bin/sh
+# Records the arguments rustc passes to the linker instead of linking.
+echo "$@" > "$ARGS_FILE"
@@ -0,0 +1,4 @@
+#[no_mangle]
+pub extern "C" fn foo() -> u32 {
+    42
+}
@@ -0,0 +1,20 @@
+use std::env;
+use std::fs;
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let image = fs::read(&args[1]).unwrap();
+    let expected: u16 = args[2].parse().unwrap();
+
+    // `e_lfanew` points at the `PE\0\0` signature, which is followed by the
+    // 20-byte COFF header and then the optional header. The subsystem is at
+    // the same offset (68) in both the PE32 and PE32+ optional headers.
+    let pe = u32::from_le_bytes([image[0x3c], image[0x3d], image[0x3e], image[0x3f]]) as usize;
+    assert_eq!(&image[pe..pe + 4], b"PE\0\0");
+    let subsystem = read_u16(&image, pe + 4 + 20 + 68);
+    assert_eq!(subsystem, expected, "unexpected subsystem in {}", args[1]);
+}
@@ -0,0 +1,6 @@
+extern crate upstream;
+
+fn main() {
+    let mut x = 0;
+    upstream::fetch_add_wide(&mut x);
+}
@@ -0,0 +1,6 @@
+#![feature(core_intrinsics)]
+
+fn main() {
+    let mut x = 0u64;
+    unsafe { std::intrinsics::atomic_xadd(&mut x, 1) };
+}
@@ -0,0 +1,6 @@
+#![crate_type = "rlib"]
+#![feature(core_intrinsics)]
+
+pub fn fetch_add_wide(x: &mut u128) -> u128 {
+    unsafe { std::intrinsics::atomic_xadd(x, 1) }
+}
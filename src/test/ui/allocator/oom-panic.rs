@@ -0,0 +1,22 @@
+// run-pass
+// compile-flags: -Z oom=panic
+// no-prefer-dynamic
+// only-linux
+// ignore-emscripten no processes
+
+#![feature(test)]
+
+use std::hint::black_box;
+use std::mem::forget;
+use std::panic::catch_unwind;
+
+fn main() {
+    let result = catch_unwind(|| {
+        // Sixteen of these exceed the address space on both 32-bit and 64-bit targets.
+        for _ in 0..16 {
+            let size = 0x1000_0000_1000_0000u64 as usize;
+            forget(black_box(vec![0u8; size]));
+        }
+    });
+    assert!(result.is_err());
+}
@@ -36,7 +36,11 @@ fn main() {
         asm!("", in("mm0") foo);
         //~^ ERROR invalid register `mm0`: MMX registers are not currently supported as operands
         asm!("", in("k0") foo);
-        //~^ ERROR invalid register `k0`: the k0 AVX mask register cannot be used as an operand
+        //~^ ERROR register class `kreg0` can only be used as a clobber, not as an input or output
+        asm!("", out(kreg0) _);
+        //~^ ERROR register class `kreg0` can only be used as a clobber, not as an input or output
+        asm!("", out("k0") _);
+        //~^ ERROR register class `kreg0` requires the `avx512f` target feature
 
         // Explicit register conflicts
         // (except in/lateout which don't conflict)
@@ -0,0 +1,21 @@
+// no-system-llvm
+// only-x86_64
+// build-fail
+
+#![feature(global_asm)]
+
+// Checks that global_asm! errors are mapped to the line of the template they come from.
+
+global_asm!("invalid_instruction");
+//~^ ERROR: invalid instruction mnemonic 'invalid_instruction'
+
+global_asm!("
+    nop
+    invalid_instruction
+");
+//~^^ ERROR: invalid instruction mnemonic 'invalid_instruction'
+
+global_asm!("nop\n    invalid_instruction");
+//~^ ERROR: invalid instruction mnemonic 'invalid_instruction'
+
+fn main() {}
@@ -0,0 +1,70 @@
+// only-x86_64
+
+#![feature(asm)]
+#![feature(naked_functions)]
+#![crate_type = "lib"]
+#![allow(unused)]
+
+#[repr(C)]
+pub struct P {
+    x: u8,
+    y: u16,
+}
+
+#[naked]
+pub unsafe fn patterns(
+    mut a: u32,
+    &b: &i32,
+    //~^ ERROR patterns not allowed in naked function parameters
+    (_, _): (u8, u8),
+    //~^ ERROR patterns not allowed in naked function parameters
+    P { x, y }: P,
+    //~^ ERROR patterns not allowed in naked function parameters
+) {
+    asm!("", options(noreturn))
+}
+
+#[naked]
+pub unsafe extern "C" fn inc(a: u32) -> u32 {
+    //~^ ERROR naked functions must contain a single asm block
+    a + 1
+    //~^ ERROR use of parameters not allowed inside naked functions
+}
+
+#[naked]
+pub unsafe extern "C" fn inc_asm(a: u32) -> u32 {
+    asm!("/* {0} */", in(reg) a, options(noreturn));
+    //~^ ERROR use of parameters not allowed inside naked functions
+    //~| ERROR only `const` and `sym` operands are supported in naked functions
+}
+
+#[naked]
+pub unsafe extern "C" fn missing_noreturn() {
+    asm!("ret");
+    //~^ ERROR asm in naked functions must use `noreturn` option
+}
+
+#[naked]
+pub unsafe extern "C" fn implicit_return() -> u64 {
+    //~^ ERROR naked functions must contain a single asm block
+    asm!("", options(noreturn));
+    0
+}
+
+#[naked]
+pub unsafe extern "C" fn multiple_asm() {
+    //~^ ERROR naked functions must contain a single asm block
+    asm!("", options(noreturn));
+    asm!("", options(noreturn));
+}
+
+#[naked]
+pub unsafe extern "C" fn bad_options() {
+    asm!("", options(nomem, preserves_flags, noreturn));
+    //~^ ERROR asm options unsupported in naked functions: `nomem`, `preserves_flags`
+}
+
+#[naked]
+pub unsafe extern "C" fn syscall_stub() {
+    asm!("mov eax, {}", "syscall", "ret", const 60, options(noreturn));
+}
@@ -0,0 +1,24 @@
+// compile-flags: --target nvptx64-nvidia-cuda
+// needs-llvm-components: nvptx
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, nvptx_address_space)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "sync"]
+trait Sync {}
+impl Sync for u32 {}
+
+#[address_space = "shared"] //~ ERROR `#[address_space]` may only be used on statics
+pub fn function() {}
+
+#[address_space = "global"] //~ ERROR unknown address space `global`
+pub static GLOBAL: u32 = 0;
+
+#[address_space = "constant"] //~ ERROR statics in the `constant` address space cannot be mutable
+pub static mut CONSTANT: u32 = 0;
+
+#[address_space = "constant"]
+pub static IMMUTABLE_CONSTANT: u32 = 0;
@@ -0,0 +1,15 @@
+// compile-flags: --target x86_64-unknown-linux-gnu
+// needs-llvm-components: x86
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, nvptx_address_space)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+#[lang = "sync"]
+trait Sync {}
+impl Sync for u32 {}
+
+#[address_space = "shared"] //~ ERROR `#[address_space]` is only supported on NVPTX targets
+pub static SHARED: u32 = 0;
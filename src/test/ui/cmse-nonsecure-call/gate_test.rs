@@ -0,0 +1,8 @@
+// gate-test-abi_c_cmse_nonsecure_call
+
+pub fn call_nonsecure(f: extern "C-cmse-nonsecure-call" fn(u32) -> u32) -> u32 {
+    //~^ ERROR C-cmse-nonsecure-call ABI is experimental and subject to change
+    f(6)
+}
+
+fn main() {}
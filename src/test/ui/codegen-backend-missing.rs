@@ -0,0 +1,3 @@
+// compile-flags: -Z codegen-backend=./no-such-backend.so
+
+fn main() {}
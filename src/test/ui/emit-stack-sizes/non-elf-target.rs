@@ -0,0 +1,8 @@
+// compile-flags: --crate-type lib --target x86_64-apple-darwin -Z emit-stack-sizes
+// needs-llvm-components: x86
+// check-pass
+
+// Stack size sections only exist in ELF object files.
+
+#![feature(no_core)]
+#![no_core]
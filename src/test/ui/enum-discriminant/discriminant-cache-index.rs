@@ -0,0 +1,34 @@
+// run-pass
+// compile-flags: -C opt-level=3 -Z mir-opt-level=3
+// Discriminants read through an index projection must not be reused once the
+// index is reassigned, even within a single basic block.
+
+#[inline(never)]
+fn states(slots: &[Option<u32>; 3], mut i: usize) -> [bool; 3] {
+    let first = matches!(slots[i], Some(_));
+    i += 1;
+    let second = matches!(slots[i], Some(_));
+    i += 1;
+    let third = matches!(slots[i], Some(_));
+    [first, second, third]
+}
+
+#[inline(never)]
+fn count(slots: &[Option<u32>; 3]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    while i < slots.len() {
+        if let Some(_) = slots[i] {
+            n += 1;
+        }
+        i += 1;
+    }
+    n
+}
+
+fn main() {
+    let slots = [Some(1), None, Some(3)];
+    assert_eq!(states(&slots, 0), [true, false, true]);
+    assert_eq!(count(&slots), 2);
+    assert_eq!(count(&[None, None, Some(0)]), 1);
+}
@@ -0,0 +1,12 @@
+// compile-flags: --target nvptx64-nvidia-cuda
+// needs-llvm-components: nvptx
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[address_space = "shared"] //~ ERROR the `#[address_space]` attribute is an experimental feature
+pub static mut SHARED: u32 = 0;
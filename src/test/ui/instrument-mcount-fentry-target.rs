@@ -0,0 +1,11 @@
+// revisions: x86_64 aarch64
+// needs-llvm-components: x86 aarch64
+// compile-flags: -Z instrument-mcount-fentry
+//[x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//[x86_64] check-pass
+//[aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//[aarch64] error-pattern: `-Z instrument-mcount-fentry` is only supported on x86 targets
+
+#![feature(no_core)]
+#![no_core]
+#![crate_type = "lib"]
@@ -0,0 +1,16 @@
+// build-fail
+
+#![feature(core_intrinsics)]
+#![crate_type = "rlib"]
+
+use std::intrinsics::{unordered_atomic_copy_memory, unordered_atomic_set_memory};
+
+pub unsafe fn copy_odd_size(dst: *mut [u8; 3], src: *const [u8; 3]) {
+    unordered_atomic_copy_memory(dst, src, 1)
+    //~^ ERROR `unordered_atomic_copy_memory` intrinsic: `[u8; 3]` with size 3 and alignment 1
+}
+
+pub unsafe fn set_underaligned(dst: *mut [u16; 2]) {
+    unordered_atomic_set_memory(dst, 0, 1)
+    //~^ ERROR `unordered_atomic_set_memory` intrinsic: `[u16; 2]` with size 4 and alignment 2
+}
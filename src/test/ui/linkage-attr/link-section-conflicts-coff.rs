@@ -0,0 +1,20 @@
+// only-windows
+
+#![allow(dead_code)]
+#![deny(conflicting_link_section)]
+
+// COFF linkers group `.CRT$XCU` into `.CRT`, and `.rdata$counter` into `.rdata`.
+#[link_section = ".CRT$XCU"]
+static INIT: extern "C" fn() = init;
+
+extern "C" fn init() {}
+
+#[link_section = ".rdata$counter"]
+static mut COUNTER: u32 = 0; //~ ERROR mutable statics cannot be placed
+//~| WARN previously accepted
+
+#[link_section = ".data$code"]
+fn code() {} //~ ERROR functions cannot be placed
+//~| WARN previously accepted
+
+fn main() {}
@@ -0,0 +1,49 @@
+// only-linux
+
+#![feature(thread_local)]
+#![allow(dead_code)]
+#![deny(conflicting_link_section)]
+
+use std::sync::atomic::AtomicU32;
+
+#[link_section = ".bss.zeroed"]
+static ZEROED: [u8; 4] = [0; 4];
+
+#[link_section = ".bss.nonzero"]
+static NONZERO: u32 = 1; //~ ERROR must be all zeroes
+//~| WARN previously accepted
+
+#[link_section = ".rodata.counter"]
+static mut COUNTER: u32 = 0; //~ ERROR mutable statics cannot be placed
+//~| WARN previously accepted
+
+#[link_section = ".rodata.atomic"]
+static ATOMIC: AtomicU32 = AtomicU32::new(0); //~ ERROR mutable statics cannot be placed
+//~| WARN previously accepted
+
+#[thread_local]
+#[link_section = ".data.tls"]
+static mut TLS: u32 = 0; //~ ERROR thread-local statics must be placed
+//~| WARN previously accepted
+
+#[link_section = ".tdata.plain"]
+static PLAIN: u32 = 0; //~ ERROR only thread-local statics
+//~| WARN previously accepted
+
+#[thread_local]
+#[link_section = ".tbss.tls"]
+static mut TLS_ZEROED: u32 = 0;
+
+#[link_section = ".rodata.code"]
+fn code() {} //~ ERROR functions cannot be placed
+//~| WARN previously accepted
+
+struct S;
+
+impl S {
+    #[link_section = ".data.method"]
+    fn method(&self) {} //~ ERROR functions cannot be placed
+    //~| WARN previously accepted
+}
+
+fn main() {}
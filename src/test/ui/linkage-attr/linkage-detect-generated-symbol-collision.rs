@@ -0,0 +1,16 @@
+// Checks that an item taking the symbol name of a compiler-generated value is
+// reported as an error rather than causing an ICE.
+
+// build-fail
+// compile-flags: -C codegen-units=1 -C overflow-checks=on -Z deterministic-ir-names
+
+#![crate_type = "lib"]
+
+#[export_name = "str.0"]
+pub static COLLISION: u8 = 0;
+//~^ ERROR symbol `str.0` is already defined
+
+// The overflow check needs a string constant for its panic message.
+pub fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
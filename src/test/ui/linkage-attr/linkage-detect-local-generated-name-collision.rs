@@ -6,7 +6,8 @@ mod dep1 {
     extern {
         #[linkage="external"]
         #[no_mangle]
-        pub static collision: *const i32; //~ ERROR symbol `collision` is already defined
+        pub static collision: *const i32;
+        //~^ ERROR symbol `_rust_extern_with_linkage_collision` is already defined
     }
 }
 
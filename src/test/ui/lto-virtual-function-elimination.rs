@@ -0,0 +1,50 @@
+// run-pass
+// compile-flags: -Clto=fat -Zvirtual-function-elimination -O
+// no-prefer-dynamic
+
+// Calls through trait objects keep working when vtables carry type metadata
+// and are loaded through checked loads, including for drop glue and for
+// entries that are never called.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+trait Shape {
+    fn area(&self) -> u32;
+    fn unused(&self) -> u32 {
+        0
+    }
+}
+
+struct Square(u32);
+struct Rect(u32, u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> u32 {
+        self.0 * self.1
+    }
+}
+
+impl Drop for Rect {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn total(shapes: &[Box<dyn Shape>]) -> u32 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+
+fn main() {
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(3)), Box::new(Rect(2, 5))];
+    assert_eq!(total(&shapes), 19);
+    drop(shapes);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+}
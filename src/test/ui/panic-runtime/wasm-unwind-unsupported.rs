@@ -0,0 +1,9 @@
+// compile-flags: --crate-type lib --target wasm32-unknown-unknown -C panic=unwind
+// needs-llvm-components: webassembly
+// check-pass
+
+// Wasm can't unwind without the exception-handling proposal, so asking for
+// `panic=unwind` falls back to aborting with a warning.
+
+#![feature(no_core)]
+#![no_core]
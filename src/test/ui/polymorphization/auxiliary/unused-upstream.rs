@@ -0,0 +1,8 @@
+// compile-flags:-Zpolymorphize=on
+#![crate_type = "rlib"]
+
+pub fn unused<T>() {}
+
+pub fn used<T: Default>() -> T {
+    Default::default()
+}
@@ -0,0 +1,28 @@
+// build-fail
+// compile-flags:-Zpolymorphize=on
+// aux-build:unused-upstream.rs
+#![crate_type = "lib"]
+#![feature(rustc_attrs)]
+
+// This test checks that generic parameters only passed to an upstream function
+// which doesn't use them are considered unused.
+
+extern crate unused_upstream;
+
+#[rustc_polymorphize_error]
+pub fn calls_unused<T>() {
+    //~^ ERROR item has unused generic parameters
+    unused_upstream::unused::<T>();
+}
+
+#[rustc_polymorphize_error]
+pub fn calls_used<T: Default>() {
+    let _ = unused_upstream::used::<T>();
+}
+
+pub fn caller() {
+    calls_unused::<u32>();
+    calls_unused::<u64>();
+    calls_used::<u32>();
+    calls_used::<u64>();
+}
@@ -1,15 +1,19 @@
-#![feature(naked_functions)]
+#![feature(asm, naked_functions)]
 
 #[track_caller] //~ ERROR cannot use `#[track_caller]` with `#[naked]`
 #[naked]
-fn f() {}
+extern "C" fn f() {
+    unsafe { asm!("", options(noreturn)) }
+}
 
 struct S;
 
 impl S {
     #[track_caller] //~ ERROR cannot use `#[track_caller]` with `#[naked]`
     #[naked]
-    fn g() {}
+    extern "C" fn g() {
+        unsafe { asm!("", options(noreturn)) }
+    }
 }
 
 extern "Rust" {
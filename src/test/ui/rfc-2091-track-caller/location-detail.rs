@@ -0,0 +1,40 @@
+// run-pass
+// revisions: none file line-column
+//[none] compile-flags: -Zlocation-detail=none
+//[file] compile-flags: -Zlocation-detail=file
+//[line-column] compile-flags: -Zlocation-detail=line,column
+
+#![feature(const_caller_location, const_fn)]
+
+use std::panic::Location;
+
+#[inline(never)]
+#[track_caller]
+fn codegen_caller_loc() -> &'static Location<'static> {
+    Location::caller()
+}
+
+#[track_caller]
+const fn const_caller_loc() -> &'static Location<'static> {
+    Location::caller()
+}
+
+const CONST_LOC: &Location<'static> = const_caller_loc();
+
+fn check(loc: &Location<'_>, line: u32, column: u32) {
+    let (file, line, column) = if cfg!(none) {
+        ("<redacted>", 0, 0)
+    } else if cfg!(file) {
+        (file!(), 0, 0)
+    } else {
+        ("<redacted>", line, column)
+    };
+    assert_eq!(loc.file(), file);
+    assert_eq!(loc.line(), line);
+    assert_eq!(loc.column(), column);
+}
+
+fn main() {
+    check(codegen_caller_loc(), 38, 11);
+    check(CONST_LOC, 22, 39);
+}
@@ -0,0 +1,21 @@
+// Links against a function of a system DLL that std does not link to, without
+// any import library for it, and calls it. On x86 the function is stdcall, so
+// the call references its decorated name.
+
+// run-pass
+// only-windows
+
+#![feature(raw_dylib)]
+//~^ WARN the feature `raw_dylib` is incomplete
+
+#[link(name = "version", kind = "raw-dylib")]
+extern "system" {
+    fn GetFileVersionInfoSizeW(filename: *const u16, handle: *mut u32) -> u32;
+}
+
+fn main() {
+    let name: Vec<u16> = "does-not-exist.dll\0".encode_utf16().collect();
+    let mut handle = 0;
+    let size = unsafe { GetFileVersionInfoSizeW(name.as_ptr(), &mut handle) };
+    assert_eq!(size, 0);
+}
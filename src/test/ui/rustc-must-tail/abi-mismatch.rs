@@ -0,0 +1,19 @@
+// build-fail
+// Calls in tail position of a `#[rustc_must_tail]` function have to be able
+// to become guaranteed tail calls.
+
+#![feature(rustc_attrs)]
+
+fn two(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[rustc_must_tail]
+fn one(x: u32) -> u32 {
+    two(x, 1)
+    //~^ ERROR cannot emit a guaranteed tail call: the callee's ABI doesn't match the caller's
+}
+
+fn main() {
+    one(1);
+}
@@ -0,0 +1,7 @@
+// Entries of `-C target-feature` that LLVM would silently ignore are warned about.
+
+// check-pass
+// only-x86_64
+// compile-flags: -C target-feature=+sse2,-not-a-feature,avx
+
+fn main() {}
@@ -0,0 +1,6 @@
+// compile-flags: -C code-model=tiny -C relocation-model=ropi --target x86_64-unknown-linux-gnu
+// error-pattern: error: `-C code-model=tiny` is not supported on the `x86_64` architecture
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
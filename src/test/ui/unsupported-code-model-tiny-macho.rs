@@ -0,0 +1,7 @@
+// compile-flags: -C code-model=tiny --target aarch64-apple-darwin
+// needs-llvm-components: aarch64
+// error-pattern: error: `-C code-model=tiny` is only supported for ELF targets
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -135,7 +135,6 @@ const PERMITTED_DEPENDENCIES: &[&str] = &[
     "ppv-lite86",
     "proc-macro2",
     "psm",
-    "punycode",
     "quick-error",
     "quote",
     "rand",